@@ -1,24 +1,166 @@
 // sentiric-observer/src/adapters/docker.rs
+use crate::adapters::docker_filter::{ContainerFilter, StreamSelection};
+use crate::adapters::normalize::LineOrigin;
 use crate::core::domain::{LogRecord, ResourceContext};
 use crate::ports::LogIngestor;
-use crate::utils::parser;
+use crate::utils::multiline::{JoinedRecord, MultilineAssembler, MultilineRules};
 use anyhow::Result;
 use async_trait::async_trait;
-use bollard::container::{ListContainersOptions, LogsOptions};
+use bollard::container::{InspectContainerOptions, ListContainersOptions, LogsOptions};
+use bollard::models::EventMessage;
+use bollard::system::EventsOptions;
 use bollard::Docker;
+use chrono::{DateTime, Utc};
 use futures_util::StreamExt;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
 use tracing::{error, info, warn};
 
+/// Log akışını tetikleyen/sonlandıran container olayları (`destroy` yalnızca temizlik içindir).
+const LIFECYCLE_ACTIONS: [&str; 5] = ["start", "die", "restart", "rename", "destroy"];
+/// Container bazında çok satırlı kayıt başlangıç desenini değiştiren etiket.
+const MULTILINE_START_LABEL: &str = "sentiric.multiline.start";
+/// Olay akışı koptuğunda yeniden bağlanmadan önceki bekleme.
+const EVENTS_RETRY_SECS: u64 = 5;
+
+/// Ham (SUTS olmayan) loglarda resource alanlarını dolduran, attach anında bir kez
+/// `inspect` ile okunan container bilgileri.
+struct ContainerMeta {
+    id: String,
+    name: String,
+    image: Option<String>,
+    image_tag: Option<String>,
+    compose_project: Option<String>,
+    compose_service: Option<String>,
+    labels: HashMap<String, String>,
+    service_name: String,
+    service_version: String,
+    service_env: String,
+    /// Resource dışı hedeflere eşlenen etiketler (ör. `team=com.acme.team`)
+    extra: HashMap<String, String>,
+}
+
+impl ContainerMeta {
+    fn new(
+        id: &str,
+        name: &str,
+        image: Option<String>,
+        labels: &HashMap<String, String>,
+        mappings: &[(String, String)],
+    ) -> Self {
+        let image_tag = image.as_deref().map(image_tag);
+        let mut meta = Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            service_name: name.to_string(),
+            service_version: image_tag.clone().unwrap_or_else(|| "unknown".to_string()),
+            service_env: "production".to_string(),
+            image,
+            image_tag,
+            compose_project: labels.get("com.docker.compose.project").cloned(),
+            compose_service: labels.get("com.docker.compose.service").cloned(),
+            labels: labels.clone(),
+            extra: HashMap::new(),
+        };
+        for (target, label) in mappings {
+            let Some(value) = labels.get(label).filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            match target.as_str() {
+                "service.name" => meta.service_name = value.clone(),
+                "service.version" => meta.service_version = value.clone(),
+                "service.env" => meta.service_env = value.clone(),
+                _ => {
+                    meta.extra.insert(target.clone(), value.clone());
+                }
+            }
+        }
+        meta
+    }
+
+    fn resource(&self, host_name: &str) -> ResourceContext {
+        ResourceContext {
+            service_name: self.service_name.clone(),
+            service_version: self.service_version.clone(),
+            service_env: self.service_env.clone(),
+            host_name: Some(host_name.to_string()),
+        }
+    }
+
+    /// Container satırlarının normalizasyonunda kullanılan kaynak bilgisi.
+    fn origin(&self, host_name: &str, tenant_id: &str) -> LineOrigin {
+        let mut attributes = HashMap::new();
+        self.enrich(&mut attributes);
+        LineOrigin {
+            resource: self.resource(host_name),
+            attributes,
+            tenant_id: tenant_id.to_string(),
+        }
+    }
+
+    /// Container kimliğini kayda ekler; uygulamanın kendi yazdığı alanlar ezilmez.
+    fn enrich(&self, attributes: &mut HashMap<String, Value>) {
+        let mut put = |key: &str, value: Option<&String>| {
+            if let Some(value) = value {
+                attributes
+                    .entry(key.to_string())
+                    .or_insert_with(|| Value::String(value.clone()));
+            }
+        };
+        put("container.id", Some(&self.id));
+        put("container.name", Some(&self.name));
+        put("container.image", self.image.as_ref());
+        put("container.image_tag", self.image_tag.as_ref());
+        put("compose.project", self.compose_project.as_ref());
+        put("compose.service", self.compose_service.as_ref());
+        for (key, value) in &self.extra {
+            put(key, Some(value));
+        }
+    }
+}
+
+/// `registry:5000/org/app:1.2.3@sha256:...` -> `1.2.3`; etiket yoksa `latest`.
+fn image_tag(image: &str) -> String {
+    let reference = image.split('@').next().unwrap_or(image);
+    let last_segment = reference.rsplit('/').next().unwrap_or(reference);
+    match last_segment.rsplit_once(':') {
+        Some((_, tag)) if !tag.is_empty() => tag.to_string(),
+        _ => "latest".to_string(),
+    }
+}
+
+/// Takip edilen bir container'ın log akışı görevi.
+struct ContainerStream {
+    meta: Arc<ContainerMeta>,
+    generation: u64,
+    /// Akışın açıldığı an (unix sn); yeniden oynatılan eski `start` olaylarını ayırt eder.
+    attached_at: i64,
+    task: AbortHandle,
+}
+
+#[derive(Clone)]
 pub struct DockerIngestor {
     docker: Docker,
     tx: Sender<LogRecord>,
     node_name: String,
-    monitored_containers: Arc<Mutex<HashMap<String, String>>>,
+    monitored_containers: Arc<Mutex<HashMap<String, ContainerStream>>>,
+    /// Container başına son okunan log satırının Docker zaman damgası. Akış yeniden açılırken
+    /// `since` olarak kullanılır (saniye hassasiyeti); o saniyede zaten okunmuş satırlar atlanır.
+    resume_points: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+    /// Kural gereği dışlanan container ID'leri; `TARGET_EXCLUDED` her resync'te tekrarlanmasın.
+    excluded: Arc<Mutex<HashSet<String>>>,
+    generations: Arc<AtomicU64>,
+    /// `(hedef, etiket)`: hedef `service.name` / `service.version` / `service.env`
+    /// ise resource alanı, değilse aynı isimli attribute olur.
+    label_mappings: Arc<Vec<(String, String)>>,
+    filter: Arc<ContainerFilter>,
+    /// `None` ise her satır ayrı kayıttır.
+    multiline: Arc<Option<MultilineRules>>,
     tenant_id: String, // [ARCH-COMPLIANCE]
 }
 
@@ -28,6 +170,9 @@ impl DockerIngestor {
         tx: Sender<LogRecord>,
         node_name: String,
         tenant_id: String,
+        label_mappings: Vec<(String, String)>,
+        filter: ContainerFilter,
+        multiline: Option<MultilineRules>,
     ) -> Result<Self> {
         let docker = Docker::connect_with_unix(socket_path, 120, bollard::API_DEFAULT_VERSION)
             .or_else(|_| Docker::connect_with_local_defaults())
@@ -37,152 +182,414 @@ impl DockerIngestor {
             tx,
             node_name,
             monitored_containers: Arc::new(Mutex::new(HashMap::new())),
+            resume_points: Arc::new(Mutex::new(HashMap::new())),
+            excluded: Arc::new(Mutex::new(HashSet::new())),
+            generations: Arc::new(AtomicU64::new(0)),
+            label_mappings: Arc::new(label_mappings),
+            filter: Arc::new(filter),
+            multiline: Arc::new(multiline),
             tenant_id,
         })
     }
+}
 
-    fn process_line(&self, line: String, container_name: &str, stream_type: &str) -> LogRecord {
-        let cleaned_line = parser::clean_ansi(&line);
-
-        if let Ok(json_val) = serde_json::from_str::<Value>(&cleaned_line) {
-            if let Some(map) = json_val.as_object() {
-                let schema = map
-                    .get("schema_v")
-                    .and_then(Value::as_str)
-                    .unwrap_or("1.0.0");
-                let severity = map
-                    .get("severity")
-                    .or_else(|| map.get("level"))
-                    .and_then(Value::as_str)
-                    .unwrap_or(if stream_type == "stderr" {
-                        "ERROR"
-                    } else {
-                        "INFO"
-                    })
-                    .to_uppercase();
-
-                let msg = map
-                    .get("message")
-                    .or_else(|| map.get("msg"))
-                    .and_then(Value::as_str)
-                    .unwrap_or(&cleaned_line)
-                    .to_string();
-
-                let ts = map
-                    .get("ts")
-                    .or_else(|| map.get("time"))
-                    .or_else(|| map.get("timestamp"))
-                    .and_then(Value::as_str)
-                    .unwrap_or("")
-                    .to_string();
-
-                let trace_id = map
-                    .get("trace_id")
-                    .and_then(Value::as_str)
-                    .map(|s| s.to_string());
-                let event = map
-                    .get("event")
-                    .and_then(Value::as_str)
-                    .unwrap_or("LOG_EVENT")
-                    .to_string();
-
-                let mut attributes = HashMap::new();
-                if let Some(attrs) = map.get("attributes").and_then(Value::as_object) {
-                    for (k, v) in attrs {
-                        attributes.insert(k.clone(), v.clone());
+/// `timestamps=true` ile gelen satırın başındaki RFC3339Nano zaman damgasını ayırır.
+fn split_timestamp(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    let Some((prefix, rest)) = line.split_once(' ') else {
+        return match DateTime::parse_from_rfc3339(line) {
+            Ok(ts) => (Some(ts.with_timezone(&Utc)), ""),
+            Err(_) => (None, line),
+        };
+    };
+    match DateTime::parse_from_rfc3339(prefix) {
+        Ok(ts) => (Some(ts.with_timezone(&Utc)), rest),
+        Err(_) => (None, line),
+    }
+}
+
+/// Olay aktöründeki container adı (`/` öneki olmadan); yoksa kısa id.
+fn actor_name(attributes: &HashMap<String, String>, id: &str) -> String {
+    attributes
+        .get("name")
+        .map(|n| n.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| id.chars().take(12).collect())
+}
+
+impl DockerIngestor {
+    /// Çalışan container'ları listeleyip henüz takip edilmeyenlere log akışı açar.
+    /// Açılışta ve olay akışı her yeniden bağlandığında (kaçan olaylar için) çağrılır.
+    async fn sync_running(&self, default_since: i64) {
+        // Yalnızca çalışan container'lar; durmuş olanlar `start` olayıyla yakalanır.
+        let options = ListContainersOptions::<String> {
+            all: false,
+            ..Default::default()
+        };
+        match self.docker.list_containers(Some(options)).await {
+            Ok(containers) => {
+                for container in containers {
+                    let id = container.id.unwrap_or_default();
+                    let name = container
+                        .names
+                        .as_ref()
+                        .and_then(|names| names.first())
+                        .map(|s| s.trim_start_matches('/').to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    if id.is_empty() {
+                        continue;
                     }
-                } else {
-                    for (k, v) in map {
-                        if ![
-                            "schema_v", "severity", "level", "message", "msg", "ts", "time",
-                            "trace_id", "event", "resource",
-                        ]
-                        .contains(&k.as_str())
-                        {
-                            attributes.insert(k.clone(), v.clone());
-                        }
+                    let meta = self.inspect(&id, &name).await;
+                    if let Some(selection) = self.select(&meta).await {
+                        self.attach(meta, selection, default_since, None).await;
                     }
                 }
+            }
+            Err(e) => error!(event="DOCKER_API_ERR", error=%e, "Docker API Hatası"),
+        }
+    }
+
+    /// Container etiketlerini ve imajını okur. `inspect` başarısız olursa yalnızca adla devam edilir.
+    async fn inspect(&self, id: &str, name: &str) -> Arc<ContainerMeta> {
+        let (image, labels) = match self
+            .docker
+            .inspect_container(id, None::<InspectContainerOptions>)
+            .await
+        {
+            Ok(details) => {
+                let config = details.config.unwrap_or_default();
+                (config.image, config.labels.unwrap_or_default())
+            }
+            Err(e) => {
+                warn!(event="DOCKER_INSPECT_ERR", container=%name, error=%e, "Container Inspect Hatası");
+                (None, HashMap::new())
+            }
+        };
+        Arc::new(ContainerMeta::new(
+            id,
+            name,
+            image,
+            &labels,
+            &self.label_mappings,
+        ))
+    }
 
-                let resource = if let Some(r) = map.get("resource").and_then(Value::as_object) {
-                    ResourceContext {
-                        service_name: r
-                            .get("service.name")
-                            .and_then(Value::as_str)
-                            .unwrap_or(container_name)
-                            .to_string(),
-                        service_version: r
-                            .get("service.version")
-                            .and_then(Value::as_str)
-                            .unwrap_or("unknown")
-                            .to_string(),
-                        service_env: r
-                            .get("service.env")
-                            .and_then(Value::as_str)
-                            .unwrap_or("production")
-                            .to_string(),
-                        host_name: Some(self.node_name.clone()),
+    /// Container kurallarına göre okunacak akışlar; dışlandıysa `None`.
+    /// Dışlanma container başına yalnızca ilk kez loglanır.
+    async fn select(&self, meta: &ContainerMeta) -> Option<StreamSelection> {
+        let selection = self
+            .filter
+            .evaluate(&meta.name, meta.image.as_deref(), &meta.labels);
+        let mut excluded = self.excluded.lock().await;
+        if selection.is_none() {
+            if excluded.insert(meta.id.clone()) {
+                info!(event="TARGET_EXCLUDED", container=%meta.name, "🚫 Container kural gereği izlenmiyor");
+            }
+        } else {
+            // Yeniden adlandırılıp kurala uyan container tekrar dışlanırsa yine loglansın.
+            excluded.remove(&meta.id);
+        }
+        selection
+    }
+
+    /// Genel çok satır kuralları; container `sentiric.multiline.start` etiketiyle
+    /// kendi başlangıç desenini verebilir.
+    fn multiline_rules(&self, meta: &ContainerMeta) -> Option<MultilineRules> {
+        let rules = self.multiline.as_ref().as_ref()?;
+        let Some(pattern) = meta.labels.get(MULTILINE_START_LABEL) else {
+            return Some(rules.clone());
+        };
+        match rules.with_start(pattern) {
+            Ok(custom) => Some(custom),
+            Err(e) => {
+                warn!(event="MULTILINE_PATTERN_INVALID", container=%meta.name, error=%e, "⚠️ Geçersiz multiline deseni, varsayılan kullanılıyor");
+                Some(rules.clone())
+            }
+        }
+    }
+
+    /// Birleştirilmiş kayıtları akışa yazar; kanal kapandıysa `false`.
+    async fn emit(
+        &self,
+        joined: Vec<JoinedRecord>,
+        origin: &LineOrigin,
+        selection: StreamSelection,
+    ) -> bool {
+        for entry in joined {
+            let mut record =
+                origin.normalize(&entry.text, entry.stream, entry.ts, entry.observed_at);
+            if entry.lines > 1 {
+                record.smart_tags.push("STACKTRACE".to_string());
+            }
+            if !selection.accepts(&record.severity) {
+                continue;
+            }
+            if self.tx.send(record).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Takip edilen container'ın son bilinen bilgileri.
+    async fn known_meta(&self, id: &str) -> Option<Arc<ContainerMeta>> {
+        let monitored = self.monitored_containers.lock().await;
+        monitored.get(id).map(|s| s.meta.clone())
+    }
+
+    /// Container için log akışı açar. `replace_before` verilirse o andan önce açılmış
+    /// (ör. container ölmeden önceki) akışın yerine yenisi açılır; verilmezse mevcut
+    /// canlı akışa dokunulmaz.
+    async fn attach(
+        &self,
+        meta: Arc<ContainerMeta>,
+        selection: StreamSelection,
+        default_since: i64,
+        replace_before: Option<i64>,
+    ) {
+        let id = meta.id.as_str();
+        let mut monitored = self.monitored_containers.lock().await;
+        if let Some(existing) = monitored.get(id) {
+            let alive = !existing.task.is_finished();
+            let current = match replace_before {
+                Some(t) => existing.attached_at >= t && existing.meta.name == meta.name,
+                None => true,
+            };
+            if alive && current {
+                return;
+            }
+            existing.task.abort();
+        }
+
+        let resume = self.resume_points.lock().await.get(id).copied();
+        let since = resume.map_or(default_since, |ts| ts.timestamp());
+        let generation = self.generations.fetch_add(1, Ordering::Relaxed);
+        info!(event="TARGET_LOCKED", container=%meta.name, service=%meta.service_name, since=since, "✨ Yeni Hedef Kilitlendi");
+
+        let ingestor = self.clone();
+        let task_meta = meta.clone();
+        let task = tokio::spawn(async move {
+            ingestor
+                .follow_logs(task_meta, selection, since, resume, generation)
+                .await;
+        });
+        monitored.insert(
+            id.to_string(),
+            ContainerStream {
+                meta,
+                generation,
+                attached_at: chrono::Utc::now().timestamp(),
+                task: task.abort_handle(),
+            },
+        );
+    }
+
+    async fn follow_logs(
+        self,
+        meta: Arc<ContainerMeta>,
+        selection: StreamSelection,
+        since: i64,
+        resume: Option<DateTime<Utc>>,
+        generation: u64,
+    ) {
+        let container_id = meta.id.as_str();
+        let container_name = meta.name.as_str();
+        let opts = LogsOptions::<String> {
+            follow: true,
+            stdout: selection.stdout,
+            stderr: selection.stderr,
+            since,
+            timestamps: true,
+            ..Default::default()
+        };
+
+        let mut stream = self.docker.logs(container_id, Some(opts));
+        let mut assembler = MultilineAssembler::new(self.multiline_rules(&meta));
+        let origin = meta.origin(&self.node_name, &self.tenant_id);
+
+        loop {
+            // Bekleyen çok satırlı kayıt varsa, yeni satır gelmese de süresi dolunca yazılır.
+            let next = match assembler.next_deadline() {
+                Some(deadline) => tokio::select! {
+                    item = stream.next() => item,
+                    _ = tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)) => {
+                        let expired = assembler.flush_expired(std::time::Instant::now());
+                        if !self.emit(expired, &origin, selection).await {
+                            break;
+                        }
+                        continue;
+                    }
+                },
+                None => stream.next().await,
+            };
+            let Some(log_result) = next else {
+                break;
+            };
+
+            match log_result {
+                Ok(output) => {
+                    let (msg, stream_type) = match output {
+                        bollard::container::LogOutput::StdOut { message } => (message, "stdout"),
+                        bollard::container::LogOutput::StdErr { message } => (message, "stderr"),
+                        _ => (bytes::Bytes::new(), "unknown"),
+                    };
+
+                    let received = std::time::Instant::now();
+                    let mut joined = Vec::new();
+                    let mut newest = None;
+                    for line in String::from_utf8_lossy(&msg).lines() {
+                        let (ts, line) = split_timestamp(line);
+                        // `since` saniye hassasiyetinde olduğundan, önceki akışın okuduğu
+                        // satırlar yeniden gelir; zaman damgasıyla ayıklanır.
+                        if ts.zip(resume).is_some_and(|(ts, resume)| ts <= resume) {
+                            continue;
+                        }
+                        newest = ts.or(newest);
+                        joined.extend(assembler.push(stream_type, line, ts, received));
+                    }
+                    if let Some(ts) = newest {
+                        self.resume_points
+                            .lock()
+                            .await
+                            .insert(container_id.to_string(), ts);
                     }
-                } else {
-                    ResourceContext {
-                        service_name: container_name.to_string(),
-                        service_version: "unknown".to_string(),
-                        service_env: "production".to_string(),
-                        host_name: Some(self.node_name.clone()),
+                    if !self.emit(joined, &origin, selection).await {
+                        break;
                     }
-                };
-
-                let mut record = LogRecord {
-                    schema_v: schema.to_string(),
-                    ts: if ts.is_empty() {
-                        chrono::Utc::now().to_rfc3339()
-                    } else {
-                        ts
-                    },
-                    severity,
-                    tenant_id: self.tenant_id.clone(),
-                    resource,
-                    trace_id,
-                    span_id: None,
-                    event,
-                    message: msg,
-                    attributes,
-                    smart_tags: vec![],
-                    _idx: 0.0,
-                };
-
-                record.sanitize_and_enrich();
-                return record;
+                }
+                Err(e) => {
+                    warn!(event="DOCKER_STREAM_ERR", container=%container_name, error=%e, "Stream Hatası");
+                    break;
+                }
             }
         }
+        self.emit(assembler.flush_all(), &origin, selection).await;
 
-        // [ARCH-COMPLIANCE FIX]: SUTS Formatında Olmayan Altyapı Loglarının Susturulması
-        // stdout -> DEBUG (Gürültü önleme), stderr -> WARN (Error yerine Warn yapıyoruz ki kırmızılar patlamasın)
-        let mut raw_record = LogRecord {
-            schema_v: "1.0.0".to_string(),
-            ts: chrono::Utc::now().to_rfc3339(),
-            severity: if stream_type == "stderr" {
-                "WARN".to_string()
-            } else {
-                "DEBUG".to_string()
+        warn!(event="TARGET_LOST", container=%container_name, "💀 Bağlantı Koptu");
+        let mut monitored = self.monitored_containers.lock().await;
+        if monitored
+            .get(container_id)
+            .is_some_and(|s| s.generation == generation)
+        {
+            monitored.remove(container_id);
+        }
+    }
+
+    async fn handle_event(&self, event: EventMessage) {
+        let action = event.action.unwrap_or_default();
+        let actor = event.actor.unwrap_or_default();
+        let id = actor.id.unwrap_or_default();
+        let attributes = actor.attributes.unwrap_or_default();
+        let name = actor_name(&attributes, &id);
+        if id.is_empty() {
+            return;
+        }
+        let time = event.time.unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+        let meta = match action.as_str() {
+            "start" | "rename" => self.inspect(&id, &name).await,
+            _ => match self.known_meta(&id).await {
+                Some(meta) => meta,
+                None => Arc::new(ContainerMeta::new(
+                    &id,
+                    &name,
+                    attributes.get("image").cloned(),
+                    &attributes,
+                    &self.label_mappings,
+                )),
             },
+        };
+
+        // Dışlanan container'ın olayları da akışa girmez; yeni adıyla dışlanan akış kapatılır.
+        let Some(selection) = self.select(&meta).await else {
+            if let Some(stream) = self.monitored_containers.lock().await.remove(&id) {
+                stream.task.abort();
+            }
+            if action == "destroy" {
+                self.excluded.lock().await.remove(&id);
+            }
+            return;
+        };
+
+        // Kayıt, container'ın yeni akışındaki ilk satırlardan önce kanala girsin.
+        self.emit_lifecycle(&action, &meta, &attributes, event.time_nano)
+            .await;
+
+        match action.as_str() {
+            "start" | "rename" => self.attach(meta, selection, time, Some(time)).await,
+            // `die` sonrası Docker log akışını kendisi kapatır; kalan satırlar okunup görev biter.
+            "destroy" => {
+                if let Some(stream) = self.monitored_containers.lock().await.remove(&id) {
+                    stream.task.abort();
+                }
+                self.resume_points.lock().await.remove(&id);
+            }
+            _ => {}
+        }
+    }
+
+    async fn emit_lifecycle(
+        &self,
+        action: &str,
+        meta: &ContainerMeta,
+        attributes: &HashMap<String, String>,
+        time_nano: Option<i64>,
+    ) {
+        let name = meta.name.as_str();
+        let exit_code = attributes.get("exitCode").map(String::as_str);
+        let old_name = attributes.get("oldName").map(|n| n.trim_start_matches('/'));
+
+        let message = match action {
+            "start" => format!("Container başlatıldı: {}", name),
+            "die" => format!(
+                "Container durdu: {} (exit {})",
+                name,
+                exit_code.unwrap_or("?")
+            ),
+            "restart" => format!("Container yeniden başlatıldı: {}", name),
+            "rename" => format!(
+                "Container yeniden adlandırıldı: {} -> {}",
+                old_name.unwrap_or("?"),
+                name
+            ),
+            "destroy" => format!("Container silindi: {}", name),
+            other => format!("Container olayı ({}): {}", other, name),
+        };
+        let severity = if action == "die" && exit_code.is_some_and(|c| c != "0") {
+            "WARN"
+        } else {
+            "INFO"
+        };
+
+        let mut record_attributes = HashMap::new();
+        record_attributes.insert("container.action".to_string(), Value::from(action));
+        if let Some(code) = exit_code.and_then(|c| c.parse::<i64>().ok()) {
+            record_attributes.insert("container.exit_code".to_string(), Value::from(code));
+        }
+        if let Some(old_name) = old_name {
+            record_attributes.insert("container.old_name".to_string(), Value::from(old_name));
+        }
+        meta.enrich(&mut record_attributes);
+
+        let ts = time_nano
+            .map(chrono::DateTime::from_timestamp_nanos)
+            .unwrap_or_else(chrono::Utc::now)
+            .to_rfc3339();
+
+        let mut record = LogRecord {
+            schema_v: "1.0.0".to_string(),
+            ts,
+            severity: severity.to_string(),
             tenant_id: self.tenant_id.clone(),
-            resource: ResourceContext {
-                service_name: container_name.to_string(),
-                service_version: "unknown".to_string(),
-                service_env: "production".to_string(),
-                host_name: Some(self.node_name.clone()),
-            },
+            resource: meta.resource(&self.node_name),
             trace_id: None,
             span_id: None,
-            event: "RAW_INFRA_LOG".to_string(),
-            message: cleaned_line,
-            attributes: HashMap::new(),
-            smart_tags: vec!["RAW".to_string(), "INFRA_RAW".to_string()],
+            event: "CONTAINER_LIFECYCLE".to_string(),
+            message,
+            attributes: record_attributes,
+            smart_tags: vec!["DOCKER".to_string(), "LIFECYCLE".to_string()],
             _idx: 0.0,
         };
-        raw_record.sanitize_and_enrich();
-        raw_record
+        record.sanitize_and_enrich();
+        let _ = self.tx.send(record).await;
     }
 }
 
@@ -191,109 +598,46 @@ impl LogIngestor for DockerIngestor {
     async fn start(&self) -> Result<()> {
         info!(event="DOCKER_INGESTOR_START", node=%self.node_name, "🐳 Docker Ingestor: Başlatıldı");
 
-        let mut last_scan_time = chrono::Utc::now().timestamp();
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+        // Olay akışı koparsa kaldığı yerden (son olayın zamanı) yeniden abone olunur.
+        let mut since = chrono::Utc::now().timestamp();
 
         loop {
-            interval.tick().await;
-            let current_scan_time = chrono::Utc::now().timestamp();
+            self.sync_running(since).await;
 
-            let options = ListContainersOptions::<String> {
-                all: true,
-                ..Default::default()
+            let mut filters = HashMap::new();
+            filters.insert("type".to_string(), vec!["container".to_string()]);
+            filters.insert(
+                "event".to_string(),
+                LIFECYCLE_ACTIONS.iter().map(|a| a.to_string()).collect(),
+            );
+            let options = EventsOptions::<String> {
+                since: Some(since.to_string()),
+                until: None,
+                filters,
             };
-            match self.docker.list_containers(Some(options)).await {
-                Ok(containers) => {
-                    let mut monitored = self.monitored_containers.lock().await;
-                    for container in containers {
-                        let id = container.id.unwrap_or_default();
-                        let name = container
-                            .names
-                            .as_ref()
-                            .and_then(|names| names.first())
-                            .map(|s| s.trim_start_matches('/').to_string())
-                            .unwrap_or_else(|| "unknown".to_string());
-
-                        if name.contains("observer") {
-                            continue;
-                        }
 
-                        if !monitored.contains_key(&id) && !id.is_empty() {
-                            info!(event="TARGET_LOCKED", container=%name, "✨ Yeni Hedef Kilitlendi");
-                            monitored.insert(id.clone(), name.clone());
-
-                            let docker = self.docker.clone();
-                            let tx = self.tx.clone();
-                            let node = self.node_name.clone();
-                            let monitored_map = self.monitored_containers.clone();
-                            let container_id = id.clone();
-                            let container_name = name.clone();
-                            let tenant_clone = self.tenant_id.clone();
-                            let since = last_scan_time;
-
-                            tokio::spawn(async move {
-                                let opts = LogsOptions::<String> {
-                                    follow: true,
-                                    stdout: true,
-                                    stderr: true,
-                                    since,
-                                    timestamps: false,
-                                    ..Default::default()
-                                };
-
-                                let mut stream = docker.logs(&container_id, Some(opts));
-                                let ingestor = DockerIngestor {
-                                    docker: docker.clone(),
-                                    tx: tx.clone(),
-                                    node_name: node.clone(),
-                                    monitored_containers: monitored_map.clone(),
-                                    tenant_id: tenant_clone,
-                                };
-
-                                while let Some(log_result) = stream.next().await {
-                                    match log_result {
-                                        Ok(output) => {
-                                            let (msg, stream_type) = match output {
-                                                bollard::container::LogOutput::StdOut {
-                                                    message,
-                                                } => (message, "stdout"),
-                                                bollard::container::LogOutput::StdErr {
-                                                    message,
-                                                } => (message, "stderr"),
-                                                _ => (bytes::Bytes::new(), "unknown"),
-                                            };
-
-                                            let text =
-                                                String::from_utf8_lossy(&msg).trim().to_string();
-                                            if !text.is_empty() {
-                                                let record = ingestor.process_line(
-                                                    text,
-                                                    &container_name,
-                                                    stream_type,
-                                                );
-
-                                                if tx.send(record).await.is_err() {
-                                                    break;
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            warn!(event="DOCKER_STREAM_ERR", container=%container_name, error=%e, "Stream Hatası");
-                                            break;
-                                        }
-                                    }
-                                }
-
-                                warn!(event="TARGET_LOST", container=%container_name, "💀 Bağlantı Koptu");
-                                let mut m = monitored_map.lock().await;
-                                m.remove(&container_id);
-                            });
+            let mut events = self.docker.events(Some(options));
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(event) => {
+                        if let Some(time) = event.time {
+                            since = since.max(time);
                         }
+                        self.handle_event(event).await;
+                    }
+                    Err(e) => {
+                        error!(event="DOCKER_EVENTS_ERR", error=%e, "Docker Olay Akışı Hatası");
+                        break;
                     }
                 }
-                Err(e) => error!(event="DOCKER_API_ERR", error=%e, "Docker API Hatası"),
             }
-            last_scan_time = current_scan_time;
+
+            warn!(
+                event = "DOCKER_EVENTS_LOST",
+                retry_secs = EVENTS_RETRY_SECS,
+                "🔌 Docker olay akışı koptu, yeniden bağlanılıyor"
+            );
+            tokio::time::sleep(tokio::time::Duration::from_secs(EVENTS_RETRY_SECS)).await;
         }
     }
 }
@@ -0,0 +1,307 @@
+// src/adapters/docker_filter.rs
+use crate::utils::glob::glob_match;
+use std::collections::HashMap;
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Include,
+    Exclude,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Name(String),
+    Image(String),
+    /// `label=anahtar` (var mı) veya `label=anahtar=glob`
+    Label {
+        key: String,
+        value: Option<String>,
+    },
+    Project(String),
+}
+
+#[derive(Debug, Clone)]
+struct ContainerRule {
+    action: Action,
+    matchers: Vec<Matcher>,
+    stdout: bool,
+    stderr: bool,
+    min_severity: Option<u8>,
+}
+
+/// Bir container'dan okunacak akışlar ve akışa alınacak en düşük log seviyesi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamSelection {
+    pub stdout: bool,
+    pub stderr: bool,
+    pub min_severity: u8,
+}
+
+impl StreamSelection {
+    pub fn accepts(&self, severity: &str) -> bool {
+        severity_rank(severity) >= self.min_severity
+    }
+}
+
+/// Hangi container'ların izleneceğine karar veren sıralı kural listesi. İlk eşleşen
+/// kural kazanır; hiçbiri eşleşmezse container varsayılan ayarlarla izlenir.
+///
+/// `DOCKER_CONTAINER_RULES` sözdizimi (`;` kural, boşluk alan ayırıcıdır):
+/// `exclude name=*observer*; exclude image=envoyproxy/*; include project=sentiric streams=stderr severity=WARN`
+pub struct ContainerFilter {
+    rules: Vec<ContainerRule>,
+    default_min_severity: u8,
+}
+
+impl ContainerFilter {
+    /// Hatalı kurallar uyarı loglanarak atlanır; servis açılışı engellenmez.
+    pub fn parse(rules: &str, default_min_severity: &str) -> Self {
+        let default_min_severity = parse_severity(default_min_severity).unwrap_or_else(|| {
+            warn!(event="DOCKER_RULE_INVALID", value=%default_min_severity, "⚠️ Geçersiz varsayılan seviye, TRACE kullanılıyor");
+            0
+        });
+        let rules = rules
+            .split(';')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .filter_map(|rule| match parse_rule(rule) {
+                Ok(parsed) => Some(parsed),
+                Err(reason) => {
+                    warn!(event="DOCKER_RULE_INVALID", rule=%rule, reason=%reason, "⚠️ Container kuralı atlandı");
+                    None
+                }
+            })
+            .collect();
+        Self {
+            rules,
+            default_min_severity,
+        }
+    }
+
+    /// Container izlenecekse okunacak akışları, dışlandıysa `None` döner.
+    pub fn evaluate(
+        &self,
+        name: &str,
+        image: Option<&str>,
+        labels: &HashMap<String, String>,
+    ) -> Option<StreamSelection> {
+        let rule = self
+            .rules
+            .iter()
+            .find(|rule| rule.matchers.iter().all(|m| m.matches(name, image, labels)));
+
+        match rule {
+            Some(rule) if rule.action == Action::Exclude => None,
+            Some(rule) => Some(StreamSelection {
+                stdout: rule.stdout,
+                stderr: rule.stderr,
+                min_severity: rule.min_severity.unwrap_or(self.default_min_severity),
+            }),
+            None => Some(StreamSelection {
+                stdout: true,
+                stderr: true,
+                min_severity: self.default_min_severity,
+            }),
+        }
+    }
+}
+
+impl Matcher {
+    fn matches(&self, name: &str, image: Option<&str>, labels: &HashMap<String, String>) -> bool {
+        match self {
+            Matcher::Name(pattern) => glob_match(pattern, name),
+            // `image=redis` hem `redis` hem `redis:7` ile eşleşsin diye etiketsiz hali de denenir.
+            Matcher::Image(pattern) => image.is_some_and(|image| {
+                glob_match(pattern, image) || glob_match(pattern, image_repository(image))
+            }),
+            Matcher::Label { key, value } => match (labels.get(key), value) {
+                (Some(actual), Some(pattern)) => glob_match(pattern, actual),
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+            Matcher::Project(pattern) => labels
+                .get("com.docker.compose.project")
+                .is_some_and(|project| glob_match(pattern, project)),
+        }
+    }
+}
+
+fn parse_rule(rule: &str) -> Result<ContainerRule, String> {
+    let mut tokens = rule.split_whitespace();
+    let action = match tokens.next() {
+        Some(a) if a.eq_ignore_ascii_case("include") => Action::Include,
+        Some(a) if a.eq_ignore_ascii_case("exclude") => Action::Exclude,
+        other => return Err(format!("include/exclude bekleniyordu: {:?}", other)),
+    };
+
+    let mut parsed = ContainerRule {
+        action,
+        matchers: Vec::new(),
+        stdout: true,
+        stderr: true,
+        min_severity: None,
+    };
+    for token in tokens {
+        let Some((key, value)) = token.split_once('=') else {
+            return Err(format!("anahtar=değer bekleniyordu: {}", token));
+        };
+        match key {
+            "name" => parsed.matchers.push(Matcher::Name(value.to_string())),
+            "image" => parsed.matchers.push(Matcher::Image(value.to_string())),
+            "project" => parsed.matchers.push(Matcher::Project(value.to_string())),
+            "label" => {
+                let (key, value) = match value.split_once('=') {
+                    Some((k, v)) => (k, Some(v.to_string())),
+                    None => (value, None),
+                };
+                parsed.matchers.push(Matcher::Label {
+                    key: key.to_string(),
+                    value,
+                });
+            }
+            "streams" => {
+                (parsed.stdout, parsed.stderr) = match value {
+                    "stdout" => (true, false),
+                    "stderr" => (false, true),
+                    "all" | "both" => (true, true),
+                    _ => return Err(format!("geçersiz streams: {}", value)),
+                }
+            }
+            "severity" => {
+                parsed.min_severity = Some(
+                    parse_severity(value).ok_or_else(|| format!("geçersiz seviye: {}", value))?,
+                )
+            }
+            _ => return Err(format!("bilinmeyen alan: {}", key)),
+        }
+    }
+    Ok(parsed)
+}
+
+/// `registry:5000/org/app:1.2.3@sha256:...` -> `registry:5000/org/app`
+fn image_repository(image: &str) -> &str {
+    let reference = image.split('@').next().unwrap_or(image);
+    let name_start = reference.rfind('/').map_or(0, |i| i + 1);
+    match reference[name_start..].rfind(':') {
+        Some(i) => &reference[..name_start + i],
+        None => reference,
+    }
+}
+
+fn parse_severity(value: &str) -> Option<u8> {
+    match value.to_uppercase().as_str() {
+        "TRACE" => Some(0),
+        "DEBUG" => Some(1),
+        "INFO" => Some(2),
+        "WARN" | "WARNING" => Some(3),
+        "ERROR" => Some(4),
+        "FATAL" | "CRITICAL" | "PANIC" => Some(5),
+        _ => None,
+    }
+}
+
+/// Kayıttaki seviye metninin sıralaması; bilinmeyen seviyeler INFO sayılır.
+pub fn severity_rank(severity: &str) -> u8 {
+    parse_severity(severity).unwrap_or(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let filter = ContainerFilter::parse(
+            "exclude name=*observer*; include project=sentiric streams=stderr severity=WARN; exclude image=redis",
+            "DEBUG",
+        );
+        let compose = labels(&[("com.docker.compose.project", "sentiric")]);
+
+        assert_eq!(filter.evaluate("sentiric-observer", None, &compose), None);
+        assert_eq!(
+            filter.evaluate("sip-proxy", Some("redis:7"), &compose),
+            Some(StreamSelection {
+                stdout: false,
+                stderr: true,
+                min_severity: 3,
+            })
+        );
+        assert_eq!(
+            filter.evaluate("cache", Some("redis:7"), &HashMap::new()),
+            None
+        );
+        assert_eq!(
+            filter.evaluate("web", Some("nginx"), &HashMap::new()),
+            Some(StreamSelection {
+                stdout: true,
+                stderr: true,
+                min_severity: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn label_matchers_check_presence_or_value() {
+        let filter = ContainerFilter::parse(
+            "exclude label=observer.skip; exclude label=tier=db*",
+            "INFO",
+        );
+        assert_eq!(
+            filter.evaluate("a", None, &labels(&[("observer.skip", "")])),
+            None
+        );
+        assert_eq!(
+            filter.evaluate("b", None, &labels(&[("tier", "db-primary")])),
+            None
+        );
+        assert!(filter
+            .evaluate("c", None, &labels(&[("tier", "web")]))
+            .is_some());
+    }
+
+    #[test]
+    fn invalid_rules_are_skipped() {
+        let filter = ContainerFilter::parse(
+            "drop name=x; include name=a streams=none; include color=red; exclude name=b",
+            "LOUD",
+        );
+        assert_eq!(filter.rules.len(), 1);
+        assert_eq!(filter.default_min_severity, 0);
+        assert_eq!(filter.evaluate("b", None, &HashMap::new()), None);
+    }
+
+    #[test]
+    fn image_repository_strips_tag_and_digest() {
+        assert_eq!(
+            image_repository("registry:5000/org/app:1.2.3@sha256:abc"),
+            "registry:5000/org/app"
+        );
+        assert_eq!(
+            image_repository("registry:5000/org/app"),
+            "registry:5000/org/app"
+        );
+        assert_eq!(image_repository("redis"), "redis");
+    }
+
+    #[test]
+    fn severity_ranking() {
+        let selection = StreamSelection {
+            stdout: true,
+            stderr: true,
+            min_severity: 3,
+        };
+        assert!(selection.accepts("error"));
+        assert!(selection.accepts("WARNING"));
+        assert!(!selection.accepts("INFO"));
+        assert!(!selection.accepts("custom"));
+        assert_eq!(severity_rank("PANIC"), 5);
+    }
+}
@@ -0,0 +1,642 @@
+// src/adapters/file_tail.rs
+use crate::adapters::normalize::LineOrigin;
+use crate::core::domain::{LogRecord, ResourceContext};
+use crate::ports::LogIngestor;
+use crate::utils::glob::expand_paths;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{File, Metadata};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::Sender;
+use tracing::{info, warn};
+
+/// Bir okuma turunda dosya başına okunacak en fazla bayt; yoğun bir dosya diğerlerini bekletmesin.
+const MAX_READ_PER_POLL: usize = 8 * 1024 * 1024;
+/// Satır sonu gelmeden bu boyu aşan satır bölünerek yazılır.
+const MAX_LINE_BYTES: usize = 1024 * 1024;
+/// Okuma konumlarının diske yazılma aralığı.
+const STATE_FLUSH_SECS: u64 = 5;
+
+/// İzlenecek glob deseni; `service_name` verilmezse dosya adının ilk parçası kullanılır.
+#[derive(Debug, Clone)]
+struct TailSource {
+    service_name: Option<String>,
+    pattern: String,
+}
+
+/// Dosyanın yoldan bağımsız kimliği. `rename` ile döndürülen dosya aynı kimliği korur,
+/// yerine açılan yeni dosya farklı kimlik alır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FileId {
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+fn file_id(meta: &Metadata) -> FileId {
+    use std::os::unix::fs::MetadataExt;
+    FileId {
+        dev: meta.dev(),
+        ino: meta.ino(),
+    }
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &Metadata) -> FileId {
+    FileId { dev: 0, ino: 0 }
+}
+
+/// Diske yazılan okuma konumu (son tam satırın sonu).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedOffset {
+    path: String,
+    dev: u64,
+    ino: u64,
+    offset: u64,
+}
+
+struct TailedFile {
+    path: PathBuf,
+    file: File,
+    /// Dosyada okunan konum (tamamlanmamış satır dahil)
+    position: u64,
+    partial: Vec<u8>,
+    origin: LineOrigin,
+}
+
+impl TailedFile {
+    /// Yeniden başlatmada kaldığı yer: tamamlanmamış satır tekrar okunur.
+    fn committed(&self) -> u64 {
+        self.position.saturating_sub(self.partial.len() as u64)
+    }
+
+    /// Dosyayı sonuna kadar okuyup tamamlanan satırları yazar; kanal kapandıysa `false`.
+    fn drain(&mut self, tx: &Sender<LogRecord>) -> bool {
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut read_total = 0;
+        while read_total < MAX_READ_PER_POLL {
+            let n = match self.file.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) => {
+                    warn!(event="FILE_TAIL_READ_ERR", file=%self.path.display(), error=%e, "Log dosyası okunamadı");
+                    break;
+                }
+            };
+            read_total += n;
+            self.position += n as u64;
+            self.partial.extend_from_slice(&buf[..n]);
+
+            let mut start = 0;
+            while let Some(i) = self.partial[start..].iter().position(|b| *b == b'\n') {
+                if !send_line(tx, &self.origin, &self.partial[start..start + i]) {
+                    return false;
+                }
+                start += i + 1;
+            }
+            self.partial.drain(..start);
+
+            if self.partial.len() >= MAX_LINE_BYTES {
+                let line = std::mem::take(&mut self.partial);
+                if !send_line(tx, &self.origin, &line) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Dosya bırakılırken satır sonu beklenmeden kalan kısım da yazılır.
+    fn finish(mut self, tx: &Sender<LogRecord>) -> bool {
+        let line = std::mem::take(&mut self.partial);
+        send_line(tx, &self.origin, &line)
+    }
+}
+
+fn send_line(tx: &Sender<LogRecord>, origin: &LineOrigin, line: &[u8]) -> bool {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim_end_matches('\r');
+    if line.trim().is_empty() {
+        return true;
+    }
+    // Dosyalarda stdout/stderr ayrımı yoktur; ham satırlar stdout gibi DEBUG seviyesindedir.
+    let record = origin.normalize(line, "file", None, Utc::now());
+    tx.blocking_send(record).is_ok()
+}
+
+/// Çıplak metal servislerin (Kamailio, rtpengine ...) log dosyalarını `tail -F` gibi izleyen
+/// ingestor. Satırlar Docker ingestor ile aynı SUTS JSON / ham normalizasyondan geçer.
+pub struct FileTailIngestor {
+    sources: Vec<TailSource>,
+    state_path: Option<PathBuf>,
+    poll_interval: Duration,
+    tx: Sender<LogRecord>,
+    node_name: String,
+    tenant_id: String, // [ARCH-COMPLIANCE]
+}
+
+impl FileTailIngestor {
+    /// `paths`: virgülle ayrılmış glob desenleri; `servis=desen` biçimi servis adını belirler
+    /// (ör. `kamailio=/var/log/kamailio/*.log,/var/log/rtpengine.log`).
+    /// `state_path` boşsa okuma konumları saklanmaz.
+    pub fn new(
+        paths: &str,
+        state_path: &str,
+        poll_ms: u64,
+        tx: Sender<LogRecord>,
+        node_name: String,
+        tenant_id: String,
+    ) -> Self {
+        let sources = paths
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|entry| match entry.split_once('=') {
+                Some((name, pattern)) if !name.is_empty() && !name.contains('/') => TailSource {
+                    service_name: Some(name.trim().to_string()),
+                    pattern: pattern.trim().to_string(),
+                },
+                _ => TailSource {
+                    service_name: None,
+                    pattern: entry.to_string(),
+                },
+            })
+            .collect();
+        Self {
+            sources,
+            state_path: Some(state_path.trim())
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from),
+            poll_interval: Duration::from_millis(poll_ms.max(100)),
+            tx,
+            node_name,
+            tenant_id,
+        }
+    }
+}
+
+#[async_trait]
+impl LogIngestor for FileTailIngestor {
+    async fn start(&self) -> Result<()> {
+        info!(event="FILE_TAIL_START", sources=self.sources.len(), node=%self.node_name, "📄 File Tail Ingestor: Başlatıldı");
+
+        let mut tailer = Tailer::new(
+            self.sources.clone(),
+            self.state_path.clone(),
+            self.tx.clone(),
+            self.node_name.clone(),
+            self.tenant_id.clone(),
+        );
+        let poll_interval = self.poll_interval;
+
+        tokio::task::spawn_blocking(move || tailer.run(poll_interval))
+            .await
+            .context("File Tail Task Error")?;
+        Ok(())
+    }
+}
+
+struct Tailer {
+    sources: Vec<TailSource>,
+    state_path: Option<PathBuf>,
+    tx: Sender<LogRecord>,
+    node_name: String,
+    tenant_id: String,
+    /// Dosyalar yola göre değil kimliğe göre izlenir; döndürülüp hâlâ desenle eşleşen
+    /// dosya yeni adıyla kaldığı yerden okunmaya devam eder.
+    files: HashMap<FileId, TailedFile>,
+    /// Açılışta diskten okunan konumlar; dosya ilk açıldığında tüketilir. Henüz yeniden
+    /// görülmemiş girdiler dosyanın artık var olmadığı doğrulanana kadar saklanmaya devam eder.
+    saved: HashMap<FileId, SavedOffset>,
+    dirty: bool,
+    state_error_logged: bool,
+}
+
+impl Tailer {
+    fn new(
+        sources: Vec<TailSource>,
+        state_path: Option<PathBuf>,
+        tx: Sender<LogRecord>,
+        node_name: String,
+        tenant_id: String,
+    ) -> Self {
+        Self {
+            sources,
+            saved: state_path.as_deref().map(load_offsets).unwrap_or_default(),
+            state_path,
+            tx,
+            node_name,
+            tenant_id,
+            files: HashMap::new(),
+            dirty: false,
+            state_error_logged: false,
+        }
+    }
+
+    fn run(&mut self, poll_interval: Duration) {
+        let mut first_scan = true;
+        let mut last_flush = Instant::now();
+        loop {
+            let open = self.poll(first_scan);
+            first_scan = false;
+            if !open {
+                self.save();
+                return;
+            }
+            if self.dirty && last_flush.elapsed() >= Duration::from_secs(STATE_FLUSH_SECS) {
+                self.save();
+                last_flush = Instant::now();
+            }
+            std::thread::sleep(poll_interval);
+        }
+    }
+
+    /// Desenleri yeniden açar, izlenen dosyaları okur ve döndürme/kesme durumlarını işler.
+    /// Kanal kapandıysa `false`.
+    fn poll(&mut self, first_scan: bool) -> bool {
+        let mut seen: HashMap<FileId, (PathBuf, Metadata, String)> = HashMap::new();
+        for source in &self.sources {
+            for path in expand_paths(&source.pattern) {
+                let Ok(meta) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                let service = source
+                    .service_name
+                    .clone()
+                    .unwrap_or_else(|| service_from_path(&path));
+                seen.entry(file_id(&meta)).or_insert((path, meta, service));
+            }
+        }
+
+        let tracked: Vec<FileId> = self.files.keys().copied().collect();
+        for id in tracked {
+            let Some(mut tailed) = self.files.remove(&id) else {
+                continue;
+            };
+            // Döndürülen dosyanın (rename) kalan satırları eski tanıtıcıdan okunur.
+            let before = tailed.position;
+            if !tailed.drain(&self.tx) {
+                return false;
+            }
+            self.dirty |= tailed.position != before;
+
+            let Some((path, meta, service)) = seen.remove(&id) else {
+                // Desenle artık eşleşmiyor: silindi veya eşleşmeyen bir ada döndürüldü.
+                info!(event="FILE_TAIL_CLOSED", file=%tailed.path.display(), "📄 Log dosyası bırakıldı (döndürüldü veya silindi)");
+                if !tailed.finish(&self.tx) {
+                    return false;
+                }
+                continue;
+            };
+
+            if path != tailed.path {
+                info!(event="FILE_ROTATED", from=%tailed.path.display(), to=%path.display(), "🔄 Log dosyası yeniden adlandırıldı, okumaya devam ediliyor");
+                tailed.origin = self.origin(&path, &service);
+                tailed.path = path;
+            }
+
+            // copytruncate / truncate: dosya okunan konumdan kısaldıysa baştan okunur.
+            if meta.len() < tailed.position {
+                info!(event="FILE_TRUNCATED", file=%tailed.path.display(), size=meta.len(), offset=tailed.position, "✂️ Log dosyası kesildi, baştan okunuyor");
+                match tailed.file.seek(SeekFrom::Start(0)) {
+                    Ok(_) => {
+                        tailed.position = 0;
+                        tailed.partial.clear();
+                        self.dirty = true;
+                        if !tailed.drain(&self.tx) {
+                            return false;
+                        }
+                    }
+                    Err(e) => {
+                        warn!(event="FILE_TAIL_READ_ERR", file=%tailed.path.display(), error=%e, "Log dosyası başa sarılamadı");
+                        continue;
+                    }
+                }
+            }
+            self.files.insert(id, tailed);
+        }
+
+        for (id, (path, meta, service)) in seen {
+            let Some(mut tailed) = self.open(id, path, &meta, &service, first_scan) else {
+                continue;
+            };
+            if !tailed.drain(&self.tx) {
+                return false;
+            }
+            self.dirty = true;
+            self.files.insert(id, tailed);
+        }
+        self.prune_saved();
+        true
+    }
+
+    /// Saklı konumu olup henüz açılmamış dosyalardan yolu silinmiş veya artık başka bir
+    /// dosyaya ait olanları unutur. Geçici olarak erişilemeyen dosyanın konumu korunur.
+    fn prune_saved(&mut self) {
+        let before = self.saved.len();
+        self.saved
+            .retain(|id, saved| match std::fs::metadata(&saved.path) {
+                Ok(meta) => file_id(&meta) == *id,
+                Err(e) => e.kind() != std::io::ErrorKind::NotFound,
+            });
+        self.dirty |= self.saved.len() != before;
+    }
+
+    /// Yeni eşleşen dosyayı açar. Saklanmış konum varsa oradan, yoksa açılışta mevcut olan
+    /// dosyalarda sondan (eski içerik yeniden basılmaz), sonradan oluşanlarda baştan okunur.
+    fn open(
+        &mut self,
+        id: FileId,
+        path: PathBuf,
+        meta: &Metadata,
+        service: &str,
+        first_scan: bool,
+    ) -> Option<TailedFile> {
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                warn!(event="FILE_TAIL_OPEN_ERR", file=%path.display(), error=%e, "Log dosyası açılamadı");
+                return None;
+            }
+        };
+        let offset = match self.saved.remove(&id).map(|saved| saved.offset) {
+            // Saklanan konum dosyadan büyükse dosya kapalıyken kesilmiştir.
+            Some(offset) if offset <= meta.len() => offset,
+            Some(_) => 0,
+            None if first_scan => meta.len(),
+            None => 0,
+        };
+        if let Err(e) = file.seek(SeekFrom::Start(offset)) {
+            warn!(event="FILE_TAIL_OPEN_ERR", file=%path.display(), error=%e, "Log dosyasında konuma gidilemedi");
+            return None;
+        }
+        info!(event="FILE_TAIL_OPENED", file=%path.display(), service=%service, offset=offset, "📄 Log dosyası izleniyor");
+        Some(TailedFile {
+            origin: self.origin(&path, service),
+            path,
+            file,
+            position: offset,
+            partial: Vec::new(),
+        })
+    }
+
+    fn origin(&self, path: &Path, service: &str) -> LineOrigin {
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            "log.file.path".to_string(),
+            Value::String(path.display().to_string()),
+        );
+        if let Some(name) = path.file_name() {
+            attributes.insert(
+                "log.file.name".to_string(),
+                Value::String(name.to_string_lossy().to_string()),
+            );
+        }
+        LineOrigin {
+            resource: ResourceContext {
+                service_name: service.to_string(),
+                service_version: "unknown".to_string(),
+                service_env: "production".to_string(),
+                host_name: Some(self.node_name.clone()),
+            },
+            attributes,
+            tenant_id: self.tenant_id.clone(),
+        }
+    }
+
+    /// Konumları geçici dosyaya yazıp yerine taşır; yarım yazılmış durum dosyası oluşmaz.
+    fn save(&mut self) {
+        let Some(state_path) = self.state_path.as_deref() else {
+            return;
+        };
+        let offsets: Vec<SavedOffset> = self
+            .files
+            .iter()
+            .map(|(id, tailed)| SavedOffset {
+                path: tailed.path.display().to_string(),
+                dev: id.dev,
+                ino: id.ino,
+                offset: tailed.committed(),
+            })
+            .chain(self.saved.values().cloned())
+            .collect();
+
+        let result = (|| -> std::io::Result<()> {
+            if let Some(dir) = state_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            let tmp = state_path.with_extension("tmp");
+            std::fs::write(&tmp, serde_json::to_vec(&offsets)?)?;
+            std::fs::rename(&tmp, state_path)
+        })();
+
+        match result {
+            Ok(()) => {
+                self.dirty = false;
+                self.state_error_logged = false;
+            }
+            Err(e) if !self.state_error_logged => {
+                warn!(event="FILE_TAIL_STATE_ERR", file=%state_path.display(), error=%e, "⚠️ Okuma konumları kaydedilemedi");
+                self.state_error_logged = true;
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+fn load_offsets(path: &Path) -> HashMap<FileId, SavedOffset> {
+    let Ok(data) = std::fs::read(path) else {
+        return HashMap::new();
+    };
+    match serde_json::from_slice::<Vec<SavedOffset>>(&data) {
+        Ok(offsets) => offsets
+            .into_iter()
+            .map(|o| {
+                (
+                    FileId {
+                        dev: o.dev,
+                        ino: o.ino,
+                    },
+                    o,
+                )
+            })
+            .collect(),
+        Err(e) => {
+            warn!(event="FILE_TAIL_STATE_ERR", file=%path.display(), error=%e, "⚠️ Okuma konumları okunamadı, dosyalar sondan izlenecek");
+            HashMap::new()
+        }
+    }
+}
+
+/// `/var/log/kamailio/kamailio.log` -> `kamailio`
+fn service_from_path(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .and_then(|n| n.split('.').next().map(str::to_string))
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "file".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::mpsc::{channel, Receiver};
+
+    static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+    struct Fixture {
+        dir: PathBuf,
+        rx: Receiver<LogRecord>,
+        tx: Sender<LogRecord>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "observer-tail-{}-{}",
+                std::process::id(),
+                NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).expect("dizin oluşturulamadı");
+            let (tx, rx) = channel(1000);
+            Self { dir, rx, tx }
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.join(name)
+        }
+
+        fn tailer(&self, pattern: &str) -> Tailer {
+            Tailer::new(
+                vec![TailSource {
+                    service_name: Some("svc".to_string()),
+                    pattern: self.path(pattern).display().to_string(),
+                }],
+                Some(self.path("offsets.json")),
+                self.tx.clone(),
+                "node".to_string(),
+                "t".to_string(),
+            )
+        }
+
+        fn append(&self, name: &str, text: &str) {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.path(name))
+                .expect("dosya açılamadı");
+            file.write_all(text.as_bytes()).expect("yazılamadı");
+        }
+
+        fn lines(&mut self) -> Vec<String> {
+            std::iter::from_fn(|| self.rx.try_recv().ok())
+                .map(|record| record.message)
+                .collect()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn rotation_keeps_every_line_once() {
+        let mut fx = Fixture::new();
+        fx.append("app.log", "eski\n");
+        let mut tailer = fx.tailer("app.log*");
+        // Açılışta mevcut dosya sondan izlenir.
+        assert!(tailer.poll(true));
+        assert!(fx.lines().is_empty());
+
+        fx.append("app.log", "a\nb\n");
+        assert!(tailer.poll(false));
+        assert_eq!(fx.lines(), ["a", "b"]);
+
+        // Döndürmeden hemen önce yazılan satır eski dosyada kalır.
+        fx.append("app.log", "c\n");
+        std::fs::rename(fx.path("app.log"), fx.path("app.log.1")).expect("taşınamadı");
+        fx.append("app.log", "d\n");
+        assert!(tailer.poll(false));
+        let mut lines = fx.lines();
+        lines.sort();
+        assert_eq!(lines, ["c", "d"]);
+
+        fx.append("app.log", "e\n");
+        assert!(tailer.poll(false));
+        assert_eq!(fx.lines(), ["e"]);
+    }
+
+    #[test]
+    fn truncation_restarts_from_the_beginning() {
+        let mut fx = Fixture::new();
+        let mut tailer = fx.tailer("app.log");
+        assert!(tailer.poll(true));
+        // Sonradan oluşan dosya baştan okunur.
+        fx.append("app.log", "birinci satır\n");
+        assert!(tailer.poll(false));
+        assert_eq!(fx.lines(), ["birinci satır"]);
+
+        // copytruncate: içerik kesilip daha kısa yeni satır yazılır.
+        std::fs::write(fx.path("app.log"), "yeni\n").expect("yazılamadı");
+        assert!(tailer.poll(false));
+        assert_eq!(fx.lines(), ["yeni"]);
+        let tailed = tailer.files.values().next().expect("dosya izlenmiyor");
+        assert_eq!(tailed.position, 5);
+    }
+
+    #[test]
+    fn restart_resumes_from_saved_offset() {
+        let mut fx = Fixture::new();
+        fx.append("app.log", "x\n");
+        let mut tailer = fx.tailer("app.log");
+        assert!(tailer.poll(true));
+        fx.append("app.log", "y\nyarım");
+        assert!(tailer.poll(false));
+        assert_eq!(fx.lines(), ["y"]);
+        tailer.save();
+        drop(tailer);
+
+        // Servis kapalıyken yazılanlar ve tamamlanmamış satır yeniden başlatmada okunur.
+        fx.append("app.log", " satır\nz\n");
+        let mut restarted = fx.tailer("app.log");
+        assert!(restarted.poll(true));
+        assert_eq!(fx.lines(), ["yarım satır", "z"]);
+    }
+
+    #[test]
+    fn unseen_saved_offsets_survive_until_the_file_is_gone() {
+        let mut fx = Fixture::new();
+        fx.append("a.log", "a\n");
+        fx.append("b.log", "b\n");
+        let mut tailer = fx.tailer("*.log");
+        assert!(tailer.poll(true));
+        tailer.save();
+        drop(tailer);
+
+        // Yalnızca a.log izlenirken b.log'un konumu kaybolmamalı.
+        let mut partial = fx.tailer("a.log");
+        assert!(partial.poll(true));
+        partial.save();
+        let saved = load_offsets(&fx.path("offsets.json"));
+        assert_eq!(saved.len(), 2);
+
+        std::fs::remove_file(fx.path("b.log")).expect("silinemedi");
+        assert!(partial.poll(false));
+        partial.save();
+        let saved = load_offsets(&fx.path("offsets.json"));
+        assert_eq!(saved.len(), 1);
+        assert!(saved.values().all(|o| o.path.ends_with("a.log")));
+        assert!(fx.lines().is_empty());
+    }
+}
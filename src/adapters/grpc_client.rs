@@ -3,13 +3,21 @@ use crate::core::domain::LogRecord;
 use crate::ports::LogEmitter;
 use anyhow::{bail, Result};
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
+use crate::api::grpc::observer_proto::ingest_log_request::Payload;
 use crate::api::grpc::observer_proto::observer_service_client::ObserverServiceClient;
-use crate::api::grpc::observer_proto::IngestLogRequest;
+use crate::api::grpc::observer_proto::{IngestBatchRequest, IngestLogRequest};
+use prost::Message;
+
+/// Tek `IngestBatch` isteğinin en büyük boyutu; tonic'in 4 MiB mesaj sınırının altında kalır.
+const MAX_BATCH_BYTES: usize = 3 * 1024 * 1024;
+const BATCH_TIMEOUT_SECS: u64 = 10;
+const STREAM_TIMEOUT_SECS: u64 = 30;
 
 pub struct GrpcEmitter {
     client: Arc<RwLock<Option<ObserverServiceClient<Channel>>>>,
@@ -17,6 +25,8 @@ pub struct GrpcEmitter {
     tls_cert_path: Option<String>,
     tls_key_path: Option<String>,
     tls_ca_path: Option<String>,
+    /// Upstream `IngestBatch`/`IngestStream` bilmiyorsa (eski sürüm) tekil gönderime geçilir.
+    legacy_upstream: AtomicBool,
 }
 
 impl GrpcEmitter {
@@ -32,6 +42,7 @@ impl GrpcEmitter {
             tls_cert_path,
             tls_key_path,
             tls_ca_path,
+            legacy_upstream: AtomicBool::new(false),
         }
     }
 
@@ -100,6 +111,20 @@ impl GrpcEmitter {
         *write_guard = Some(c.clone());
         Ok(c)
     }
+
+    /// Kayıt başına bir `IngestLog` çağrısı (yalnızca eski upstream'ler için).
+    async fn emit_each(client: &mut ObserverServiceClient<Channel>, logs: &[LogRecord]) {
+        for request in logs.iter().filter_map(serialize_json) {
+            let mut req = tonic::Request::new(request);
+
+            // [ARCH-COMPLIANCE] Zorunlu Senkron Çağrı Timeout Koruması
+            req.set_timeout(std::time::Duration::from_secs(3));
+
+            if let Err(e) = client.ingest_log(req).await {
+                debug!(event="UPSTREAM_SEND_FAIL", error=%e, "⚠️ Failed to send log to upstream");
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -115,27 +140,139 @@ impl LogEmitter for GrpcEmitter {
 
     async fn emit_batch(&self, logs: Vec<LogRecord>) -> Result<()> {
         let mut client = self.connect().await?;
+        if self.legacy_upstream.load(Ordering::Relaxed) {
+            Self::emit_each(&mut client, &logs).await;
+            return Ok(());
+        }
+
+        let Some((batch, total_bytes)) = plan_batch(&logs) else {
+            return Ok(());
+        };
+        let result = match batch {
+            UpstreamBatch::Single(batch) => {
+                let mut req = tonic::Request::new(batch);
+                req.set_timeout(std::time::Duration::from_secs(BATCH_TIMEOUT_SECS));
+                client.ingest_batch(req).await
+            }
+            UpstreamBatch::Stream(requests) => {
+                let mut req = tonic::Request::new(futures_util::stream::iter(requests));
+                req.set_timeout(std::time::Duration::from_secs(STREAM_TIMEOUT_SECS));
+                client.ingest_stream(req).await
+            }
+        };
 
-        for log in logs {
-            let raw_json = match serde_json::to_string(&log) {
-                Ok(json) => json,
-                Err(e) => {
-                    error!(event="JSON_SERIALIZE_ERROR", error=%e, "Failed to serialize LogRecord for gRPC");
-                    continue;
+        match result {
+            Ok(response) => {
+                let summary = response.into_inner();
+                if summary.rejected > 0 {
+                    warn!(
+                        event = "UPSTREAM_REJECTED",
+                        accepted = summary.accepted,
+                        rejected = summary.rejected,
+                        reason = %summary.rejections.first().map(|r| r.reason.as_str()).unwrap_or(""),
+                        "⚠️ Upstream bazı kayıtları reddetti"
+                    );
+                } else {
+                    debug!(
+                        event = "UPSTREAM_BATCH_SENT",
+                        accepted = summary.accepted,
+                        bytes = total_bytes,
+                        "📤 Yığın upstream'e iletildi"
+                    );
                 }
-            };
+                Ok(())
+            }
+            // Toplu RPC'leri bilmeyen eski upstream: kayıt başına `IngestLog`'a dönülür.
+            Err(status) if status.code() == tonic::Code::Unimplemented => {
+                warn!(event="UPSTREAM_LEGACY_MODE", target=%self.target_url, "⚠️ Upstream toplu ingest desteklemiyor, tekil IngestLog kullanılacak");
+                self.legacy_upstream.store(true, Ordering::Relaxed);
+                Self::emit_each(&mut client, &logs).await;
+                Ok(())
+            }
+            Err(status) => bail!("Upstream batch ingest failed: {}", status),
+        }
+    }
+}
 
-            let mut req = tonic::Request::new(IngestLogRequest {
-                raw_json_log: raw_json,
-            });
+/// Upstream'e tek seferde gidecek kayıtlar.
+enum UpstreamBatch {
+    Single(IngestBatchRequest),
+    Stream(Vec<IngestLogRequest>),
+}
 
-            // [ARCH-COMPLIANCE] Zorunlu Senkron Çağrı Timeout Koruması
-            req.set_timeout(std::time::Duration::from_secs(3));
+/// Kayıtları tipli isteklere çevirir (toplu RPC'leri bilen upstream tipli kaydı da bilir;
+/// JSON serileştirme yapılmaz). Küçük yığınlar tek istekte, mesaj boyutu sınırını aşacak
+/// yığınlar akış olarak gider. Boş yığında `None`.
+fn plan_batch(logs: &[LogRecord]) -> Option<(UpstreamBatch, usize)> {
+    let requests: Vec<IngestLogRequest> = logs
+        .iter()
+        .map(|log| IngestLogRequest {
+            payload: Some(Payload::Record(log.into())),
+        })
+        .collect();
+    if requests.is_empty() {
+        return None;
+    }
+    let total_bytes: usize = requests.iter().map(Message::encoded_len).sum();
+    let batch = if total_bytes <= MAX_BATCH_BYTES {
+        UpstreamBatch::Single(IngestBatchRequest { logs: requests })
+    } else {
+        UpstreamBatch::Stream(requests)
+    };
+    Some((batch, total_bytes))
+}
 
-            if let Err(e) = client.ingest_log(req).await {
-                debug!(event="UPSTREAM_SEND_FAIL", error=%e, "⚠️ Failed to send log to upstream");
-            }
+/// Eski upstream'ler için JSON biçimi.
+fn serialize_json(log: &LogRecord) -> Option<IngestLogRequest> {
+    match serde_json::to_string(log) {
+        Ok(raw_json_log) => Some(IngestLogRequest {
+            payload: Some(Payload::RawJsonLog(raw_json_log)),
+        }),
+        Err(e) => {
+            error!(event="JSON_SERIALIZE_ERROR", error=%e, "Failed to serialize LogRecord for gRPC");
+            None
         }
-        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(message: &str) -> LogRecord {
+        LogRecord::new_system("INFO", "TEST_EVENT", message, "t")
+    }
+
+    #[test]
+    fn small_batches_go_as_a_single_typed_request() {
+        let logs = vec![record("a"), record("b")];
+        let Some((UpstreamBatch::Single(batch), bytes)) = plan_batch(&logs) else {
+            panic!("tek istek bekleniyordu");
+        };
+        assert_eq!(batch.logs.len(), 2);
+        assert_eq!(
+            bytes,
+            batch.logs.iter().map(Message::encoded_len).sum::<usize>()
+        );
+        assert!(batch
+            .logs
+            .iter()
+            .all(|req| matches!(req.payload, Some(Payload::Record(_)))));
+    }
+
+    #[test]
+    fn batches_over_the_message_limit_are_streamed() {
+        let big = "x".repeat(MAX_BATCH_BYTES / 2);
+        let logs = vec![record(&big), record(&big), record("küçük")];
+        let Some((UpstreamBatch::Stream(requests), bytes)) = plan_batch(&logs) else {
+            panic!("akış bekleniyordu");
+        };
+        assert_eq!(requests.len(), 3);
+        assert!(bytes > MAX_BATCH_BYTES);
+    }
+
+    #[test]
+    fn empty_batch_sends_nothing() {
+        assert!(plan_batch(&[]).is_none());
     }
 }
@@ -1,4 +1,10 @@
 pub mod docker;
+pub mod docker_filter;
 pub mod exporter;
+pub mod file_tail;
 pub mod grpc_client;
-pub mod sniffer; // <--- YENİ
+pub mod normalize;
+pub mod pcap_file;
+pub mod segment_store;
+pub mod sniffer;
+pub mod syslog; // <--- YENİ
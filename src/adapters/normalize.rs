@@ -0,0 +1,167 @@
+// src/adapters/normalize.rs
+use crate::core::domain::{LogRecord, ResourceContext};
+use crate::utils::parser;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Satır tabanlı log kaynaklarının (Docker container, log dosyası) ortak kimliği.
+/// SUTS JSON satırları kendi `resource` alanlarını taşıyabilir; eksik alanlar ve
+/// ham satırlar buradaki varsayılanlarla doldurulur.
+pub struct LineOrigin {
+    pub resource: ResourceContext,
+    /// Kayda eklenen kaynak alanları; uygulamanın kendi yazdığı alanlar ezilmez.
+    pub attributes: HashMap<String, Value>,
+    pub tenant_id: String,
+}
+
+impl LineOrigin {
+    /// Tek bir satırı (veya birleştirilmiş çok satırlı kaydı) `LogRecord`'a çevirir.
+    /// `source_ts`: kaynağın satıra yazdığı zaman; uygulama `ts` vermediyse kayıt zamanı olur.
+    /// `observed_at`: satırın okunduğu an, `observed_ts` olarak saklanır.
+    pub fn normalize(
+        &self,
+        line: &str,
+        stream_type: &str,
+        source_ts: Option<DateTime<Utc>>,
+        observed_at: DateTime<Utc>,
+    ) -> LogRecord {
+        let cleaned_line = parser::clean_ansi(line);
+        let fallback_ts = || source_ts.unwrap_or(observed_at).to_rfc3339();
+
+        if let Ok(json_val) = serde_json::from_str::<Value>(&cleaned_line) {
+            if let Some(map) = json_val.as_object() {
+                let schema = map
+                    .get("schema_v")
+                    .and_then(Value::as_str)
+                    .unwrap_or("1.0.0");
+                let severity = map
+                    .get("severity")
+                    .or_else(|| map.get("level"))
+                    .and_then(Value::as_str)
+                    .unwrap_or(if stream_type == "stderr" {
+                        "ERROR"
+                    } else {
+                        "INFO"
+                    })
+                    .to_uppercase();
+
+                let msg = map
+                    .get("message")
+                    .or_else(|| map.get("msg"))
+                    .and_then(Value::as_str)
+                    .unwrap_or(&cleaned_line)
+                    .to_string();
+
+                let ts = map
+                    .get("ts")
+                    .or_else(|| map.get("time"))
+                    .or_else(|| map.get("timestamp"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string();
+
+                let trace_id = map
+                    .get("trace_id")
+                    .and_then(Value::as_str)
+                    .map(|s| s.to_string());
+                let event = map
+                    .get("event")
+                    .and_then(Value::as_str)
+                    .unwrap_or("LOG_EVENT")
+                    .to_string();
+
+                let mut attributes = HashMap::new();
+                if let Some(attrs) = map.get("attributes").and_then(Value::as_object) {
+                    for (k, v) in attrs {
+                        attributes.insert(k.clone(), v.clone());
+                    }
+                } else {
+                    for (k, v) in map {
+                        if ![
+                            "schema_v", "severity", "level", "message", "msg", "ts", "time",
+                            "trace_id", "event", "resource",
+                        ]
+                        .contains(&k.as_str())
+                        {
+                            attributes.insert(k.clone(), v.clone());
+                        }
+                    }
+                }
+
+                self.enrich(&mut attributes, observed_at);
+
+                let resource = if let Some(r) = map.get("resource").and_then(Value::as_object) {
+                    let field = |key: &str, default: &str| {
+                        r.get(key)
+                            .and_then(Value::as_str)
+                            .unwrap_or(default)
+                            .to_string()
+                    };
+                    ResourceContext {
+                        service_name: field("service.name", &self.resource.service_name),
+                        service_version: field("service.version", &self.resource.service_version),
+                        service_env: field("service.env", &self.resource.service_env),
+                        host_name: self.resource.host_name.clone(),
+                    }
+                } else {
+                    self.resource.clone()
+                };
+
+                let mut record = LogRecord {
+                    schema_v: schema.to_string(),
+                    ts: if ts.is_empty() { fallback_ts() } else { ts },
+                    severity,
+                    tenant_id: self.tenant_id.clone(),
+                    resource,
+                    trace_id,
+                    span_id: None,
+                    event,
+                    message: msg,
+                    attributes,
+                    smart_tags: vec![],
+                    _idx: 0.0,
+                };
+
+                record.sanitize_and_enrich();
+                return record;
+            }
+        }
+
+        // [ARCH-COMPLIANCE FIX]: SUTS Formatında Olmayan Altyapı Loglarının Susturulması
+        // stdout -> DEBUG (Gürültü önleme), stderr -> WARN (Error yerine Warn yapıyoruz ki kırmızılar patlamasın)
+        let mut raw_record = LogRecord {
+            schema_v: "1.0.0".to_string(),
+            ts: fallback_ts(),
+            severity: if stream_type == "stderr" {
+                "WARN".to_string()
+            } else {
+                "DEBUG".to_string()
+            },
+            tenant_id: self.tenant_id.clone(),
+            resource: self.resource.clone(),
+            trace_id: None,
+            span_id: None,
+            event: "RAW_INFRA_LOG".to_string(),
+            message: cleaned_line,
+            attributes: HashMap::new(),
+            smart_tags: vec!["RAW".to_string(), "INFRA_RAW".to_string()],
+            _idx: 0.0,
+        };
+        self.enrich(&mut raw_record.attributes, observed_at);
+        raw_record.sanitize_and_enrich();
+        raw_record
+    }
+
+    fn enrich(&self, attributes: &mut HashMap<String, Value>, observed_at: DateTime<Utc>) {
+        for (key, value) in &self.attributes {
+            attributes
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        attributes.insert(
+            "observed_ts".to_string(),
+            Value::String(observed_at.to_rfc3339()),
+        );
+    }
+}
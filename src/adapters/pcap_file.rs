@@ -0,0 +1,141 @@
+// src/adapters/pcap_file.rs
+use crate::adapters::sniffer::{capture_micros, PacketProcessor};
+use crate::core::call_frames::CallFrameStore;
+use crate::core::domain::LogRecord;
+use crate::ports::LogIngestor;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use pcap::Capture;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::Sender;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// Sonradan alınan müşteri yakalamalarını (.pcap / .pcapng) canlı sniffer ile
+/// aynı SIP/RTP kayıtlarına dönüştürüp ingest kanalına basan offline ingestor.
+pub struct PcapFileIngestor {
+    path: String,
+    filter: String,
+    realtime: bool,
+    tx: Sender<LogRecord>,
+    node_name: String,
+    tenant_id: String, // [ARCH-COMPLIANCE]
+    frame_store: Arc<Mutex<CallFrameStore>>,
+}
+
+impl PcapFileIngestor {
+    pub fn new(
+        path: &str,
+        filter: &str,
+        realtime: bool,
+        tx: Sender<LogRecord>,
+        node_name: String,
+        tenant_id: String,
+        frame_store: Arc<Mutex<CallFrameStore>>,
+    ) -> Self {
+        Self {
+            path: path.to_string(),
+            filter: filter.trim().to_string(),
+            realtime,
+            tx,
+            node_name,
+            tenant_id,
+            frame_store,
+        }
+    }
+}
+
+#[async_trait]
+impl LogIngestor for PcapFileIngestor {
+    async fn start(&self) -> Result<()> {
+        info!(event="PCAP_REPLAY_START", file=%self.path, realtime=self.realtime, "📼 Pcap Replay: Başlatılıyor.");
+
+        let mut cap = Capture::from_file(&self.path).context("Pcap File Open Error")?;
+
+        if !self.filter.is_empty() {
+            if let Err(e) = cap.filter(&self.filter, true) {
+                error!(event="BPF_FILTER_ERR", error=%e, "BPF Filtre Hatası");
+                return Err(anyhow::anyhow!("BPF Filter Error"));
+            }
+        }
+
+        let link_type = cap.get_datalink();
+        let tx = self.tx.clone();
+        let path = self.path.clone();
+        let realtime = self.realtime;
+        let tenant_id = self.tenant_id.clone();
+        // Dosyadaki kayıtlar canlı "any" tekilleştirmesinden geçirilmez.
+        let mut processor = PacketProcessor::new(
+            &format!("pcap:{}", self.path),
+            self.node_name.clone(),
+            self.tenant_id.clone(),
+            self.frame_store.clone(),
+        )
+        .without_dedup();
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut packets: u64 = 0;
+            let mut emitted: u64 = 0;
+            // (ilk paketin yakalama zamanı [µs], replay başlangıç anı)
+            let mut clock: Option<(i64, Instant)> = None;
+
+            loop {
+                match cap.next_packet() {
+                    Ok(packet) => {
+                        packets += 1;
+
+                        if realtime {
+                            let captured_us = capture_micros(packet.header);
+                            let (first_us, started) =
+                                *clock.get_or_insert((captured_us, Instant::now()));
+                            let offset =
+                                Duration::from_micros((captured_us - first_us).max(0) as u64);
+                            if let Some(wait) = offset.checked_sub(started.elapsed()) {
+                                std::thread::sleep(wait);
+                            }
+                        }
+
+                        for log in processor.handle_packet(&packet, link_type) {
+                            // Offline modda paket düşürmek yerine kanalın boşalmasını bekleriz.
+                            if tx.blocking_send(log).is_err() {
+                                return (packets, emitted);
+                            }
+                            emitted += 1;
+                        }
+                    }
+                    Err(pcap::Error::NoMorePackets) => break,
+                    Err(e) => {
+                        warn!(event="PCAP_REPLAY_READ_ERR", error=?e, "Pcap dosyası okunurken hata");
+                        break;
+                    }
+                }
+            }
+
+            // Dosya sonunda hâlâ açık olan RTP akışlarının final kalite raporları.
+            for log in processor.finish() {
+                if tx.blocking_send(log).is_err() {
+                    return (packets, emitted);
+                }
+                emitted += 1;
+            }
+
+            let summary = LogRecord::new_system(
+                "INFO",
+                "PCAP_REPLAY_COMPLETE",
+                &format!(
+                    "Pcap replay tamamlandı: {} ({} paket, {} kayıt)",
+                    path, packets, emitted
+                ),
+                &tenant_id,
+            );
+            let _ = tx.blocking_send(summary);
+            (packets, emitted)
+        });
+
+        let (packets, emitted) = handle.await.context("Pcap Replay Task Error")?;
+        info!(event="PCAP_REPLAY_DONE", file=%self.path, packets=packets, records=emitted, "📼 Pcap Replay: Tamamlandı.");
+
+        Ok(())
+    }
+}
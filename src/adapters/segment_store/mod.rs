@@ -0,0 +1,587 @@
+// src/adapters/segment_store/mod.rs
+mod segment;
+
+use crate::core::domain::LogRecord;
+use crate::core::log_query::{LogQuery, MAX_LOG_QUERY_LIMIT};
+use crate::ports::LogStore;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use segment::SegmentMeta;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info, warn};
+
+/// Tek gzip üyesine yazılan en fazla kayıt; kuyruk bundan hızlı dolarsa erken yazılır.
+const WRITE_BATCH_SIZE: usize = 1000;
+const FLUSH_INTERVAL_MS: u64 = 1000;
+const RETENTION_INTERVAL_SECS: u64 = 60;
+
+#[derive(Debug, Clone)]
+pub struct StoreSettings {
+    pub dir: PathBuf,
+    pub retention_hours: u64,
+    pub max_bytes: u64,
+    pub segment_max_bytes: u64,
+}
+
+struct ActiveSegment {
+    file: File,
+    meta: SegmentMeta,
+}
+
+struct StoreState {
+    /// Mühürlenmiş segmentler, sıra numarasına (yazım sırasına) göre
+    sealed: Vec<SegmentMeta>,
+    active: Option<ActiveSegment>,
+    next_seq: u64,
+}
+
+/// Her kaydın yazıldığı yalnızca-ekleme (append-only) disk deposu.
+/// Segmentler alındıkları saate göre `YYYYMMDDHH` dizinlerine bölünür, gzip ile sıkıştırılır
+/// ve yaş / toplam boyut sınırına göre en eskiden başlanarak silinir.
+#[derive(Clone)]
+pub struct SegmentStore {
+    settings: Arc<StoreSettings>,
+    state: Arc<Mutex<StoreState>>,
+}
+
+impl SegmentStore {
+    /// Dizini hazırlar, mevcut segmentleri indeksler ve çökmeden kalan aktif segmentleri mühürler.
+    pub fn open(settings: StoreSettings) -> Result<Self> {
+        fs::create_dir_all(&settings.dir)
+            .with_context(|| format!("Store dizini oluşturulamadı: {}", settings.dir.display()))?;
+
+        let mut sealed = Vec::new();
+        let mut orphans = Vec::new();
+        for partition in fs::read_dir(&settings.dir)? {
+            let partition = partition?;
+            let name = partition.file_name();
+            let Some(partition_ts) = name.to_str().and_then(segment::parse_partition) else {
+                continue;
+            };
+            for entry in fs::read_dir(partition.path())? {
+                let entry = entry?;
+                let file_name = entry.file_name();
+                let Some(file_name) = file_name.to_str() else {
+                    continue;
+                };
+                let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                if let Some((seq, min_ts_ms, max_ts_ms)) = segment::parse_sealed(file_name) {
+                    sealed.push(SegmentMeta {
+                        path: entry.path(),
+                        seq,
+                        partition_ts,
+                        min_ts_ms,
+                        max_ts_ms,
+                        bytes,
+                    });
+                } else if let Some(seq) = segment::parse_active(file_name) {
+                    orphans.push(SegmentMeta {
+                        path: entry.path(),
+                        seq,
+                        partition_ts,
+                        min_ts_ms: i64::MAX,
+                        max_ts_ms: i64::MIN,
+                        bytes,
+                    });
+                }
+            }
+        }
+
+        for mut orphan in orphans {
+            let fallback_ms = orphan.partition_ts * 1000;
+            let path = orphan.path.clone();
+            segment::read_records(&path, |log| {
+                let ts = log.ts_millis().unwrap_or(fallback_ms);
+                orphan.extend(ts, ts);
+                true
+            })?;
+            if let Some(meta) = seal_file(orphan)? {
+                info!(event="STORE_SEGMENT_RECOVERED", path=%meta.path.display(), "♻️ Yarım kalan segment kurtarıldı");
+                sealed.push(meta);
+            }
+        }
+        sealed.sort_by_key(|meta| meta.seq);
+
+        let next_seq = sealed.last().map(|meta| meta.seq + 1).unwrap_or(1);
+        let total: u64 = sealed.iter().map(|meta| meta.bytes).sum();
+        info!(
+            event = "STORE_OPENED",
+            dir = %settings.dir.display(),
+            segments = sealed.len(),
+            bytes = total,
+            "🗄️ Kalıcı log deposu açıldı"
+        );
+
+        let store = Self {
+            settings: Arc::new(settings),
+            state: Arc::new(Mutex::new(StoreState {
+                sealed,
+                active: None,
+                next_seq,
+            })),
+        };
+        store.enforce_retention();
+        Ok(store)
+    }
+
+    /// Kanaldan gelen kayıtları yığınlar halinde yazan ve saklama politikasını uygulayan görev.
+    pub fn start(&self, mut rx: mpsc::Receiver<LogRecord>) {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut buffer = Vec::with_capacity(WRITE_BATCH_SIZE);
+            let mut flush_ticker = interval(Duration::from_millis(FLUSH_INTERVAL_MS));
+            let mut retention_ticker = interval(Duration::from_secs(RETENTION_INTERVAL_SECS));
+
+            loop {
+                tokio::select! {
+                    received = rx.recv() => {
+                        let Some(log) = received else {
+                            break;
+                        };
+                        buffer.push(log);
+                        if buffer.len() >= WRITE_BATCH_SIZE {
+                            store.write(std::mem::take(&mut buffer)).await;
+                        }
+                    }
+                    _ = flush_ticker.tick() => {
+                        if !buffer.is_empty() {
+                            store.write(std::mem::take(&mut buffer)).await;
+                        }
+                    }
+                    _ = retention_ticker.tick() => {
+                        let worker = store.clone();
+                        let _ = tokio::task::spawn_blocking(move || worker.enforce_retention()).await;
+                    }
+                }
+            }
+
+            // Kanal kapandı: kalanları yaz ve aktif segmenti mühürle.
+            if !buffer.is_empty() {
+                store.write(buffer).await;
+            }
+            let worker = store.clone();
+            let _ = tokio::task::spawn_blocking(move || worker.seal_active()).await;
+        });
+    }
+
+    async fn write(&self, batch: Vec<LogRecord>) {
+        let count = batch.len();
+        let worker = self.clone();
+        match tokio::task::spawn_blocking(move || worker.append(&batch)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                error!(event="STORE_WRITE_ERR", error=%e, dropped=count, "Log deposuna yazılamadı")
+            }
+            Err(e) => {
+                error!(event="STORE_WRITE_ERR", error=%e, dropped=count, "Log deposu yazma görevi çöktü")
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StoreState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn append(&self, batch: &[LogRecord]) -> io::Result<()> {
+        let now = Utc::now();
+        let (bytes, min_ts_ms, max_ts_ms) = segment::encode_batch(batch, now.timestamp_millis())?;
+        let (partition_ts, partition_name) = segment::partition_of(now);
+
+        let mut state = self.lock();
+        let rotate = state.active.as_ref().is_some_and(|active| {
+            active.meta.partition_ts != partition_ts
+                || active.meta.bytes >= self.settings.segment_max_bytes
+        });
+        // Mühürleme başarısızsa yığın atılmaz; mevcut aktif segmente yazılır, bir sonraki
+        // yığında yeniden denenir.
+        if rotate {
+            if let Err(e) = self.seal_locked(&mut state) {
+                warn!(event="STORE_SEAL_ERR", error=%e, "Segment mühürlenemedi, aktif segmente yazılmaya devam ediliyor");
+            }
+        }
+
+        let active = match state.active.as_mut() {
+            Some(active) => active,
+            None => {
+                let seq = state.next_seq;
+                let dir = self.settings.dir.join(&partition_name);
+                fs::create_dir_all(&dir)?;
+                let path = dir.join(segment::active_name(seq));
+                let file = OpenOptions::new().create(true).append(true).open(&path)?;
+                state.next_seq += 1;
+                state.active.insert(ActiveSegment {
+                    file,
+                    meta: SegmentMeta {
+                        path,
+                        seq,
+                        partition_ts,
+                        min_ts_ms: i64::MAX,
+                        max_ts_ms: i64::MIN,
+                        bytes: 0,
+                    },
+                })
+            }
+        };
+
+        active.file.write_all(&bytes)?;
+        active.file.flush()?;
+        active.meta.bytes += bytes.len() as u64;
+        active.meta.extend(min_ts_ms, max_ts_ms);
+        Ok(())
+    }
+
+    fn seal_active(&self) {
+        let mut state = self.lock();
+        if let Err(e) = self.seal_locked(&mut state) {
+            error!(event="STORE_SEAL_ERR", error=%e, "Aktif segment mühürlenemedi");
+        }
+    }
+
+    /// Aktif segment yalnızca mühürleme başarılı olunca bırakılır; hata durumunda
+    /// indekste kalır ve sorgularda görünmeye devam eder.
+    fn seal_locked(&self, state: &mut StoreState) -> io::Result<()> {
+        let Some(active) = state.active.as_ref() else {
+            return Ok(());
+        };
+        active.file.sync_data()?;
+        let sealed = seal_file(active.meta.clone())?;
+        state.active = None;
+        if let Some(meta) = sealed {
+            state.sealed.push(meta);
+        }
+        Ok(())
+    }
+
+    /// Segmentin mühürlenmiş güncel yolu; saklama politikasıyla silindiyse `None`.
+    fn sealed_path(&self, seq: u64) -> Option<PathBuf> {
+        self.lock()
+            .sealed
+            .iter()
+            .find(|meta| meta.seq == seq)
+            .map(|meta| meta.path.clone())
+    }
+
+    /// Segment okunurken dosya bulunamazsa (sorgu sürerken mühürlenip yeniden adlandırıldı)
+    /// mühürlü adıyla yeniden denenir; silinmişse atlanır.
+    fn read_segment(
+        &self,
+        meta: &SegmentMeta,
+        mut visit: impl FnMut(LogRecord) -> bool,
+    ) -> Result<()> {
+        let mut path = meta.path.clone();
+        loop {
+            match segment::read_records(&path, &mut visit) {
+                Ok(()) => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => match self.sealed_path(meta.seq) {
+                    Some(sealed) if sealed != path => path = sealed,
+                    _ => return Ok(()),
+                },
+                Err(e) => return Err(e).context(format!("Segment okunamadı: {}", path.display())),
+            }
+        }
+    }
+
+    /// Saklama süresini aşan bölümlerin ve toplam boyut sınırını aşan en eski segmentlerin silinmesi.
+    /// Aktif segment hiçbir zaman silinmez.
+    fn enforce_retention(&self) {
+        let cutoff = Utc::now().timestamp() - (self.settings.retention_hours as i64) * 3600;
+        let mut removed = Vec::new();
+
+        let mut state = self.lock();
+        // Bölüm saati bitmeden (partition_ts + 1 saat) yaşı dolmuş sayılmaz.
+        let (expired, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut state.sealed)
+            .into_iter()
+            .partition(|meta| meta.partition_ts + 3600 <= cutoff);
+        state.sealed = kept;
+        removed.extend(expired);
+
+        let active_bytes = state.active.as_ref().map(|a| a.meta.bytes).unwrap_or(0);
+        let mut total: u64 = active_bytes + state.sealed.iter().map(|meta| meta.bytes).sum::<u64>();
+        let overflow = state
+            .sealed
+            .iter()
+            .take_while(|meta| {
+                let over = total > self.settings.max_bytes;
+                total = total.saturating_sub(meta.bytes);
+                over
+            })
+            .count();
+        removed.extend(state.sealed.drain(..overflow));
+        drop(state);
+
+        if removed.is_empty() {
+            return;
+        }
+        let mut freed = 0;
+        for meta in &removed {
+            match fs::remove_file(&meta.path) {
+                Ok(()) => freed += meta.bytes,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    warn!(event="STORE_PURGE_ERR", path=%meta.path.display(), error=%e, "Segment silinemedi")
+                }
+            }
+            if let Some(dir) = meta.path.parent() {
+                // Yalnızca boşalan bölüm dizinleri silinir.
+                let _ = fs::remove_dir(dir);
+            }
+        }
+        info!(
+            event = "STORE_RETENTION_PURGED",
+            segments = removed.len(),
+            bytes = freed,
+            "🧹 Saklama politikası: eski segmentler silindi"
+        );
+    }
+
+    /// Sorguyla zaman aralığı kesişen segmentler en yeniden eskiye taranır; yeterli kayıt
+    /// toplandıktan sonra eldeki en eski kayıttan daha eski segmentler okunmaz.
+    fn scan(&self, query: &LogQuery) -> Result<Vec<LogRecord>> {
+        let limit = query.limit.clamp(1, MAX_LOG_QUERY_LIMIT);
+        let mut segments: Vec<SegmentMeta> = {
+            let state = self.lock();
+            state
+                .sealed
+                .iter()
+                .chain(state.active.as_ref().map(|active| &active.meta))
+                .filter(|meta| !meta.is_empty() && query.overlaps(meta.min_ts_ms, meta.max_ts_ms))
+                .cloned()
+                .collect()
+        };
+        segments.sort_by_key(|meta| std::cmp::Reverse(meta.max_ts_ms));
+
+        let mut matched: Vec<(i64, LogRecord)> = Vec::new();
+        for meta in segments {
+            if matched.len() >= limit {
+                let oldest = matched.iter().map(|(ts, _)| *ts).min().unwrap_or(i64::MIN);
+                if meta.max_ts_ms < oldest {
+                    break;
+                }
+            }
+            let fallback_ms = meta.partition_ts * 1000;
+            self.read_segment(&meta, |log| {
+                let ts = log.ts_millis();
+                if query.matches(&log, ts) {
+                    matched.push((ts.unwrap_or(fallback_ms), log));
+                    if matched.len() >= limit * 2 {
+                        keep_newest(&mut matched, limit);
+                    }
+                }
+                true
+            })?;
+            keep_newest(&mut matched, limit);
+        }
+
+        keep_newest(&mut matched, limit);
+        Ok(matched.into_iter().map(|(_, log)| log).collect())
+    }
+}
+
+fn keep_newest(matched: &mut Vec<(i64, LogRecord)>, limit: usize) {
+    matched.sort_by_key(|(ts, _)| std::cmp::Reverse(*ts));
+    matched.truncate(limit);
+}
+
+/// Aktif segmenti kayıt aralığını taşıyan adla yeniden adlandırır; boşsa siler.
+fn seal_file(mut meta: SegmentMeta) -> io::Result<Option<SegmentMeta>> {
+    if meta.is_empty() {
+        fs::remove_file(&meta.path)?;
+        return Ok(None);
+    }
+    let dir = meta.path.parent().unwrap_or(Path::new("."));
+    let sealed_path = dir.join(segment::sealed_name(
+        meta.seq,
+        meta.min_ts_ms,
+        meta.max_ts_ms,
+    ));
+    fs::rename(&meta.path, &sealed_path)?;
+    meta.bytes = fs::metadata(&sealed_path)
+        .map(|m| m.len())
+        .unwrap_or(meta.bytes);
+    meta.path = sealed_path;
+    Ok(Some(meta))
+}
+
+#[async_trait]
+impl LogStore for SegmentStore {
+    async fn query(&self, query: LogQuery) -> Result<Vec<LogRecord>> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || store.scan(&query)).await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_store() -> SegmentStore {
+        temp_store_with(u64::MAX, u64::MAX)
+    }
+
+    fn temp_store_with(max_bytes: u64, segment_max_bytes: u64) -> SegmentStore {
+        let dir = std::env::temp_dir().join(format!(
+            "observer-store-{}-{}",
+            std::process::id(),
+            NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        SegmentStore::open(StoreSettings {
+            dir,
+            retention_hours: 72,
+            max_bytes,
+            segment_max_bytes,
+        })
+        .expect("store açılamadı")
+    }
+
+    fn record(ts: &str, message: &str) -> LogRecord {
+        let mut log = LogRecord::new_system("INFO", "TEST", message, "t");
+        log.ts = ts.to_string();
+        log
+    }
+
+    fn messages(store: &SegmentStore, query: &LogQuery) -> Vec<String> {
+        store
+            .scan(query)
+            .expect("sorgu başarısız")
+            .into_iter()
+            .map(|log| log.message)
+            .collect()
+    }
+
+    #[test]
+    fn scan_returns_newest_matches_first() {
+        let store = temp_store();
+        store
+            .append(&[
+                record("2024-05-01T10:00:00Z", "a"),
+                record("2024-05-01T10:00:02Z", "c"),
+                record("2024-05-01T10:00:01Z", "b"),
+            ])
+            .expect("yazılamadı");
+
+        let all = LogQuery::default();
+        assert_eq!(messages(&store, &all), ["c", "b", "a"]);
+
+        let window = LogQuery {
+            from_ms: Some(1_714_557_601_000),
+            limit: 1,
+            ..LogQuery::default()
+        };
+        assert_eq!(messages(&store, &window), ["c"]);
+    }
+
+    #[test]
+    fn segment_sealed_mid_query_is_read_under_sealed_name() {
+        let store = temp_store();
+        store
+            .append(&[record("2024-05-01T10:00:00Z", "a")])
+            .expect("yazılamadı");
+        let active = store
+            .lock()
+            .active
+            .as_ref()
+            .map(|active| active.meta.clone())
+            .expect("aktif segment yok");
+
+        store.seal_active();
+        assert!(!active.path.exists());
+
+        let mut seen = Vec::new();
+        store
+            .read_segment(&active, |log| {
+                seen.push(log.message);
+                true
+            })
+            .expect("okunamadı");
+        assert_eq!(seen, ["a"]);
+    }
+
+    #[test]
+    fn failed_seal_keeps_active_segment() {
+        let store = temp_store();
+        store
+            .append(&[record("2024-05-01T10:00:00Z", "a")])
+            .expect("yazılamadı");
+        let meta = store
+            .lock()
+            .active
+            .as_ref()
+            .map(|active| active.meta.clone())
+            .expect("aktif segment yok");
+
+        // Hedef adda bir dizin varken yeniden adlandırma başarısız olur.
+        let dir = meta.path.parent().expect("bölüm dizini yok");
+        let blocker = dir.join(segment::sealed_name(
+            meta.seq,
+            meta.min_ts_ms,
+            meta.max_ts_ms,
+        ));
+        fs::create_dir(&blocker).expect("dizin oluşturulamadı");
+        {
+            let mut state = store.lock();
+            assert!(store.seal_locked(&mut state).is_err());
+            assert!(state.active.is_some());
+            assert!(state.sealed.is_empty());
+        }
+        assert_eq!(messages(&store, &LogQuery::default()), ["a"]);
+
+        fs::remove_dir(&blocker).expect("dizin silinemedi");
+        store.seal_active();
+        let state = store.lock();
+        assert!(state.active.is_none());
+        assert_eq!(state.sealed.len(), 1);
+    }
+
+    #[test]
+    fn size_retention_drops_oldest_sealed_segments() {
+        // Her yazımda segment dolar ve bir sonraki yazım onu mühürler.
+        let store = temp_store_with(1, 1);
+        for message in ["a", "b", "c"] {
+            store
+                .append(&[record("2024-05-01T10:00:00Z", message)])
+                .expect("yazılamadı");
+        }
+        assert_eq!(store.lock().sealed.len(), 2);
+
+        store.enforce_retention();
+        let state = store.lock();
+        assert!(state.sealed.is_empty());
+        assert!(state.active.is_some());
+        drop(state);
+        assert_eq!(messages(&store, &LogQuery::default()), ["c"]);
+    }
+
+    #[test]
+    fn open_recovers_orphan_active_segment() {
+        let store = temp_store();
+        store
+            .append(&[record("2024-05-01T10:00:00Z", "a")])
+            .expect("yazılamadı");
+        let dir = store.settings.dir.clone();
+        drop(store);
+
+        let reopened = SegmentStore::open(StoreSettings {
+            dir,
+            retention_hours: 72,
+            max_bytes: u64::MAX,
+            segment_max_bytes: u64::MAX,
+        })
+        .expect("store açılamadı");
+        let state = reopened.lock();
+        assert_eq!(state.sealed.len(), 1);
+        assert_eq!(state.sealed[0].min_ts_ms, 1_714_557_600_000);
+        assert_eq!(state.next_seq, 2);
+    }
+}
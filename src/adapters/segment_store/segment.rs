@@ -0,0 +1,131 @@
+// src/adapters/segment_store/segment.rs
+use crate::core::domain::LogRecord;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Timelike, Utc};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const PARTITION_FORMAT: &str = "%Y%m%d%H";
+const SEGMENT_SUFFIX: &str = ".ndjson.gz";
+const ACTIVE_SUFFIX: &str = ".active.ndjson.gz";
+
+/// Diskteki bir segment dosyası. Saat bölümü kaydın alındığı (ingest) saattir;
+/// `min_ts_ms` / `max_ts_ms` ise içindeki kayıtların `ts` aralığıdır ve sorgu budamasında kullanılır.
+#[derive(Debug, Clone)]
+pub struct SegmentMeta {
+    pub path: PathBuf,
+    pub seq: u64,
+    /// Bölüm saatinin başlangıcı (unix saniye)
+    pub partition_ts: i64,
+    pub min_ts_ms: i64,
+    pub max_ts_ms: i64,
+    pub bytes: u64,
+}
+
+impl SegmentMeta {
+    pub fn is_empty(&self) -> bool {
+        self.min_ts_ms > self.max_ts_ms
+    }
+
+    pub fn extend(&mut self, min_ts_ms: i64, max_ts_ms: i64) {
+        self.min_ts_ms = self.min_ts_ms.min(min_ts_ms);
+        self.max_ts_ms = self.max_ts_ms.max(max_ts_ms);
+    }
+}
+
+/// `LogRecord` dışarıdan gelen `smart_tags`'i kabul etmez (sunucu türetir);
+/// depodan okunan kayıtlarda yazıldığı andaki etiketler korunur.
+#[derive(Deserialize)]
+struct StoredRecord {
+    #[serde(flatten)]
+    record: LogRecord,
+    #[serde(default)]
+    smart_tags: Vec<String>,
+}
+
+/// `ts` saatinin başlangıcı (unix saniye) ve bölüm dizininin adı (`YYYYMMDDHH`, UTC).
+pub fn partition_of(ts: DateTime<Utc>) -> (i64, String) {
+    let hour = ts
+        .with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(ts);
+    (hour.timestamp(), hour.format(PARTITION_FORMAT).to_string())
+}
+
+pub fn parse_partition(name: &str) -> Option<i64> {
+    let naive = NaiveDateTime::parse_from_str(&format!("{}00", name), "%Y%m%d%H%M").ok()?;
+    Some(Utc.from_utc_datetime(&naive).timestamp())
+}
+
+pub fn active_name(seq: u64) -> String {
+    format!("seg_{:010}{}", seq, ACTIVE_SUFFIX)
+}
+
+/// Mühürlenen segmentin adı kayıt zaman aralığını taşır; açılışta dosyaları okumadan indekslenir.
+pub fn sealed_name(seq: u64, min_ts_ms: i64, max_ts_ms: i64) -> String {
+    format!(
+        "seg_{:010}_{}_{}{}",
+        seq, min_ts_ms, max_ts_ms, SEGMENT_SUFFIX
+    )
+}
+
+pub fn parse_active(name: &str) -> Option<u64> {
+    name.strip_prefix("seg_")?
+        .strip_suffix(ACTIVE_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+pub fn parse_sealed(name: &str) -> Option<(u64, i64, i64)> {
+    if name.ends_with(ACTIVE_SUFFIX) {
+        return None;
+    }
+    let body = name.strip_prefix("seg_")?.strip_suffix(SEGMENT_SUFFIX)?;
+    let mut parts = body.splitn(3, '_');
+    let seq = parts.next()?.parse().ok()?;
+    let min = parts.next()?.parse().ok()?;
+    let max = parts.next()?.parse().ok()?;
+    Some((seq, min, max))
+}
+
+/// Bir yazma yığını bağımsız bir gzip üyesi olarak (NDJSON) sıkıştırılır. Çok üyeli gzip
+/// dosyası geçerli bir gzip akışıdır; yarım kalmış son üye okuyucuda yalnızca sonu keser.
+/// Dönen aralık, çözülemeyen zaman damgaları için `fallback_ms` ile hesaplanır.
+pub fn encode_batch(batch: &[LogRecord], fallback_ms: i64) -> io::Result<(Vec<u8>, i64, i64)> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+    let mut min_ts_ms = i64::MAX;
+    let mut max_ts_ms = i64::MIN;
+
+    for log in batch {
+        let ts = log.ts_millis().unwrap_or(fallback_ms);
+        min_ts_ms = min_ts_ms.min(ts);
+        max_ts_ms = max_ts_ms.max(ts);
+        serde_json::to_writer(&mut encoder, log)?;
+        encoder.write_all(b"\n")?;
+    }
+    Ok((encoder.finish()?, min_ts_ms, max_ts_ms))
+}
+
+/// Segmentteki kayıtları sırayla `visit`'e verir; `visit` false dönerse okuma durur.
+/// Bozuk satırlar atlanır, yarım kalmış gzip üyesinde (çökme / yazım sürüyor) okuma biter.
+pub fn read_records(path: &Path, mut visit: impl FnMut(LogRecord) -> bool) -> io::Result<()> {
+    let reader = BufReader::new(MultiGzDecoder::new(File::open(path)?));
+    for line in reader.split(b'\n') {
+        let Ok(line) = line else {
+            break;
+        };
+        if let Ok(stored) = serde_json::from_slice::<StoredRecord>(&line) {
+            let mut log = stored.record;
+            log.smart_tags = stored.smart_tags;
+            if !visit(log) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
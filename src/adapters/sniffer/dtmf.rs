@@ -0,0 +1,178 @@
+// src/adapters/sniffer/dtmf.rs
+use super::rtp::RtpHeader;
+use lru::LruCache;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+
+const MAX_DTMF_STREAMS: usize = 10_000;
+
+/// RFC 4733 §2.3 telephone-event yükü: event, E/R/volume, duration.
+pub(super) struct TelephoneEvent {
+    pub event: u8,
+    pub end: bool,
+    /// dBm0 cinsinden (0 ile -63 arası), pozitif saklanır.
+    pub volume: u8,
+    /// RTP saat birimi cinsinden
+    pub duration: u16,
+}
+
+impl TelephoneEvent {
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        if payload.len() < 4 {
+            return None;
+        }
+        Some(Self {
+            event: payload[0],
+            end: payload[1] & 0x80 != 0,
+            volume: payload[1] & 0x3F,
+            duration: u16::from_be_bytes([payload[2], payload[3]]),
+        })
+    }
+}
+
+/// Tek bir tuş basımı (redundant başlangıç/bitiş paketleri birleştirilmiş).
+pub(super) struct DtmfDigit {
+    pub ssrc: u32,
+    pub event: u8,
+    pub digit: char,
+    pub duration_ms: f64,
+    pub volume: u8,
+    /// Bitiş paketi görülmeden sonraki olay başladıysa false.
+    pub ended: bool,
+}
+
+struct EventState {
+    timestamp: u32,
+    event: u8,
+    duration: u16,
+    volume: u8,
+    reported: bool,
+}
+
+/// Aynı RTP timestamp'ini paylaşan telephone-event paketlerini tek tuşa indirger.
+/// Bitiş (E) paketi RFC gereği üç kez tekrarlanır; yalnızca ilki raporlanır.
+pub(super) struct DtmfDetector {
+    streams: LruCache<(u32, SocketAddr), EventState>,
+}
+
+impl DtmfDetector {
+    pub fn new() -> Self {
+        Self {
+            streams: LruCache::new(
+                NonZeroUsize::new(MAX_DTMF_STREAMS).unwrap_or(NonZeroUsize::MIN),
+            ),
+        }
+    }
+
+    pub fn observe(
+        &mut self,
+        header: &RtpHeader,
+        te: &TelephoneEvent,
+        src: SocketAddr,
+        clock_rate: u32,
+    ) -> Vec<DtmfDigit> {
+        let mut digits = Vec::new();
+        let key = (header.ssrc, src);
+        let to_digit = |state: &EventState, ended: bool| DtmfDigit {
+            ssrc: header.ssrc,
+            event: state.event,
+            digit: event_char(state.event),
+            duration_ms: state.duration as f64 * 1000.0 / clock_rate.max(1) as f64,
+            volume: state.volume,
+            ended,
+        };
+
+        match self.streams.get_mut(&key) {
+            Some(state) if state.timestamp == header.timestamp => {
+                if state.reported {
+                    return digits;
+                }
+                state.duration = state.duration.max(te.duration);
+                state.volume = te.volume;
+                if te.end {
+                    state.reported = true;
+                    digits.push(to_digit(state, true));
+                }
+                return digits;
+            }
+            // Yeni olay: bitiş paketleri kaybolmuş önceki tuş yine de raporlanır.
+            Some(state) if !state.reported => digits.push(to_digit(state, false)),
+            _ => {}
+        }
+
+        let state = EventState {
+            timestamp: header.timestamp,
+            event: te.event,
+            duration: te.duration,
+            volume: te.volume,
+            reported: te.end,
+        };
+        if te.end {
+            digits.push(to_digit(&state, true));
+        }
+        self.streams.put(key, state);
+        digits
+    }
+}
+
+/// RFC 4733 §3.2 DTMF olay kodları.
+fn event_char(event: u8) -> char {
+    match event {
+        0..=9 => (b'0' + event) as char,
+        10 => '*',
+        11 => '#',
+        12..=15 => (b'A' + event - 12) as char,
+        16 => '!', // Flash
+        _ => '?',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(detector: &mut DtmfDetector, ts: u32, payload: [u8; 4]) -> Vec<DtmfDigit> {
+        let mut data = vec![0x80, 101, 0, 1];
+        data.extend_from_slice(&ts.to_be_bytes());
+        data.extend_from_slice(&42u32.to_be_bytes());
+        data.extend_from_slice(&payload);
+        let header = RtpHeader::parse(&data).expect("RTP çözülemedi");
+        let te = TelephoneEvent::parse(header.payload(&data)).expect("olay çözülemedi");
+        detector.observe(&header, &te, SocketAddr::from(([10, 0, 0, 1], 4000)), 8000)
+    }
+
+    #[test]
+    fn redundant_end_packets_report_one_digit() {
+        let mut detector = DtmfDetector::new();
+        assert!(event(&mut detector, 1000, [5, 10, 0x01, 0x40]).is_empty());
+        let digits = event(&mut detector, 1000, [5, 0x80 | 10, 0x03, 0x20]);
+        assert_eq!(digits.len(), 1);
+        assert_eq!(digits[0].digit, '5');
+        assert!(digits[0].ended);
+        assert_eq!(digits[0].volume, 10);
+        assert!((digits[0].duration_ms - 100.0).abs() < f64::EPSILON);
+
+        assert!(event(&mut detector, 1000, [5, 0x80 | 10, 0x03, 0x20]).is_empty());
+        assert!(event(&mut detector, 1000, [5, 0x80 | 10, 0x03, 0x20]).is_empty());
+    }
+
+    #[test]
+    fn lost_end_packet_is_reported_when_next_event_starts() {
+        let mut detector = DtmfDetector::new();
+        assert!(event(&mut detector, 1000, [10, 10, 0x00, 0xA0]).is_empty());
+        let digits = event(&mut detector, 3000, [11, 10, 0x00, 0xA0]);
+        assert_eq!(digits.len(), 1);
+        assert_eq!(digits[0].digit, '*');
+        assert!(!digits[0].ended);
+    }
+
+    #[test]
+    fn event_codes_map_to_keys() {
+        assert_eq!(event_char(0), '0');
+        assert_eq!(event_char(11), '#');
+        assert_eq!(event_char(15), 'D');
+        assert_eq!(event_char(16), '!');
+        assert_eq!(event_char(99), '?');
+        assert!(TelephoneEvent::parse(&[1, 2, 3]).is_none());
+    }
+}
@@ -0,0 +1,238 @@
+// src/adapters/sniffer/frag.rs
+use super::IpHeader;
+use crate::core::call_frames::RawFrame;
+use lru::LruCache;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+
+/// Aynı anda birleştirilmeyi bekleyen datagram üst sınırı.
+const MAX_DATAGRAMS: usize = 1024;
+/// IPv4 toplam uzunluk alanı ve IPv6 (jumbogram hariç) payload sınırı.
+const MAX_DATAGRAM_LEN: usize = 65_535;
+const MAX_FRAGMENTS_PER_DATAGRAM: usize = 64;
+/// Linux `ipfrag_time` varsayılanı (yakalama zamanı, µs).
+const FRAGMENT_TIMEOUT_US: i64 = 30_000_000;
+const SWEEP_INTERVAL_US: i64 = 5_000_000;
+
+/// IPv4 (MF / fragment offset) veya IPv6 Fragment başlığından okunan parça bilgisi.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Fragment {
+    pub id: u32,
+    /// Bayt cinsinden (başlıktaki 8 baytlık birimler çevrilmiş)
+    pub offset: usize,
+    pub more: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct DatagramKey {
+    src: IpAddr,
+    dst: IpAddr,
+    protocol: u8,
+    id: u32,
+}
+
+struct PendingDatagram {
+    parts: BTreeMap<usize, Vec<u8>>,
+    total_len: Option<usize>,
+    frames: Vec<RawFrame>,
+    first_seen_us: i64,
+}
+
+/// Birleştirilmiş L4 yükü (UDP/TCP başlığı dahil) ve datagramı oluşturan ham çerçeveler.
+pub(super) struct Reassembled {
+    pub payload: Vec<u8>,
+    pub frames: Vec<RawFrame>,
+}
+
+/// MTU'yu aşan SIP datagramları (büyük SDP, çok sayıda Via) için IP parça birleştirici.
+/// Çakışan parçalarda son gelen kazanır; eksik parçası zaman aşımına uğrayan datagram atılır.
+pub(super) struct FragmentReassembler {
+    datagrams: LruCache<DatagramKey, PendingDatagram>,
+    last_sweep_us: i64,
+}
+
+impl FragmentReassembler {
+    pub fn new() -> Self {
+        Self {
+            datagrams: LruCache::new(NonZeroUsize::new(MAX_DATAGRAMS).unwrap_or(NonZeroUsize::MIN)),
+            last_sweep_us: 0,
+        }
+    }
+
+    /// Parçayı (`ip.l4_start..ip.end`) ekler; datagram tamamlandıysa birleştirilmiş yükü döner.
+    pub fn push(
+        &mut self,
+        ip: &IpHeader,
+        fragment: Fragment,
+        data: &[u8],
+        frame: RawFrame,
+        now_us: i64,
+    ) -> Option<Reassembled> {
+        self.sweep(now_us);
+
+        let end = fragment.offset + data.len();
+        if end > MAX_DATAGRAM_LEN {
+            return None;
+        }
+
+        let key = DatagramKey {
+            src: ip.src,
+            dst: ip.dst,
+            protocol: ip.protocol,
+            id: fragment.id,
+        };
+        let datagram = self.datagrams.get_or_insert_mut(key, || PendingDatagram {
+            parts: BTreeMap::new(),
+            total_len: None,
+            frames: Vec::new(),
+            first_seen_us: now_us,
+        });
+        if datagram.parts.len() >= MAX_FRAGMENTS_PER_DATAGRAM {
+            self.datagrams.pop(&key);
+            return None;
+        }
+
+        if !fragment.more {
+            datagram.total_len = Some(end);
+        }
+        datagram.parts.insert(fragment.offset, data.to_vec());
+        datagram.frames.push(frame);
+
+        let total_len = datagram.total_len?;
+        let mut covered = 0;
+        for (offset, part) in &datagram.parts {
+            if *offset > covered {
+                return None;
+            }
+            covered = covered.max(offset + part.len());
+        }
+        if covered < total_len {
+            return None;
+        }
+
+        let datagram = self.datagrams.pop(&key)?;
+        let mut payload = vec![0u8; total_len];
+        for (offset, part) in datagram.parts {
+            let end = (offset + part.len()).min(total_len);
+            if offset < end {
+                payload[offset..end].copy_from_slice(&part[..end - offset]);
+            }
+        }
+        Some(Reassembled {
+            payload,
+            frames: datagram.frames,
+        })
+    }
+
+    fn sweep(&mut self, now_us: i64) {
+        if now_us - self.last_sweep_us < SWEEP_INTERVAL_US {
+            return;
+        }
+        self.last_sweep_us = now_us;
+
+        let expired: Vec<DatagramKey> = self
+            .datagrams
+            .iter()
+            .filter(|(_, d)| now_us - d.first_seen_us > FRAGMENT_TIMEOUT_US)
+            .map(|(k, _)| *k)
+            .collect();
+        for key in expired {
+            self.datagrams.pop(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(id_src: u8) -> IpHeader {
+        IpHeader {
+            src: IpAddr::from([10, 0, 0, id_src]),
+            dst: IpAddr::from([10, 0, 0, 2]),
+            protocol: 17,
+            l4_start: 0,
+            end: 0,
+            fragment: None,
+            ident: 0,
+        }
+    }
+
+    fn frame() -> RawFrame {
+        RawFrame {
+            ts_us: 0,
+            link_type: 1,
+            orig_len: 0,
+            data: Vec::new(),
+        }
+    }
+
+    fn fragment(offset: usize, more: bool) -> Fragment {
+        Fragment {
+            id: 7,
+            offset,
+            more,
+        }
+    }
+
+    #[test]
+    fn reassembles_out_of_order_fragments() {
+        let mut reassembler = FragmentReassembler::new();
+        assert!(reassembler
+            .push(&ip(1), fragment(8, false), b"world", frame(), 0)
+            .is_none());
+        let done = reassembler
+            .push(&ip(1), fragment(0, true), b"hello, w", frame(), 10)
+            .expect("datagram tamamlanmadı");
+        assert_eq!(done.payload, b"hello, wworld");
+        assert_eq!(done.frames.len(), 2);
+    }
+
+    #[test]
+    fn keeps_waiting_on_gap_and_separates_sources() {
+        let mut reassembler = FragmentReassembler::new();
+        assert!(reassembler
+            .push(&ip(1), fragment(0, true), b"12345678", frame(), 0)
+            .is_none());
+        // Farklı kaynak aynı id ile ayrı datagramdır.
+        assert!(reassembler
+            .push(&ip(3), fragment(16, false), b"x", frame(), 0)
+            .is_none());
+        assert!(reassembler
+            .push(&ip(1), fragment(16, false), b"x", frame(), 0)
+            .is_none());
+        let done = reassembler
+            .push(&ip(1), fragment(8, true), b"abcdefgh", frame(), 0)
+            .expect("datagram tamamlanmadı");
+        assert_eq!(done.payload, b"12345678abcdefghx");
+    }
+
+    #[test]
+    fn expired_and_oversized_datagrams_are_dropped() {
+        let mut reassembler = FragmentReassembler::new();
+        assert!(reassembler
+            .push(
+                &ip(1),
+                fragment(0, true),
+                b"12345678",
+                frame(),
+                SWEEP_INTERVAL_US
+            )
+            .is_none());
+        let later = SWEEP_INTERVAL_US * 2 + FRAGMENT_TIMEOUT_US;
+        assert!(reassembler
+            .push(&ip(1), fragment(8, false), b"x", frame(), later)
+            .is_none());
+
+        assert!(reassembler
+            .push(
+                &ip(1),
+                fragment(MAX_DATAGRAM_LEN, false),
+                b"x",
+                frame(),
+                later
+            )
+            .is_none());
+    }
+}
@@ -0,0 +1,210 @@
+// src/adapters/sniffer/media.rs
+use super::sdp::{static_codec, Codec, SessionDescription};
+use super::sip::{NameAddr, SipMessage, StartLine};
+use lru::LruCache;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+
+/// Aynı anda bağlı tutulabilecek medya uç noktası (RTP + RTCP) sayısı.
+const MAX_MEDIA_ENDPOINTS: usize = 20_000;
+const MAX_TRACKED_CALLS: usize = 10_000;
+
+pub(super) const DIRECTION_CALLER_TO_CALLEE: &str = "caller_to_callee";
+pub(super) const DIRECTION_CALLEE_TO_CALLER: &str = "callee_to_caller";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Party {
+    Caller,
+    Callee,
+}
+
+/// SDP ile ilan edilmiş bir medya adresinin ait olduğu çağrı.
+#[derive(Debug, Clone)]
+pub(super) struct MediaBinding {
+    pub call_id: String,
+    pub owner: Party,
+    pub rtcp: bool,
+    pub codecs: HashMap<u8, Codec>,
+}
+
+impl MediaBinding {
+    /// Akışın ses/görüntü kodeğinin RTP saat hızı (telephone-event / CN hariç, en küçük PT).
+    pub fn media_clock_rate(&self) -> u32 {
+        let mut pts: Vec<&u8> = self.codecs.keys().collect();
+        pts.sort();
+        pts.into_iter()
+            .filter_map(|pt| self.codecs.get(pt))
+            .find(|c| {
+                !c.name.eq_ignore_ascii_case("telephone-event")
+                    && !c.name.eq_ignore_ascii_case("CN")
+            })
+            .map(|c| c.clock_rate)
+            .unwrap_or(8000)
+    }
+}
+
+struct CallMedia {
+    caller_tag: Option<String>,
+    endpoints: Vec<SocketAddr>,
+}
+
+/// SIP/SDP trafiğinden öğrenilen "medya adresi -> Call-ID" tablosu.
+/// Girdiler BYE/CANCEL ile, aksi halde LRU sınırıyla düşer.
+pub(super) struct MediaRegistry {
+    endpoints: LruCache<SocketAddr, MediaBinding>,
+    calls: LruCache<String, CallMedia>,
+}
+
+impl MediaRegistry {
+    pub fn new() -> Self {
+        Self {
+            endpoints: LruCache::new(
+                NonZeroUsize::new(MAX_MEDIA_ENDPOINTS).unwrap_or(NonZeroUsize::MIN),
+            ),
+            calls: LruCache::new(NonZeroUsize::new(MAX_TRACKED_CALLS).unwrap_or(NonZeroUsize::MIN)),
+        }
+    }
+
+    /// Her SIP mesajında çağrılır: SDP varsa adresleri bağlar, BYE/CANCEL'da çözer.
+    pub fn observe(&mut self, msg: &SipMessage) {
+        let Some(call_id) = msg.header("call-id") else {
+            return;
+        };
+        let from_tag = msg
+            .header("from")
+            .and_then(|f| NameAddr::parse(f).tag)
+            .unwrap_or_default();
+
+        let is_request = match &msg.start {
+            StartLine::Request { method, .. } => {
+                if method == "BYE" || method == "CANCEL" {
+                    self.release(call_id);
+                    return;
+                }
+                // Arayan taraf, diyaloğu başlatan INVITE'ın From-tag'i ile tanınır.
+                if method == "INVITE" {
+                    let call = self.call_entry(call_id);
+                    if call.caller_tag.is_none() && !from_tag.is_empty() {
+                        call.caller_tag = Some(from_tag.clone());
+                    }
+                }
+                true
+            }
+            StartLine::Response { .. } => false,
+        };
+
+        let has_sdp = msg
+            .header("content-type")
+            .map(|ct| ct.to_ascii_lowercase().contains("application/sdp"))
+            .unwrap_or(true);
+        if !has_sdp {
+            return;
+        }
+        let Some(sdp) = SessionDescription::parse(&msg.body) else {
+            return;
+        };
+
+        // İstekte SDP'nin sahibi göndericidir; yanıtta ise isteği alan taraftır.
+        // From başlığı istek göndericisini taşır, yanıtlarda da aynen korunur.
+        let call = self.call_entry(call_id);
+        let from_is_caller = match &call.caller_tag {
+            Some(tag) => *tag == from_tag,
+            None => true,
+        };
+        let owner = if from_is_caller == is_request {
+            Party::Caller
+        } else {
+            Party::Callee
+        };
+
+        let mut bound = Vec::new();
+        for media in sdp.media {
+            if media.port == 0 || !(media.kind == "audio" || media.kind == "video") {
+                continue;
+            }
+            let Some(addr) = media.addr.filter(|a| !a.is_unspecified()) else {
+                continue;
+            };
+
+            let mut codecs = media.codecs;
+            for pt in &media.payload_types {
+                if let Some(codec) = static_codec(*pt) {
+                    codecs.entry(*pt).or_insert(codec);
+                }
+            }
+
+            let rtp_addr = SocketAddr::new(addr, media.port);
+            let rtcp_addr = SocketAddr::new(addr, media.rtcp_port);
+            let binding = MediaBinding {
+                call_id: call_id.to_string(),
+                owner,
+                rtcp: false,
+                codecs,
+            };
+            if rtcp_addr != rtp_addr {
+                self.endpoints.put(
+                    rtcp_addr,
+                    MediaBinding {
+                        rtcp: true,
+                        ..binding.clone()
+                    },
+                );
+                bound.push(rtcp_addr);
+            }
+            self.endpoints.put(rtp_addr, binding);
+            bound.push(rtp_addr);
+        }
+
+        let call = self.call_entry(call_id);
+        for addr in bound {
+            if !call.endpoints.contains(&addr) {
+                call.endpoints.push(addr);
+            }
+        }
+    }
+
+    /// Paketin bağlı olduğu çağrı ve akış yönü. Önce hedef, sonra (simetrik RTP için) kaynak denenir.
+    pub fn lookup(
+        &mut self,
+        src: SocketAddr,
+        dst: SocketAddr,
+    ) -> Option<(MediaBinding, &'static str)> {
+        if let Some(binding) = self.endpoints.get(&dst) {
+            let direction = match binding.owner {
+                Party::Caller => DIRECTION_CALLEE_TO_CALLER,
+                Party::Callee => DIRECTION_CALLER_TO_CALLEE,
+            };
+            return Some((binding.clone(), direction));
+        }
+        let binding = self.endpoints.get(&src)?;
+        let direction = match binding.owner {
+            Party::Caller => DIRECTION_CALLER_TO_CALLEE,
+            Party::Callee => DIRECTION_CALLEE_TO_CALLER,
+        };
+        Some((binding.clone(), direction))
+    }
+
+    fn call_entry(&mut self, call_id: &str) -> &mut CallMedia {
+        self.calls
+            .get_or_insert_mut(call_id.to_string(), || CallMedia {
+                caller_tag: None,
+                endpoints: Vec::new(),
+            })
+    }
+
+    fn release(&mut self, call_id: &str) {
+        if let Some(call) = self.calls.pop(call_id) {
+            for addr in call.endpoints {
+                // Adres bu arada başka bir çağrıya devredilmiş olabilir.
+                if self
+                    .endpoints
+                    .peek(&addr)
+                    .is_some_and(|b| b.call_id == call_id)
+                {
+                    self.endpoints.pop(&addr);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1684 @@
+// src/adapters/sniffer/mod.rs
+mod dtmf;
+mod frag;
+mod media;
+mod rtcp;
+mod rtp;
+mod sdp;
+mod sip;
+mod tcp;
+mod ws;
+
+use crate::core::call_frames::{CallFrameStore, RawFrame};
+use crate::core::domain::{LogRecord, ResourceContext};
+use crate::ports::LogIngestor;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use dtmf::{DtmfDetector, DtmfDigit, TelephoneEvent};
+use frag::{Fragment, FragmentReassembler};
+use lru::LruCache;
+use media::{MediaBinding, MediaRegistry};
+use pcap::{Capture, Device, Linktype};
+use rtcp::RtcpPacket;
+use rtp::{RtpHeader, RtpStreamTracker, StreamContext, StreamReport};
+use sdp::{static_codec, Codec};
+use serde_json::Value;
+use sip::{param, NameAddr, SipMessage, StartLine};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tcp::{FlowKey, TcpReassembler};
+use tokio::sync::mpsc::Sender;
+use tokio::sync::Mutex;
+use tracing::{debug, error, info};
+
+pub struct NetworkSniffer {
+    interface: String,
+    filter: String,
+    tx: Sender<LogRecord>,
+    node_name: String,
+    active_flag: Arc<AtomicBool>,
+    tenant_id: String, // [ARCH-COMPLIANCE] Dinamik tenant
+    frame_store: Arc<Mutex<CallFrameStore>>,
+}
+
+/// Ham çerçeveleri (Ethernet/SLL/Loopback) SIP/RTP `LogRecord`'larına çeviren ortak hat.
+/// Canlı arayüz (NetworkSniffer) ve pcap dosyası (PcapFileIngestor) aynı mantığı kullanır.
+pub struct PacketProcessor {
+    interface: String,
+    node_name: String,
+    tenant_id: String,
+    /// Parmak izi -> son görülme zamanı (µs).
+    seen_packets: LruCache<PacketFingerprint, i64>,
+    /// Canlı "any" yakalamasındaki kopyalar için; dosya replay'inde kapatılır.
+    dedup: bool,
+    tcp: TcpReassembler,
+    fragments: FragmentReassembler,
+    media: MediaRegistry,
+    rtp_streams: RtpStreamTracker,
+    dtmf: DtmfDetector,
+    frame_store: Arc<Mutex<CallFrameStore>>,
+    /// İşlenmekte olan çerçevenin ait olduğu çağrılar (SIP Call-ID / medya bağı).
+    frame_traces: Vec<String>,
+    /// Henüz tam bir SIP mesajı tamamlamamış TCP segmentleri; mesaj çıkınca çağrıya yazılır.
+    pending_tcp_frames: LruCache<FlowKey, Vec<RawFrame>>,
+}
+
+/// IP ve taşıma (UDP/TCP) katmanları soyulmuş paket (IPv4 veya IPv6).
+struct Segment {
+    src_ip: IpAddr,
+    dst_ip: IpAddr,
+    src_port: u16,
+    dst_port: u16,
+    transport: Transport,
+    payload: Vec<u8>,
+}
+
+enum Transport {
+    Udp,
+    Tcp { seq: u32, flags: u8 },
+}
+
+impl Transport {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Transport::Udp => "udp",
+            Transport::Tcp { .. } => "tcp",
+        }
+    }
+}
+
+/// IP başlığından çıkarılan adresler ve L4 sınırları.
+#[derive(Clone, Copy)]
+struct IpHeader {
+    src: IpAddr,
+    dst: IpAddr,
+    protocol: u8,
+    l4_start: usize,
+    /// IP toplam uzunluğuna göre paketin sonu (Ethernet dolgu baytları hariç).
+    end: usize,
+    /// Parçalanmış datagramın bir parçasıysa; `l4_start..end` parça verisidir.
+    fragment: Option<Fragment>,
+    /// IPv4 Identification alanı; IPv6'da Fragment başlığındaki id (yoksa 0).
+    ident: u32,
+}
+
+/// "any" arayüzünde aynı paketin tekrarını tanımak için çözülmüş IP başlığından
+/// çıkarılan parmak izi. Parça id + offset sayesinde aynı datagramın parçaları
+/// birbirinin tekrarı sayılmaz; taşıma başlığının ilk baytları (portlar, UDP
+/// checksum'ı, TCP seq/checksum, RTP seq/timestamp) eşit boyutlu farklı paketleri ayırır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PacketFingerprint {
+    src: IpAddr,
+    dst: IpAddr,
+    protocol: u8,
+    ident: u32,
+    fragment_offset: usize,
+    len: u32,
+    l4_head: [u8; FINGERPRINT_L4_BYTES],
+}
+
+impl PacketFingerprint {
+    fn new(ip: &IpHeader, data: &[u8], len: u32) -> Self {
+        let mut l4_head = [0u8; FINGERPRINT_L4_BYTES];
+        if let Some(l4) = data.get(ip.l4_start..ip.end) {
+            let n = l4.len().min(FINGERPRINT_L4_BYTES);
+            l4_head[..n].copy_from_slice(&l4[..n]);
+        }
+        Self {
+            src: ip.src,
+            dst: ip.dst,
+            protocol: ip.protocol,
+            ident: ip.ident,
+            fragment_offset: ip.fragment.map_or(0, |f| f.offset),
+            len,
+            l4_head,
+        }
+    }
+}
+
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+/// Sonsuz/bozuk uzantı zincirlerine karşı üst sınır.
+const MAX_IPV6_EXT_HEADERS: usize = 8;
+const MAX_PENDING_TCP_FLOWS: usize = 1024;
+const MAX_PENDING_TCP_FRAMES: usize = 32;
+/// Parmak izine giren taşıma katmanı baytı: TCP başlığının tamamı (seçeneksiz) veya
+/// UDP başlığı + RTP başlığı.
+const FINGERPRINT_L4_BYTES: usize = 20;
+/// Aynı paketin farklı arayüzlerdeki kopyaları mikro saniyeler içinde görülür; bu pencere
+/// TCP yeniden iletim süresinin (RTO >= 200 ms) altında tutulur.
+const DUPLICATE_WINDOW_US: i64 = 50_000;
+
+/// Pcap başlığındaki yakalama zamanını mikro saniyeye çevirir.
+/// `timeval` alan tipleri platforma göre (i32/i64) değiştiği için cast bilinçlidir.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn capture_micros(header: &pcap::PacketHeader) -> i64 {
+    header.ts.tv_sec as i64 * 1_000_000 + header.ts.tv_usec as i64
+}
+
+impl NetworkSniffer {
+    pub fn new(
+        interface: &str,
+        filter: &str,
+        tx: Sender<LogRecord>,
+        node_name: String,
+        active_flag: Arc<AtomicBool>,
+        tenant_id: String,
+        frame_store: Arc<Mutex<CallFrameStore>>,
+    ) -> Self {
+        let safe_filter = if filter.trim() == "any" || filter.trim().is_empty() {
+            "".to_string()
+        } else {
+            filter.to_string()
+        };
+
+        Self {
+            interface: interface.to_string(),
+            filter: safe_filter,
+            tx,
+            node_name,
+            active_flag,
+            tenant_id,
+            frame_store,
+        }
+    }
+}
+
+impl PacketProcessor {
+    pub fn new(
+        interface: &str,
+        node_name: String,
+        tenant_id: String,
+        frame_store: Arc<Mutex<CallFrameStore>>,
+    ) -> Self {
+        Self {
+            interface: interface.to_string(),
+            node_name,
+            tenant_id,
+            seen_packets: LruCache::new(NonZeroUsize::new(1000).unwrap_or(NonZeroUsize::MIN)),
+            dedup: true,
+            tcp: TcpReassembler::new(),
+            fragments: FragmentReassembler::new(),
+            media: MediaRegistry::new(),
+            rtp_streams: RtpStreamTracker::new(),
+            dtmf: DtmfDetector::new(),
+            frame_store,
+            frame_traces: Vec::new(),
+            pending_tcp_frames: LruCache::new(
+                NonZeroUsize::new(MAX_PENDING_TCP_FLOWS).unwrap_or(NonZeroUsize::MIN),
+            ),
+        }
+    }
+
+    /// Tekilleştirmeyi kapatır. Dosyadaki her kayıt bilinçli olarak yakalanmıştır;
+    /// checksum'ı boş eşit boyutlu farklı paketler parmak izinde çakışabileceği için
+    /// replay'de hiçbir paket atılmaz.
+    pub fn without_dedup(mut self) -> Self {
+        self.dedup = false;
+        self
+    }
+
+    /// Tek bir yakalanmış çerçeveyi işler. "any" arayüzünde aynı paket birden fazla
+    /// kez görülebildiği için parmak izi ile tekilleştirme burada yapılır.
+    pub fn handle_packet(&mut self, packet: &pcap::Packet, link_type: Linktype) -> Vec<LogRecord> {
+        let len = packet.header.len;
+        let captured_us = capture_micros(packet.header);
+        let captured_at =
+            chrono::DateTime::from_timestamp_micros(captured_us).unwrap_or_else(chrono::Utc::now);
+
+        let ts = captured_at.to_rfc3339();
+
+        let mut records = Vec::new();
+        let Some(ip) = Self::parse_ip(packet.data, link_type) else {
+            return records;
+        };
+        if self.dedup {
+            let fingerprint = PacketFingerprint::new(&ip, packet.data, len);
+            if let Some(seen_us) = self.seen_packets.put(fingerprint, captured_us) {
+                if (captured_us - seen_us).abs() <= DUPLICATE_WINDOW_US {
+                    return records;
+                }
+            }
+        }
+        self.frame_traces.clear();
+
+        // Parçalanmış datagram: son parça gelene kadar bekletilir, sonra tek segment olarak işlenir.
+        let mut fragment_frames = None;
+        let segment = match ip.fragment {
+            None => Self::parse_transport(&ip, packet.data),
+            Some(fragment) => {
+                if !matches!(ip.protocol, IPPROTO_UDP | IPPROTO_TCP) {
+                    return records;
+                }
+                let Some(data) = packet.data.get(ip.l4_start..ip.end) else {
+                    return records;
+                };
+                let frame = Self::raw_frame(packet, link_type, captured_us);
+                let reassembled = self.fragments.push(&ip, fragment, data, frame, captured_us);
+                reassembled.and_then(|datagram| {
+                    let whole = IpHeader {
+                        l4_start: 0,
+                        end: datagram.payload.len(),
+                        fragment: None,
+                        ..ip
+                    };
+                    fragment_frames = Some(datagram.frames);
+                    Self::parse_transport(&whole, &datagram.payload)
+                })
+            }
+        };
+        let Some(segment) = segment else {
+            return records;
+        };
+
+        let mut tcp_key = None;
+        match segment.transport {
+            Transport::Udp => {
+                records.extend(self.process_payload(&segment, len, captured_us, &ts));
+            }
+            Transport::Tcp { seq, flags } => {
+                let key = FlowKey {
+                    src_ip: segment.src_ip,
+                    src_port: segment.src_port,
+                    dst_ip: segment.dst_ip,
+                    dst_port: segment.dst_port,
+                };
+                tcp_key = Some(key);
+                for message in self
+                    .tcp
+                    .push(key, seq, flags, &segment.payload, captured_us)
+                {
+                    if let Ok(text) = std::str::from_utf8(&message.data) {
+                        if let Some(mut log) =
+                            self.create_sip_log(text, &segment, message.data.len() as u32, &ts)
+                        {
+                            log.attributes.insert(
+                                "net.transport".to_string(),
+                                Value::String(message.transport.to_string()),
+                            );
+                            records.push(log);
+                        }
+                    }
+                }
+            }
+        }
+        self.retain_frame(packet, link_type, captured_us, tcp_key, fragment_frames);
+        records.extend(self.stream_reports(captured_us));
+        records
+    }
+
+    fn raw_frame(packet: &pcap::Packet, link_type: Linktype, captured_us: i64) -> RawFrame {
+        RawFrame {
+            ts_us: captured_us,
+            link_type: link_type.0,
+            orig_len: packet.header.len,
+            data: packet.data.to_vec(),
+        }
+    }
+
+    /// Çerçeveyi ait olduğu çağrı(lar) için ham çerçeve deposuna yazar.
+    fn retain_frame(
+        &mut self,
+        packet: &pcap::Packet,
+        link_type: Linktype,
+        captured_us: i64,
+        tcp_key: Option<FlowKey>,
+        fragment_frames: Option<Vec<RawFrame>>,
+    ) {
+        let mut traces = std::mem::take(&mut self.frame_traces);
+        let tracking = tcp_key.is_some_and(|key| self.tcp.is_tracking(&key));
+        if traces.is_empty() && !tracking {
+            if let Some(key) = tcp_key {
+                self.pending_tcp_frames.pop(&key);
+            }
+            return;
+        }
+
+        let current = fragment_frames
+            .unwrap_or_else(|| vec![Self::raw_frame(packet, link_type, captured_us)]);
+        let mut frames = Vec::new();
+        if let Some(key) = tcp_key {
+            if traces.is_empty() {
+                let pending = self.pending_tcp_frames.get_or_insert_mut(key, Vec::new);
+                let room = MAX_PENDING_TCP_FRAMES.saturating_sub(pending.len());
+                pending.extend(current.into_iter().take(room));
+                return;
+            }
+            frames.extend(self.pending_tcp_frames.pop(&key).unwrap_or_default());
+        }
+        frames.extend(current);
+
+        traces.sort();
+        traces.dedup();
+        let mut store = self.frame_store.blocking_lock();
+        for trace in &traces {
+            for frame in &frames {
+                store.push(trace, frame.clone());
+            }
+        }
+    }
+
+    /// Paket gelmediği anlarda (canlı yakalama zaman aşımı) periyodik RTP raporlarını tetikler.
+    pub fn tick(&mut self, now_us: i64) -> Vec<LogRecord> {
+        self.stream_reports(now_us)
+    }
+
+    /// Yakalama kaynağı tükendiğinde açık kalan akışların final raporları.
+    pub fn finish(&mut self) -> Vec<LogRecord> {
+        let reports = self.rtp_streams.drain();
+        reports
+            .iter()
+            .map(|r| self.create_stream_stats_log(r))
+            .collect()
+    }
+
+    fn stream_reports(&mut self, now_us: i64) -> Vec<LogRecord> {
+        let reports = self.rtp_streams.poll(now_us);
+        reports
+            .iter()
+            .map(|r| self.create_stream_stats_log(r))
+            .collect()
+    }
+
+    fn parse_ip(data: &[u8], link_type: Linktype) -> Option<IpHeader> {
+        let offset = match link_type {
+            Linktype::ETHERNET => {
+                if data.len() < 14 {
+                    return None;
+                }
+                let ether_type = ((data[12] as u16) << 8) | data[13] as u16;
+                if ether_type == 0x8100 {
+                    18
+                } else {
+                    14
+                }
+            }
+            Linktype::LINUX_SLL => 16,
+            Linktype::NULL | Linktype::LOOP => 4,
+            _ => 14,
+        };
+        if data.len() <= offset {
+            return None;
+        }
+        let ip_header_start = offset;
+        match data[ip_header_start] >> 4 {
+            4 => Self::parse_ipv4(data, ip_header_start),
+            6 => Self::parse_ipv6(data, ip_header_start),
+            _ => None,
+        }
+    }
+
+    /// `data[ip.l4_start..ip.end]` aralığındaki UDP/TCP başlığını çözer.
+    fn parse_transport(ip: &IpHeader, data: &[u8]) -> Option<Segment> {
+        let l4 = ip.l4_start;
+        if ip.end < l4 + 8 {
+            return None;
+        }
+
+        let (transport, payload_start) = match ip.protocol {
+            IPPROTO_UDP => (Transport::Udp, l4 + 8),
+            IPPROTO_TCP => {
+                if ip.end < l4 + 20 {
+                    return None;
+                }
+                let seq =
+                    u32::from_be_bytes([data[l4 + 4], data[l4 + 5], data[l4 + 6], data[l4 + 7]]);
+                let data_offset = (data[l4 + 12] >> 4) as usize * 4;
+                let flags = data[l4 + 13];
+                (Transport::Tcp { seq, flags }, l4 + data_offset.max(20))
+            }
+            _ => return None,
+        };
+        if ip.end < payload_start {
+            return None;
+        }
+        // TCP'de boş payload (FIN/RST) akış durumu için anlamlıdır; UDP'de değildir.
+        if matches!(transport, Transport::Udp) && ip.end == payload_start {
+            return None;
+        }
+
+        Some(Segment {
+            src_ip: ip.src,
+            dst_ip: ip.dst,
+            src_port: u16::from_be_bytes([data[l4], data[l4 + 1]]),
+            dst_port: u16::from_be_bytes([data[l4 + 2], data[l4 + 3]]),
+            transport,
+            payload: data[payload_start..ip.end].to_vec(),
+        })
+    }
+
+    fn parse_ipv4(data: &[u8], start: usize) -> Option<IpHeader> {
+        if data.len() < start + 20 {
+            return None;
+        }
+        let ip_header_len = (data[start] & 0x0F) as usize * 4;
+        if ip_header_len < 20 {
+            return None;
+        }
+        let src = Ipv4Addr::new(
+            data[start + 12],
+            data[start + 13],
+            data[start + 14],
+            data[start + 15],
+        );
+        let dst = Ipv4Addr::new(
+            data[start + 16],
+            data[start + 17],
+            data[start + 18],
+            data[start + 19],
+        );
+        let total_len = u16::from_be_bytes([data[start + 2], data[start + 3]]) as usize;
+
+        // Flags (MF = 0x2000) + 13 bit fragment offset (8 baytlık birim)
+        let frag_field = u16::from_be_bytes([data[start + 6], data[start + 7]]);
+        let more = frag_field & 0x2000 != 0;
+        let frag_offset = (frag_field & 0x1FFF) as usize * 8;
+        let ident = u16::from_be_bytes([data[start + 4], data[start + 5]]) as u32;
+        let fragment = (more || frag_offset != 0).then_some(Fragment {
+            id: ident,
+            offset: frag_offset,
+            more,
+        });
+
+        Some(IpHeader {
+            src: IpAddr::V4(src),
+            dst: IpAddr::V4(dst),
+            protocol: data[start + 9],
+            l4_start: start + ip_header_len,
+            end: Self::ip_end(data, start, total_len),
+            fragment,
+            ident,
+        })
+    }
+
+    /// IPv6 sabit başlığı + uzantı başlığı zinciri (Hop-by-Hop, Routing, Fragment,
+    /// Destination Options, AH) yürünerek gerçek L4 protokolüne ulaşılır.
+    fn parse_ipv6(data: &[u8], start: usize) -> Option<IpHeader> {
+        if data.len() < start + 40 {
+            return None;
+        }
+        let mut src = [0u8; 16];
+        src.copy_from_slice(&data[start + 8..start + 24]);
+        let mut dst = [0u8; 16];
+        dst.copy_from_slice(&data[start + 24..start + 40]);
+
+        let mut next_header = data[start + 6];
+        let mut offset = start + 40;
+        let mut fragment = None;
+
+        for _ in 0..MAX_IPV6_EXT_HEADERS {
+            match next_header {
+                // Hop-by-Hop (0), Routing (43), Destination Options (60): (len + 1) * 8 bayt
+                0 | 43 | 60 => {
+                    if data.len() < offset + 2 {
+                        return None;
+                    }
+                    next_header = data[offset];
+                    offset += (data[offset + 1] as usize + 1) * 8;
+                }
+                // Fragment (44): sabit 8 bayt. Sonrası parçalanabilir kısımdır; birleştirme
+                // sonrası L4 başlığı ilk parçanın başında yer alır.
+                44 => {
+                    if data.len() < offset + 8 {
+                        return None;
+                    }
+                    let frag_field = u16::from_be_bytes([data[offset + 2], data[offset + 3]]);
+                    let more = frag_field & 0x0001 != 0;
+                    let frag_offset = (frag_field >> 3) as usize * 8;
+                    // Atomik parça (RFC 6946): offset 0 ve M=0 ise tek başına tam datagramdır.
+                    if more || frag_offset != 0 {
+                        fragment = Some(Fragment {
+                            id: u32::from_be_bytes([
+                                data[offset + 4],
+                                data[offset + 5],
+                                data[offset + 6],
+                                data[offset + 7],
+                            ]),
+                            offset: frag_offset,
+                            more,
+                        });
+                    }
+                    next_header = data[offset];
+                    offset += 8;
+                    if fragment.is_some() {
+                        break;
+                    }
+                }
+                // Authentication Header (51): (len + 2) * 4 bayt
+                51 => {
+                    if data.len() < offset + 2 {
+                        return None;
+                    }
+                    next_header = data[offset];
+                    offset += (data[offset + 1] as usize + 2) * 4;
+                }
+                // No Next Header (59)
+                59 => return None,
+                _ => break,
+            }
+        }
+        if fragment.is_none() && matches!(next_header, 0 | 43 | 44 | 51 | 60) {
+            return None;
+        }
+
+        let payload_len = u16::from_be_bytes([data[start + 4], data[start + 5]]) as usize;
+        let ip_len = if payload_len == 0 {
+            0
+        } else {
+            40 + payload_len
+        };
+        Some(IpHeader {
+            src: IpAddr::V6(Ipv6Addr::from(src)),
+            dst: IpAddr::V6(Ipv6Addr::from(dst)),
+            protocol: next_header,
+            l4_start: offset,
+            end: Self::ip_end(data, start, ip_len),
+            fragment,
+            ident: fragment.map_or(0, |f| f.id),
+        })
+    }
+
+    /// Snaplen kırpması veya hatalı uzunluk alanına karşı çerçeve sınırına kırpar.
+    /// Sıfır uzunluk (ör. TSO/jumbogram) durumunda çerçevenin sonu kullanılır.
+    fn ip_end(data: &[u8], start: usize, ip_len: usize) -> usize {
+        if ip_len == 0 {
+            data.len()
+        } else {
+            (start + ip_len).min(data.len())
+        }
+    }
+
+    fn process_payload(
+        &mut self,
+        segment: &Segment,
+        original_len: u32,
+        captured_us: i64,
+        ts: &str,
+    ) -> Vec<LogRecord> {
+        let payload = segment.payload.as_slice();
+        if let Ok(data_str) = std::str::from_utf8(payload) {
+            if data_str.contains("SIP/2.0") {
+                return self
+                    .create_sip_log(data_str, segment, original_len, ts)
+                    .into_iter()
+                    .collect();
+            }
+        }
+        let src = SocketAddr::new(segment.src_ip, segment.src_port);
+        let dst = SocketAddr::new(segment.dst_ip, segment.dst_port);
+
+        // RTCP (PT 200-207) RTP'den önce ayrılır; rtcp-mux'ta aynı portu paylaşırlar.
+        // SDP ile bağlanmamış adreste yalnızca RFC 3550 A.2 doğrulamasından geçen paket kabul edilir.
+        if rtcp::looks_like_rtcp(payload) {
+            let binding = self.media.lookup(src, dst);
+            let Some(packets) = rtcp::parse(payload, binding.is_none()) else {
+                return Vec::new();
+            };
+            if let Some((binding, _)) = &binding {
+                self.frame_traces.push(binding.call_id.clone());
+            }
+            return vec![self.create_rtcp_log(
+                &packets,
+                segment,
+                original_len,
+                captured_us,
+                ts,
+                binding,
+            )];
+        }
+
+        if payload.len() <= 12 {
+            return Vec::new();
+        }
+        let Some(header) = RtpHeader::parse(payload) else {
+            return Vec::new();
+        };
+        let pt = header.payload_type;
+        let binding = match self.media.lookup(src, dst) {
+            // SDP ile bağlanmış akışta PT listesi tahmin edilmez.
+            Some((binding, direction)) => {
+                if binding.rtcp {
+                    return Vec::new();
+                }
+                self.frame_traces.push(binding.call_id.clone());
+                Some((binding, direction))
+            }
+            None => {
+                if !(pt == 0 || pt == 8 || pt == 18 || pt == 101 || (96..=127).contains(&pt)) {
+                    return Vec::new();
+                }
+                None
+            }
+        };
+
+        let codec = binding
+            .as_ref()
+            .and_then(|(b, _)| b.codecs.get(&pt).cloned())
+            .or_else(|| static_codec(pt));
+        let is_dtmf = match &codec {
+            Some(c) => c.name.eq_ignore_ascii_case("telephone-event"),
+            None => pt == 101,
+        };
+        self.rtp_streams.observe(
+            &header,
+            src,
+            dst,
+            captured_us,
+            StreamContext {
+                call_id: binding.as_ref().map(|(b, _)| b.call_id.as_str()),
+                direction: binding.as_ref().map(|(_, d)| *d),
+                codec: codec.as_ref(),
+                telephone_event: is_dtmf,
+            },
+        );
+
+        // telephone-event paketleri tek tek değil, tuş basımı başına bir kayıt olarak raporlanır.
+        if is_dtmf {
+            let Some(te) = TelephoneEvent::parse(header.payload(payload)) else {
+                return Vec::new();
+            };
+            let clock_rate = codec.as_ref().map(|c| c.clock_rate).unwrap_or(8000);
+            return self
+                .dtmf
+                .observe(&header, &te, src, clock_rate)
+                .iter()
+                .map(|digit| self.create_dtmf_log(digit, segment, original_len, ts, &binding))
+                .collect();
+        }
+
+        vec![self.create_rtp_log(&header, codec, segment, original_len, ts, binding)]
+    }
+
+    fn net_attributes(&self, segment: &Segment, len: u32) -> HashMap<String, Value> {
+        let mut attributes = HashMap::new();
+        attributes.insert("net.packet_len".to_string(), Value::from(len));
+        attributes.insert(
+            "net.interface".to_string(),
+            Value::String(self.interface.clone()),
+        );
+        attributes.insert(
+            "net.src_ip".to_string(),
+            Value::String(segment.src_ip.to_string()),
+        );
+        attributes.insert(
+            "net.dst_ip".to_string(),
+            Value::String(segment.dst_ip.to_string()),
+        );
+        attributes.insert("net.src_port".to_string(), Value::from(segment.src_port));
+        attributes.insert("net.dst_port".to_string(), Value::from(segment.dst_port));
+        attributes.insert(
+            "net.transport".to_string(),
+            Value::String(segment.transport.as_str().to_string()),
+        );
+        attributes
+    }
+
+    fn create_sip_log(
+        &mut self,
+        data: &str,
+        segment: &Segment,
+        len: u32,
+        ts: &str,
+    ) -> Option<LogRecord> {
+        let msg = SipMessage::parse(data)?;
+        self.media.observe(&msg);
+        let method = match &msg.start {
+            StartLine::Request { method, .. } => method.clone(),
+            StartLine::Response { code, .. } => format!("RESPONSE/{}", code),
+        };
+        let call_id = msg.header("call-id").unwrap_or("unknown").to_string();
+        if msg.header("call-id").is_some() {
+            self.frame_traces.push(call_id.clone());
+        }
+
+        let mut attributes = self.net_attributes(segment, len);
+        let mut put = |key: &str, value: &str| {
+            if !value.is_empty() {
+                attributes.insert(key.to_string(), Value::String(value.to_string()));
+            }
+        };
+        put("sip.method", &method);
+        put("sip.call_id", &call_id);
+
+        match &msg.start {
+            StartLine::Request { uri, .. } => put("sip.request_uri", uri),
+            StartLine::Response { reason, .. } => put("sip.reason_phrase", reason),
+        }
+
+        for (header, prefix) in [("from", "sip.from"), ("to", "sip.to")] {
+            if let Some(value) = msg.header(header) {
+                let addr = NameAddr::parse(value);
+                put(prefix, &addr.uri);
+                put(&format!("{}_user", prefix), addr.user().unwrap_or(""));
+                put(
+                    &format!("{}_display", prefix),
+                    addr.display.as_deref().unwrap_or(""),
+                );
+                put(
+                    &format!("{}_tag", prefix),
+                    addr.tag.as_deref().unwrap_or(""),
+                );
+            }
+        }
+
+        if let Some(cseq) = msg.header("cseq") {
+            put("sip.cseq", cseq);
+        }
+        if let Some(contact) = msg.header_values("contact").first() {
+            put("sip.contact", &NameAddr::parse(contact).uri);
+        }
+        for (header, key) in [
+            ("user-agent", "sip.user_agent"),
+            ("server", "sip.server"),
+            ("reason", "sip.reason"),
+            ("content-type", "sip.content_type"),
+            ("max-forwards", "sip.max_forwards"),
+            ("subject", "sip.subject"),
+            ("event", "sip.event"),
+            ("refer-to", "sip.refer_to"),
+            ("p-asserted-identity", "sip.p_asserted_identity"),
+        ] {
+            if let Some(value) = msg.header(header) {
+                put(key, value);
+            }
+        }
+
+        let vias = msg.header_values("via");
+        if let Some(top_via) = vias.first() {
+            put("sip.via", top_via);
+            put("sip.via_branch", param(top_via, "branch").unwrap_or(""));
+        }
+
+        if let StartLine::Response { code, .. } = &msg.start {
+            attributes.insert("sip.status_code".to_string(), Value::from(*code));
+        }
+        if let Some((seq, cseq_method)) = msg.cseq() {
+            attributes.insert("sip.cseq_num".to_string(), Value::from(seq));
+            attributes.insert(
+                "sip.cseq_method".to_string(),
+                Value::String(cseq_method.to_string()),
+            );
+        }
+        if vias.len() > 1 {
+            attributes.insert("sip.via_count".to_string(), Value::from(vias.len()));
+        }
+        attributes.insert("sip.body_len".to_string(), Value::from(msg.body.len()));
+
+        let mut preview_end = data.len().min(1000);
+        while !data.is_char_boundary(preview_end) {
+            preview_end -= 1;
+        }
+        attributes.insert(
+            "payload".to_string(),
+            Value::String(data[..preview_end].to_string()),
+        );
+
+        let mut log = self.build_log(
+            "SIP_PACKET",
+            format!("SIP {} captured", method),
+            attributes,
+            ts,
+        );
+        log.trace_id = Some(call_id);
+        log.smart_tags.push("SIP".to_string());
+        log.smart_tags.push("NET".to_string());
+        Some(log)
+    }
+
+    fn create_rtp_log(
+        &self,
+        header: &RtpHeader,
+        codec: Option<Codec>,
+        segment: &Segment,
+        len: u32,
+        ts: &str,
+        binding: Option<(MediaBinding, &'static str)>,
+    ) -> LogRecord {
+        let pt = header.payload_type;
+        let mut attributes = self.net_attributes(segment, len);
+        attributes.insert("rtp.payload_type".to_string(), Value::from(pt));
+        attributes.insert("rtp.sequence".to_string(), Value::from(header.sequence));
+        attributes.insert("rtp.timestamp".to_string(), Value::from(header.timestamp));
+        attributes.insert(
+            "rtp.ssrc".to_string(),
+            Value::String(format!("0x{:08X}", header.ssrc)),
+        );
+        if header.marker {
+            attributes.insert("rtp.marker".to_string(), Value::Bool(true));
+        }
+
+        if let Some(codec) = &codec {
+            attributes.insert("rtp.codec".to_string(), Value::String(codec.name.clone()));
+        }
+        if let Some((_, direction)) = &binding {
+            attributes.insert(
+                "rtp.direction".to_string(),
+                Value::String(direction.to_string()),
+            );
+        }
+
+        let rtp_payload = header.payload(&segment.payload);
+        if (pt == 8 || pt == 0) && !rtp_payload.is_empty() {
+            use base64::{engine::general_purpose::STANDARD, Engine as _};
+            let b64_audio = STANDARD.encode(rtp_payload);
+            attributes.insert("rtp.audio_b64".to_string(), Value::String(b64_audio));
+        }
+
+        let mut log = self.build_log(
+            "RTP_PACKET",
+            format!("RTP MEDIA (PT: {})", pt),
+            attributes,
+            ts,
+        );
+        if let Some((binding, _)) = binding {
+            log.trace_id = Some(binding.call_id);
+        }
+        log.smart_tags.push("RTP".to_string());
+        log.smart_tags.push("NET".to_string());
+        log
+    }
+
+    fn create_dtmf_log(
+        &self,
+        digit: &DtmfDigit,
+        segment: &Segment,
+        len: u32,
+        ts: &str,
+        binding: &Option<(MediaBinding, &'static str)>,
+    ) -> LogRecord {
+        let mut attributes = self.net_attributes(segment, len);
+        attributes.insert(
+            "dtmf.digit".to_string(),
+            Value::String(digit.digit.to_string()),
+        );
+        attributes.insert("dtmf.event".to_string(), Value::from(digit.event));
+        attributes.insert("dtmf.duration_ms".to_string(), round2(digit.duration_ms));
+        attributes.insert(
+            "dtmf.volume_dbm0".to_string(),
+            Value::from(-(digit.volume as i32)),
+        );
+        attributes.insert("dtmf.end_seen".to_string(), Value::Bool(digit.ended));
+        attributes.insert(
+            "rtp.ssrc".to_string(),
+            Value::String(format!("0x{:08X}", digit.ssrc)),
+        );
+        if let Some((_, direction)) = binding {
+            attributes.insert(
+                "rtp.direction".to_string(),
+                Value::String(direction.to_string()),
+            );
+        }
+
+        let mut log = self.build_log(
+            "DTMF_DIGIT",
+            format!("DTMF '{}' ({:.0} ms)", digit.digit, digit.duration_ms),
+            attributes,
+            ts,
+        );
+        if let Some((binding, _)) = binding {
+            log.trace_id = Some(binding.call_id.clone());
+        }
+        log.smart_tags.push("DTMF".to_string());
+        log.smart_tags.push("NET".to_string());
+        log
+    }
+
+    fn create_rtcp_log(
+        &self,
+        packets: &[RtcpPacket],
+        segment: &Segment,
+        len: u32,
+        captured_us: i64,
+        ts: &str,
+        binding: Option<(MediaBinding, &'static str)>,
+    ) -> LogRecord {
+        let mut attributes = self.net_attributes(segment, len);
+        let types: Vec<&str> = packets.iter().map(|p| p.type_name()).collect();
+        attributes.insert("rtcp.types".to_string(), Value::String(types.join(",")));
+        if let Some((_, direction)) = &binding {
+            attributes.insert(
+                "rtp.direction".to_string(),
+                Value::String(direction.to_string()),
+            );
+        }
+        let clock_rate = binding
+            .as_ref()
+            .map(|(b, _)| b.media_clock_rate())
+            .unwrap_or(8000) as f64;
+
+        let mut sender_ssrc = None;
+        let mut blocks = Vec::new();
+        let mut degraded = false;
+
+        for packet in packets {
+            match packet {
+                RtcpPacket::SenderReport {
+                    ssrc,
+                    ntp_sec,
+                    ntp_frac,
+                    rtp_ts,
+                    packet_count,
+                    octet_count,
+                    reports,
+                } => {
+                    sender_ssrc.get_or_insert(*ssrc);
+                    // NTP epoch (1900) -> Unix epoch (1970)
+                    let unix_us = (*ntp_sec as i64 - 2_208_988_800) * 1_000_000
+                        + ((*ntp_frac as i64 * 1_000_000) >> 32);
+                    if let Some(ntp) = chrono::DateTime::from_timestamp_micros(unix_us) {
+                        attributes.insert(
+                            "rtcp.sr_ntp_ts".to_string(),
+                            Value::String(ntp.to_rfc3339()),
+                        );
+                    }
+                    attributes.insert("rtcp.sr_rtp_ts".to_string(), Value::from(*rtp_ts));
+                    attributes.insert(
+                        "rtcp.sr_packet_count".to_string(),
+                        Value::from(*packet_count),
+                    );
+                    attributes.insert("rtcp.sr_octet_count".to_string(), Value::from(*octet_count));
+                    blocks.extend(reports);
+                }
+                RtcpPacket::ReceiverReport { ssrc, reports } => {
+                    sender_ssrc.get_or_insert(*ssrc);
+                    blocks.extend(reports);
+                }
+                RtcpPacket::SourceDescription(chunks) => {
+                    for chunk in chunks {
+                        for (key, value) in [
+                            ("rtcp.cname", &chunk.cname),
+                            ("rtcp.sdes_name", &chunk.name),
+                            ("rtcp.tool", &chunk.tool),
+                        ] {
+                            if let Some(value) = value {
+                                attributes
+                                    .entry(key.to_string())
+                                    .or_insert_with(|| Value::String(value.clone()));
+                            }
+                        }
+                    }
+                }
+                RtcpPacket::Goodbye { sources, reason } => {
+                    let sources: Vec<Value> = sources
+                        .iter()
+                        .map(|s| Value::String(format!("0x{:08X}", s)))
+                        .collect();
+                    attributes.insert("rtcp.bye_ssrcs".to_string(), Value::Array(sources));
+                    if let Some(reason) = reason {
+                        attributes
+                            .insert("rtcp.bye_reason".to_string(), Value::String(reason.clone()));
+                    }
+                }
+                RtcpPacket::ExtendedReport { ssrc, voip } => {
+                    sender_ssrc.get_or_insert(*ssrc);
+                    let Some(m) = voip.first() else {
+                        continue;
+                    };
+                    attributes.insert(
+                        "rtcp.xr_ssrc".to_string(),
+                        Value::String(format!("0x{:08X}", m.ssrc)),
+                    );
+                    let loss_pct = m.loss_rate as f64 * 100.0 / 256.0;
+                    attributes.insert("rtcp.xr_loss_rate_pct".to_string(), round2(loss_pct));
+                    attributes.insert(
+                        "rtcp.xr_discard_rate_pct".to_string(),
+                        round2(m.discard_rate as f64 * 100.0 / 256.0),
+                    );
+                    for (key, value) in [
+                        ("rtcp.xr_rtt_ms", m.round_trip_delay_ms),
+                        ("rtcp.xr_end_system_delay_ms", m.end_system_delay_ms),
+                        ("rtcp.xr_jb_nominal_ms", m.jb_nominal_ms),
+                        ("rtcp.xr_jb_max_ms", m.jb_max_ms),
+                    ] {
+                        attributes.insert(key.to_string(), Value::from(value));
+                    }
+                    // RFC 3611: 127 "ölçülemedi" anlamına gelir.
+                    if m.r_factor != 127 {
+                        attributes.insert("rtcp.xr_r_factor".to_string(), Value::from(m.r_factor));
+                    }
+                    for (key, value) in [("rtcp.xr_mos_lq", m.mos_lq), ("rtcp.xr_mos_cq", m.mos_cq)]
+                    {
+                        if value != 127 {
+                            attributes.insert(key.to_string(), round2(value as f64 / 10.0));
+                        }
+                    }
+                    for (key, value) in [
+                        ("rtcp.xr_signal_level_dbm", m.signal_level),
+                        ("rtcp.xr_noise_level_dbm", m.noise_level),
+                    ] {
+                        if value != 127 {
+                            attributes.insert(key.to_string(), Value::from(value));
+                        }
+                    }
+                    degraded |= loss_pct >= rtp::LOSS_WARN_PCT;
+                }
+                RtcpPacket::Other(_) => {}
+            }
+        }
+
+        if let Some(ssrc) = sender_ssrc {
+            attributes.insert(
+                "rtcp.ssrc".to_string(),
+                Value::String(format!("0x{:08X}", ssrc)),
+            );
+        }
+
+        // Report block'lar: ilki filtreleme için düz alanlara, hepsi `rtcp.reports` dizisine.
+        let mut reports = Vec::new();
+        for (i, block) in blocks.iter().enumerate() {
+            let fraction_lost_pct = block.fraction_lost as f64 * 100.0 / 256.0;
+            let jitter_ms = block.jitter as f64 * 1000.0 / clock_rate;
+            degraded |= fraction_lost_pct >= rtp::LOSS_WARN_PCT || jitter_ms >= rtp::JITTER_WARN_MS;
+
+            let mut report = serde_json::Map::new();
+            report.insert(
+                "ssrc".to_string(),
+                Value::String(format!("0x{:08X}", block.ssrc)),
+            );
+            report.insert("fraction_lost_pct".to_string(), round2(fraction_lost_pct));
+            report.insert(
+                "cumulative_lost".to_string(),
+                Value::from(block.cumulative_lost),
+            );
+            report.insert("highest_seq".to_string(), Value::from(block.highest_seq));
+            report.insert("jitter_ms".to_string(), round2(jitter_ms));
+            report.insert(
+                "dlsr_ms".to_string(),
+                round2(block.delay_since_last_sr as f64 * 1000.0 / 65536.0),
+            );
+            if let Some(rtt) = rtcp::round_trip_ms(block, captured_us) {
+                report.insert("rtt_ms".to_string(), round2(rtt));
+            }
+
+            if i == 0 {
+                for (key, value) in &report {
+                    attributes.insert(format!("rtcp.report_{}", key), value.clone());
+                }
+            }
+            reports.push(Value::Object(report));
+        }
+        if !reports.is_empty() {
+            attributes.insert("rtcp.reports".to_string(), Value::Array(reports));
+        }
+
+        let mut log = self.build_log(
+            "RTCP_REPORT",
+            format!("RTCP {}", types.join(",")),
+            attributes,
+            ts,
+        );
+        if let Some((binding, _)) = binding {
+            log.trace_id = Some(binding.call_id);
+        }
+        log.smart_tags.push("RTCP".to_string());
+        log.smart_tags.push("NET".to_string());
+        if degraded {
+            log.severity = "WARN".to_string();
+            log.smart_tags.push("QOS".to_string());
+        }
+        log
+    }
+
+    fn create_stream_stats_log(&self, report: &StreamReport) -> LogRecord {
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            "net.interface".to_string(),
+            Value::String(self.interface.clone()),
+        );
+        attributes.insert(
+            "net.src_ip".to_string(),
+            Value::String(report.src.ip().to_string()),
+        );
+        attributes.insert(
+            "net.dst_ip".to_string(),
+            Value::String(report.dst.ip().to_string()),
+        );
+        attributes.insert("net.src_port".to_string(), Value::from(report.src.port()));
+        attributes.insert("net.dst_port".to_string(), Value::from(report.dst.port()));
+
+        let loss_pct = report.loss_pct();
+        attributes.insert(
+            "rtp.ssrc".to_string(),
+            Value::String(format!("0x{:08X}", report.ssrc)),
+        );
+        attributes.insert(
+            "rtp.payload_type".to_string(),
+            Value::from(report.payload_type),
+        );
+        if let Some(codec) = &report.codec {
+            attributes.insert("rtp.codec".to_string(), Value::String(codec.clone()));
+        }
+        if let Some(direction) = report.direction {
+            attributes.insert(
+                "rtp.direction".to_string(),
+                Value::String(direction.to_string()),
+            );
+        }
+        for (key, value) in [
+            ("rtp.packets_received", report.packets_received),
+            ("rtp.packets_expected", report.packets_expected),
+            ("rtp.packets_lost", report.packets_lost),
+            ("rtp.duplicates", report.duplicates),
+            ("rtp.out_of_order", report.out_of_order),
+        ] {
+            attributes.insert(key.to_string(), Value::from(value));
+        }
+        for (key, value) in [
+            ("rtp.loss_pct", loss_pct),
+            ("rtp.jitter_ms", report.jitter_ms),
+            ("rtp.max_jitter_ms", report.max_jitter_ms),
+            ("rtp.max_delta_ms", report.max_delta_ms),
+            ("rtp.duration_ms", report.duration_ms),
+        ] {
+            attributes.insert(key.to_string(), round2(value));
+        }
+        attributes.insert("rtp.final".to_string(), Value::Bool(report.is_final));
+
+        let ts = chrono::DateTime::from_timestamp_micros(report.last_seen_us)
+            .unwrap_or_else(chrono::Utc::now)
+            .to_rfc3339();
+        let mut log = self.build_log(
+            "RTP_STREAM_STATS",
+            format!(
+                "RTP stream 0x{:08X}: loss {:.2}%, jitter {:.2} ms{}",
+                report.ssrc,
+                loss_pct,
+                report.jitter_ms,
+                if report.is_final { " (final)" } else { "" }
+            ),
+            attributes,
+            &ts,
+        );
+        log.trace_id = report.call_id.clone();
+        log.smart_tags.push("RTP".to_string());
+        if report.is_degraded() {
+            log.severity = "WARN".to_string();
+            log.smart_tags.push("QOS".to_string());
+        }
+        log
+    }
+
+    fn build_log(
+        &self,
+        event: &str,
+        msg: String,
+        attributes: HashMap<String, Value>,
+        ts: &str,
+    ) -> LogRecord {
+        LogRecord {
+            schema_v: "1.0.0".to_string(),
+            ts: ts.to_string(),
+            severity: "INFO".to_string(),
+            tenant_id: self.tenant_id.clone(), // [ARCH-COMPLIANCE]
+            resource: ResourceContext {
+                service_name: "network-sniffer".to_string(),
+                service_version: "4.1.0".to_string(),
+                service_env: "production".to_string(),
+                host_name: Some(self.node_name.clone()),
+            },
+            trace_id: None,
+            span_id: None,
+            event: event.to_string(),
+            message: msg,
+            attributes,
+            smart_tags: vec![],
+            _idx: 0.0,
+        }
+    }
+}
+
+/// Ölçümler JSON'da iki ondalık basamakla taşınır.
+fn round2(value: f64) -> Value {
+    Value::from((value * 100.0).round() / 100.0)
+}
+
+#[async_trait]
+impl LogIngestor for NetworkSniffer {
+    async fn start(&self) -> Result<()> {
+        info!(event="SNIFFER_START", interface=%self.interface, "🕸️ Sniffer: Başlatılıyor.");
+
+        let device_name = if self.interface == "any" {
+            "any".to_string()
+        } else {
+            match Device::list() {
+                Ok(devs) => devs
+                    .into_iter()
+                    .find(|d| d.name == self.interface)
+                    .map(|d| d.name)
+                    .unwrap_or("any".to_string()),
+                Err(_) => "any".to_string(),
+            }
+        };
+
+        let mut cap = Capture::from_device(device_name.as_str())
+            .context("Pcap Device Error")?
+            .promisc(true)
+            .snaplen(65535)
+            .timeout(50)
+            .open()
+            .context("Pcap Open Error")?;
+
+        if !self.filter.is_empty() {
+            if let Err(e) = cap.filter(&self.filter, true) {
+                error!(event="BPF_FILTER_ERR", error=%e, "BPF Filtre Hatası");
+                return Err(anyhow::anyhow!("BPF Filter Error"));
+            }
+        }
+
+        let link_type = cap.get_datalink();
+        let active_flag = self.active_flag.clone();
+        let tx_clone = self.tx.clone();
+        let mut processor = PacketProcessor::new(
+            &self.interface,
+            self.node_name.clone(),
+            self.tenant_id.clone(),
+            self.frame_store.clone(),
+        );
+
+        std::thread::spawn(move || {
+            let mut dropped_packets = 0;
+            let mut last_drop_report = std::time::Instant::now();
+
+            loop {
+                if !active_flag.load(Ordering::Relaxed) {
+                    std::thread::sleep(Duration::from_millis(500));
+                    continue;
+                }
+
+                match cap.next_packet() {
+                    Ok(packet) => {
+                        for log in processor.handle_packet(&packet, link_type) {
+                            if let Err(tokio::sync::mpsc::error::TrySendError::Full(_)) =
+                                tx_clone.try_send(log)
+                            {
+                                dropped_packets += 1;
+                            }
+                        }
+                    }
+                    Err(pcap::Error::TimeoutExpired) => {
+                        let now_us = chrono::Utc::now().timestamp_micros();
+                        for log in processor.tick(now_us) {
+                            let _ = tx_clone.try_send(log);
+                        }
+                    }
+                    Err(e) => {
+                        error!(event="PCAP_ERROR", error=?e, "Sniffer Hatası");
+                        std::thread::sleep(Duration::from_secs(2));
+                    }
+                }
+
+                if dropped_packets > 0 && last_drop_report.elapsed() > Duration::from_secs(1) {
+                    debug!(event="SNIFFER_BUFFER_FULL", dropped=%dropped_packets, "⚠️ Sniffer Buffer Dolu.");
+                    dropped_packets = 0;
+                    last_drop_report = std::time::Instant::now();
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALLER: [u8; 4] = [10, 0, 0, 1];
+    const CALLEE: [u8; 4] = [10, 0, 0, 2];
+
+    /// Ethernet + IPv4 + UDP çerçevesi. Tekilleştirme parmak izi IP id alanını içerdiği
+    /// için her çerçeveye ayrı bir id verilir.
+    fn udp_frame(id: u16, src: ([u8; 4], u16), dst: ([u8; 4], u16), payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x08, 0x00]);
+        let total_len = (20 + 8 + payload.len()) as u16;
+        frame.extend_from_slice(&[0x45, 0]);
+        frame.extend_from_slice(&total_len.to_be_bytes());
+        frame.extend_from_slice(&id.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 64, IPPROTO_UDP, 0, 0]);
+        frame.extend_from_slice(&src.0);
+        frame.extend_from_slice(&dst.0);
+        frame.extend_from_slice(&src.1.to_be_bytes());
+        frame.extend_from_slice(&dst.1.to_be_bytes());
+        frame.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn rtp(pt: u8, seq: u16, ts: u32, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0x80, pt];
+        data.extend_from_slice(&seq.to_be_bytes());
+        data.extend_from_slice(&ts.to_be_bytes());
+        data.extend_from_slice(&0x1234_5678u32.to_be_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    fn sip(start: &str, cseq: &str, sdp_ip: &str, sdp_port: u16) -> Vec<u8> {
+        let body = format!(
+            "v=0\r\no=- 1 1 IN IP4 {ip}\r\nc=IN IP4 {ip}\r\nm=audio {port} RTP/AVP 0 101\r\na=rtpmap:101 telephone-event/8000\r\n",
+            ip = sdp_ip,
+            port = sdp_port
+        );
+        format!(
+            "{start}\r\nVia: SIP/2.0/UDP 10.0.0.1:5060;branch=z9hG4bK1\r\nFrom: <sip:alice@a>;tag=a1\r\nTo: <sip:bob@b>\r\nCall-ID: replay-1\r\nCSeq: {cseq}\r\nContent-Type: application/sdp\r\nContent-Length: {len}\r\n\r\n{body}",
+            start = start,
+            cseq = cseq,
+            len = body.len(),
+            body = body
+        )
+        .into_bytes()
+    }
+
+    /// Klasik (libpcap) dosya biçiminde küçük bir çağrı yakalaması: INVITE, 200 OK,
+    /// iki G.711 paketi ve bir DTMF bitiş paketi.
+    fn capture() -> Vec<u8> {
+        let frames = [
+            udp_frame(
+                1,
+                (CALLER, 5060),
+                (CALLEE, 5060),
+                &sip("INVITE sip:bob@b SIP/2.0", "1 INVITE", "10.0.0.1", 40000),
+            ),
+            udp_frame(
+                2,
+                (CALLEE, 5060),
+                (CALLER, 5060),
+                &sip("SIP/2.0 200 OK", "1 INVITE", "10.0.0.2", 50000),
+            ),
+            udp_frame(
+                3,
+                (CALLER, 40000),
+                (CALLEE, 50000),
+                &rtp(0, 1, 0, &[0xFF; 160]),
+            ),
+            udp_frame(
+                4,
+                (CALLER, 40000),
+                (CALLEE, 50000),
+                &rtp(0, 2, 160, &[0xFF; 160]),
+            ),
+            udp_frame(
+                5,
+                (CALLER, 40000),
+                (CALLEE, 50000),
+                &rtp(101, 3, 320, &[5, 0x80 | 10, 0x03, 0x20]),
+            ),
+        ];
+
+        pcap_file(1, &frames, 20_000)
+    }
+
+    /// Klasik pcap dosyası; kayıtlar aynı saniye içinde `step_usec` arayla dizilir.
+    fn pcap_file(link_type: u32, frames: &[Vec<u8>], step_usec: u32) -> Vec<u8> {
+        // Genel başlık: magic, sürüm 2.4 (iki u16), thiszone, sigfigs, snaplen, link tipi
+        let mut file = Vec::new();
+        for word in [0xA1B2_C3D4u32, 0x0004_0002, 0, 0, 65535, link_type] {
+            file.extend_from_slice(&word.to_le_bytes());
+        }
+        for (i, frame) in frames.iter().enumerate() {
+            let ts_usec = i as u32 * step_usec;
+            for word in [
+                1_700_000_000u32,
+                ts_usec,
+                frame.len() as u32,
+                frame.len() as u32,
+            ] {
+                file.extend_from_slice(&word.to_le_bytes());
+            }
+            file.extend_from_slice(frame);
+        }
+        file
+    }
+
+    /// Pcap kayıtlarını sırayla `PacketProcessor`'a verir.
+    fn replay(processor: &mut PacketProcessor, file: &[u8]) -> Vec<LogRecord> {
+        let read = |offset: usize| {
+            let mut word = [0u8; 4];
+            word.copy_from_slice(&file[offset..offset + 4]);
+            u32::from_le_bytes(word)
+        };
+        assert_eq!(read(0), 0xA1B2_C3D4);
+        let link_type = Linktype(read(20) as i32);
+
+        let mut records = Vec::new();
+        let mut offset = 24;
+        while offset + 16 <= file.len() {
+            let header = pcap::PacketHeader {
+                ts: libc::timeval {
+                    tv_sec: read(offset) as libc::time_t,
+                    tv_usec: read(offset + 4) as libc::suseconds_t,
+                },
+                caplen: read(offset + 8),
+                len: read(offset + 12),
+            };
+            let start = offset + 16;
+            let end = start + header.caplen as usize;
+            let packet = pcap::Packet::new(&header, &file[start..end]);
+            records.extend(processor.handle_packet(&packet, link_type));
+            offset = end;
+        }
+        records.extend(processor.finish());
+        records
+    }
+
+    #[test]
+    fn replays_small_capture_into_call_records() {
+        let frames = Arc::new(Mutex::new(CallFrameStore::new(10, 1 << 20)));
+        let mut processor = PacketProcessor::new(
+            "pcap:test",
+            "node".to_string(),
+            "t".to_string(),
+            frames.clone(),
+        );
+        let records = replay(&mut processor, &capture());
+
+        let events: Vec<&str> = records.iter().map(|r| r.event.as_str()).collect();
+        assert_eq!(
+            events,
+            [
+                "SIP_PACKET",
+                "SIP_PACKET",
+                "RTP_PACKET",
+                "RTP_PACKET",
+                "DTMF_DIGIT",
+                "RTP_STREAM_STATS"
+            ]
+        );
+        assert!(records
+            .iter()
+            .all(|r| r.trace_id.as_deref() == Some("replay-1")));
+
+        let invite = &records[0].attributes;
+        assert_eq!(invite["sip.method"], "INVITE");
+        assert_eq!(invite["sip.from_user"], "alice");
+        assert_eq!(invite["net.src_port"], 5060);
+        assert_eq!(records[1].attributes["sip.status_code"], 200);
+        assert_eq!(records[2].attributes["rtp.codec"], "PCMU");
+        assert_eq!(records[2].attributes["rtp.direction"], "caller_to_callee");
+        assert_eq!(records[4].attributes["dtmf.digit"], "5");
+        assert_eq!(records[5].attributes["rtp.packets_lost"], 0);
+        assert_eq!(records[0].ts, "2023-11-14T22:13:20+00:00");
+
+        // Çağrının tüm çerçeveleri pcapng dışa aktarımı için saklanır.
+        let stored = frames
+            .blocking_lock()
+            .snapshot("replay-1")
+            .expect("çerçeve saklanmadı");
+        assert_eq!(stored.len(), 5);
+    }
+
+    fn processor(interface: &str) -> PacketProcessor {
+        let frames = Arc::new(Mutex::new(CallFrameStore::new(10, 1 << 20)));
+        PacketProcessor::new(interface, "node".to_string(), "t".to_string(), frames)
+    }
+
+    /// Tek bir çerçeveyi verilen yakalama zamanıyla işler.
+    fn handle(
+        processor: &mut PacketProcessor,
+        data: &[u8],
+        link_type: Linktype,
+        ts_usec: u32,
+    ) -> Vec<LogRecord> {
+        let header = pcap::PacketHeader {
+            ts: libc::timeval {
+                tv_sec: 1,
+                tv_usec: ts_usec as libc::suseconds_t,
+            },
+            caplen: data.len() as u32,
+            len: data.len() as u32,
+        };
+        processor.handle_packet(&pcap::Packet::new(&header, data), link_type)
+    }
+
+    /// `udp_frame` çıktısını L4 verisi `chunk` baytlık (8'in katı) IPv4 parçalarına böler.
+    fn fragments(frame: &[u8], chunk: usize) -> Vec<Vec<u8>> {
+        let (head, l4) = frame.split_at(14 + 20);
+        l4.chunks(chunk)
+            .enumerate()
+            .map(|(i, part)| {
+                let mut fragment = head.to_vec();
+                let total_len = (20 + part.len()) as u16;
+                fragment[16..18].copy_from_slice(&total_len.to_be_bytes());
+                let more = if (i + 1) * chunk < l4.len() {
+                    0x2000
+                } else {
+                    0
+                };
+                let field = more | (i * chunk / 8) as u16;
+                fragment[20..22].copy_from_slice(&field.to_be_bytes());
+                fragment.extend_from_slice(part);
+                fragment
+            })
+            .collect()
+    }
+
+    fn invite_frame(id: u16) -> Vec<u8> {
+        udp_frame(
+            id,
+            (CALLER, 5060),
+            (CALLEE, 5060),
+            &sip("INVITE sip:bob@b SIP/2.0", "1 INVITE", "10.0.0.1", 40000),
+        )
+    }
+
+    #[test]
+    fn file_replay_keeps_equal_length_packets_of_the_same_second() {
+        // `tcpdump -i any` (SLL) kaydı: IP id ve UDP checksum'ı boş, aynı boyutta iki farklı
+        // OPTIONS; parmak izleri çakışsa da ikisi de işlenmelidir.
+        let frames: Vec<Vec<u8>> = ["1 OPTIONS", "2 OPTIONS"]
+            .iter()
+            .map(|cseq| {
+                let udp = udp_frame(
+                    0,
+                    (CALLER, 5060),
+                    (CALLEE, 5060),
+                    &sip("OPTIONS sip:bob@b SIP/2.0", cseq, "10.0.0.1", 40000),
+                );
+                let mut sll = vec![0, 0, 0, 1, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0x08, 0x00];
+                sll.extend_from_slice(&udp[14..]);
+                sll
+            })
+            .collect();
+        assert_eq!(frames[0].len(), frames[1].len());
+        let file = pcap_file(Linktype::LINUX_SLL.0 as u32, &frames, 0);
+
+        let records = replay(&mut processor("pcap:test").without_dedup(), &file);
+        let cseqs: Vec<&Value> = records.iter().map(|r| &r.attributes["sip.cseq"]).collect();
+        assert_eq!(cseqs, ["1 OPTIONS", "2 OPTIONS"]);
+        // Canlı yakalamadaki tekilleştirme bu iki paketi birbirinin kopyası sayardı.
+        assert_eq!(replay(&mut processor("any"), &file).len(), 1);
+    }
+
+    #[test]
+    fn duplicate_frames_are_ignored() {
+        let mut processor = processor("any");
+        let data = invite_frame(9);
+        assert_eq!(
+            handle(&mut processor, &data, Linktype::ETHERNET, 0).len(),
+            1
+        );
+        assert!(handle(&mut processor, &data, Linktype::ETHERNET, 0).is_empty());
+    }
+
+    /// Ethernet + IPv6 + UDP çerçevesi (akış etiketi ve checksum sıfır).
+    fn udp6_frame(src_port: u16, dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let udp_len = (8 + payload.len()) as u16;
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x86, 0xDD, 0x60, 0, 0, 0]);
+        frame.extend_from_slice(&udp_len.to_be_bytes());
+        frame.extend_from_slice(&[IPPROTO_UDP, 64]);
+        frame.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        frame.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        frame.extend_from_slice(&src_port.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        frame.extend_from_slice(&udp_len.to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    #[test]
+    fn equal_length_ipv6_packets_are_not_duplicates() {
+        let mut processor = processor("any");
+        let first = udp6_frame(40000, 50000, &rtp(0, 1, 0, &[0xFF; 160]));
+        let second = udp6_frame(40000, 50000, &rtp(0, 2, 160, &[0xFF; 160]));
+        assert_eq!(first.len(), second.len());
+
+        assert_eq!(
+            handle(&mut processor, &first, Linktype::ETHERNET, 0).len(),
+            1
+        );
+        // Aynı paketin başka arayüzdeki kopyası.
+        assert!(handle(&mut processor, &first, Linktype::ETHERNET, 5).is_empty());
+        let records = handle(&mut processor, &second, Linktype::ETHERNET, 20_000);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, "RTP_PACKET");
+        assert_eq!(records[0].attributes["rtp.sequence"], 2);
+    }
+
+    #[test]
+    fn reassembles_datagram_split_into_three_fragments() {
+        let mut processor = processor("any");
+        let parts = fragments(&invite_frame(7), 160);
+        assert_eq!(parts.len(), 3);
+        // İlk iki parça aynı uzunlukta, aynı id ve aynı saniyede; tekrar sayılmamalılar.
+        assert_eq!(parts[0].len(), parts[1].len());
+
+        let mut records = Vec::new();
+        for (i, part) in parts.iter().enumerate() {
+            records.extend(handle(&mut processor, part, Linktype::ETHERNET, i as u32));
+        }
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, "SIP_PACKET");
+        assert_eq!(records[0].attributes["sip.method"], "INVITE");
+    }
+
+    #[test]
+    fn parses_vlan_and_ipv6_fragment_headers() {
+        let mut vlan = udp_frame(1, (CALLER, 1), (CALLEE, 2), b"x");
+        vlan.splice(12..12, [0x81, 0x00, 0x00, 0x0A]);
+        let ip = PacketProcessor::parse_ip(&vlan, Linktype::ETHERNET).expect("IPv4 çözülemedi");
+        assert_eq!(ip.src, IpAddr::from(CALLER));
+        assert_eq!(ip.l4_start, 18 + 20);
+        assert_eq!(ip.end, vlan.len());
+
+        // Loopback (4 bayt) + IPv6 + Fragment başlığı (offset 0, M=1)
+        let mut v6 = vec![0u8; 4];
+        v6.extend_from_slice(&[0x60, 0, 0, 0, 0, 16, 44, 64]);
+        v6.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        v6.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        v6.extend_from_slice(&[IPPROTO_UDP, 0, 0x00, 0x01, 0, 0, 0, 42]);
+        v6.extend_from_slice(&[0u8; 8]);
+        let ip = PacketProcessor::parse_ip(&v6, Linktype::NULL).expect("IPv6 çözülemedi");
+        assert_eq!(ip.protocol, IPPROTO_UDP);
+        assert_eq!(ip.l4_start, 4 + 40 + 8);
+        let fragment = ip.fragment.expect("parça bilgisi yok");
+        assert_eq!((fragment.id, fragment.offset, fragment.more), (42, 0, true));
+    }
+}
@@ -0,0 +1,416 @@
+// src/adapters/sniffer/rtcp.rs
+
+const RTCP_SR: u8 = 200;
+const RTCP_RR: u8 = 201;
+const RTCP_SDES: u8 = 202;
+const RTCP_BYE: u8 = 203;
+const RTCP_APP: u8 = 204;
+const RTCP_XR: u8 = 207;
+
+const SDES_CNAME: u8 = 1;
+const SDES_NAME: u8 = 2;
+const SDES_TOOL: u8 = 6;
+/// RFC 3611 §4.7 VoIP Metrics Report Block
+const XR_VOIP_METRICS: u8 = 7;
+
+/// RFC 3550 §6.4.1 report block: karşı tarafın gözlemlediği kayıp ve jitter.
+pub(super) struct ReportBlock {
+    pub ssrc: u32,
+    pub fraction_lost: u8,
+    pub cumulative_lost: i32,
+    pub highest_seq: u32,
+    /// RTP saat birimi cinsinden
+    pub jitter: u32,
+    pub last_sr: u32,
+    /// 1/65536 saniye
+    pub delay_since_last_sr: u32,
+}
+
+#[derive(Default)]
+pub(super) struct SdesChunk {
+    pub cname: Option<String>,
+    pub name: Option<String>,
+    pub tool: Option<String>,
+}
+
+pub(super) struct VoipMetrics {
+    pub ssrc: u32,
+    pub loss_rate: u8,
+    pub discard_rate: u8,
+    pub round_trip_delay_ms: u16,
+    pub end_system_delay_ms: u16,
+    pub signal_level: i8,
+    pub noise_level: i8,
+    pub r_factor: u8,
+    pub mos_lq: u8,
+    pub mos_cq: u8,
+    pub jb_nominal_ms: u16,
+    pub jb_max_ms: u16,
+}
+
+pub(super) enum RtcpPacket {
+    SenderReport {
+        ssrc: u32,
+        ntp_sec: u32,
+        ntp_frac: u32,
+        rtp_ts: u32,
+        packet_count: u32,
+        octet_count: u32,
+        reports: Vec<ReportBlock>,
+    },
+    ReceiverReport {
+        ssrc: u32,
+        reports: Vec<ReportBlock>,
+    },
+    SourceDescription(Vec<SdesChunk>),
+    Goodbye {
+        sources: Vec<u32>,
+        reason: Option<String>,
+    },
+    ExtendedReport {
+        ssrc: u32,
+        voip: Vec<VoipMetrics>,
+    },
+    Other(u8),
+}
+
+impl RtcpPacket {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            RtcpPacket::SenderReport { .. } => "SR",
+            RtcpPacket::ReceiverReport { .. } => "RR",
+            RtcpPacket::SourceDescription(_) => "SDES",
+            RtcpPacket::Goodbye { .. } => "BYE",
+            RtcpPacket::ExtendedReport { .. } => "XR",
+            RtcpPacket::Other(RTCP_APP) => "APP",
+            RtcpPacket::Other(_) => "OTHER",
+        }
+    }
+}
+
+/// İlk bayttan sonraki PT alanı RTCP aralığında mı (RTP ile aynı portu paylaşan rtcp-mux dahil).
+pub(super) fn looks_like_rtcp(data: &[u8]) -> bool {
+    data.len() >= 8 && data[0] >> 6 == 2 && (RTCP_SR..=RTCP_XR).contains(&data[1])
+}
+
+/// Bileşik (compound) RTCP paketini çözer. Uzunluk alanlarının toplamı UDP yüküne
+/// tam oturmalıdır. `strict` modda RFC 3550 A.2 doğrulaması da uygulanır: ilk paket
+/// SR/RR olmalı ve dolgu biti yalnızca son pakette bulunabilir.
+pub(super) fn parse(data: &[u8], strict: bool) -> Option<Vec<RtcpPacket>> {
+    let mut packets = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let header = data.get(offset..offset + 4)?;
+        if header[0] >> 6 != 2 {
+            return None;
+        }
+        let count = (header[0] & 0x1F) as usize;
+        let packet_type = header[1];
+        let len = (u16::from_be_bytes([header[2], header[3]]) as usize + 1) * 4;
+        let packet = data.get(offset..offset + len)?;
+        let is_last = offset + len == data.len();
+
+        if strict {
+            if offset == 0 && !(packet_type == RTCP_SR || packet_type == RTCP_RR) {
+                return None;
+            }
+            if header[0] & 0x20 != 0 && !is_last {
+                return None;
+            }
+        }
+
+        let mut body = &packet[4..];
+        if header[0] & 0x20 != 0 {
+            let pad = *packet.last()? as usize;
+            body = body.get(..body.len().checked_sub(pad)?)?;
+        }
+
+        packets.push(match packet_type {
+            RTCP_SR => parse_sender_report(body, count)?,
+            RTCP_RR => RtcpPacket::ReceiverReport {
+                ssrc: read_u32(body, 0)?,
+                reports: parse_report_blocks(body.get(4..)?, count)?,
+            },
+            RTCP_SDES => RtcpPacket::SourceDescription(parse_sdes(body, count)),
+            RTCP_BYE => parse_bye(body, count)?,
+            RTCP_XR => parse_xr(body)?,
+            RTCP_APP..=RTCP_XR => RtcpPacket::Other(packet_type),
+            _ => return None,
+        });
+        offset += len;
+    }
+
+    (!packets.is_empty()).then_some(packets)
+}
+
+fn parse_sender_report(body: &[u8], count: usize) -> Option<RtcpPacket> {
+    Some(RtcpPacket::SenderReport {
+        ssrc: read_u32(body, 0)?,
+        ntp_sec: read_u32(body, 4)?,
+        ntp_frac: read_u32(body, 8)?,
+        rtp_ts: read_u32(body, 12)?,
+        packet_count: read_u32(body, 16)?,
+        octet_count: read_u32(body, 20)?,
+        reports: parse_report_blocks(body.get(24..)?, count)?,
+    })
+}
+
+fn parse_report_blocks(data: &[u8], count: usize) -> Option<Vec<ReportBlock>> {
+    (0..count)
+        .map(|i| {
+            let b = data.get(i * 24..(i + 1) * 24)?;
+            // 24 bit işaretli kümülatif kayıp
+            let cumulative = i32::from_be_bytes([b[5], b[6], b[7], 0]) >> 8;
+            Some(ReportBlock {
+                ssrc: read_u32(b, 0)?,
+                fraction_lost: b[4],
+                cumulative_lost: cumulative,
+                highest_seq: read_u32(b, 8)?,
+                jitter: read_u32(b, 12)?,
+                last_sr: read_u32(b, 16)?,
+                delay_since_last_sr: read_u32(b, 20)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_sdes(body: &[u8], count: usize) -> Vec<SdesChunk> {
+    let mut chunks = Vec::new();
+    let mut offset = 0;
+
+    for _ in 0..count {
+        if read_u32(body, offset).is_none() {
+            break;
+        }
+        let mut chunk = SdesChunk::default();
+        offset += 4;
+
+        // Öğe listesi tip 0 ile biter; chunk 32 bit sınırına hizalanır.
+        while let Some(&item_type) = body.get(offset) {
+            if item_type == 0 {
+                break;
+            }
+            let Some(&len) = body.get(offset + 1) else {
+                break;
+            };
+            let Some(value) = body.get(offset + 2..offset + 2 + len as usize) else {
+                break;
+            };
+            let text = String::from_utf8_lossy(value).to_string();
+            match item_type {
+                SDES_CNAME => chunk.cname = Some(text),
+                SDES_NAME => chunk.name = Some(text),
+                SDES_TOOL => chunk.tool = Some(text),
+                _ => {}
+            }
+            offset += 2 + len as usize;
+        }
+        offset = (offset + 4) & !3;
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+fn parse_bye(body: &[u8], count: usize) -> Option<RtcpPacket> {
+    let sources = (0..count)
+        .map(|i| read_u32(body, i * 4))
+        .collect::<Option<Vec<_>>>()?;
+    let reason = body.get(count * 4).and_then(|&len| {
+        let text = body.get(count * 4 + 1..count * 4 + 1 + len as usize)?;
+        Some(String::from_utf8_lossy(text).to_string())
+    });
+    Some(RtcpPacket::Goodbye { sources, reason })
+}
+
+fn parse_xr(body: &[u8]) -> Option<RtcpPacket> {
+    let ssrc = read_u32(body, 0)?;
+    let mut voip = Vec::new();
+    let mut offset = 4;
+
+    while let Some(header) = body.get(offset..offset + 4) {
+        let block_len = (u16::from_be_bytes([header[2], header[3]]) as usize + 1) * 4;
+        let block = body.get(offset..offset + block_len)?;
+        if header[0] == XR_VOIP_METRICS && block.len() >= 36 {
+            voip.push(VoipMetrics {
+                ssrc: read_u32(block, 4)?,
+                loss_rate: block[8],
+                discard_rate: block[9],
+                round_trip_delay_ms: read_u16(block, 16)?,
+                end_system_delay_ms: read_u16(block, 18)?,
+                signal_level: block[20] as i8,
+                noise_level: block[21] as i8,
+                r_factor: block[24],
+                mos_lq: block[26],
+                mos_cq: block[27],
+                jb_nominal_ms: read_u16(block, 30)?,
+                jb_max_ms: read_u16(block, 32)?,
+            });
+        }
+        offset += block_len;
+    }
+    Some(RtcpPacket::ExtendedReport { ssrc, voip })
+}
+
+/// RFC 3550 §6.4.1: A - LSR - DLSR (hepsi NTP orta 32 bit, 1/65536 s).
+/// `arrival_us` raporun yakalandığı an; ölçüm, sniffer SR göndericisine yakın olduğunda anlamlıdır.
+pub(super) fn round_trip_ms(block: &ReportBlock, arrival_us: i64) -> Option<f64> {
+    if block.last_sr == 0 {
+        return None;
+    }
+    const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+    let secs = arrival_us.div_euclid(1_000_000) + NTP_UNIX_OFFSET;
+    let frac = (arrival_us.rem_euclid(1_000_000) << 16) / 1_000_000;
+    let arrival = (((secs as u32) & 0xFFFF) << 16) | frac as u32;
+
+    let rtt = arrival
+        .wrapping_sub(block.last_sr)
+        .wrapping_sub(block.delay_since_last_sr);
+    // Saat kayması / yanlış eşleşme nedeniyle "negatif" sonuçlar atılır.
+    (rtt < 0x8000_0000).then(|| rtt as f64 * 1000.0 / 65536.0)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let b = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let b = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([b[0], b[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(count: u8, packet_type: u8, words: u16) -> Vec<u8> {
+        let mut data = vec![0x80 | count, packet_type];
+        data.extend_from_slice(&words.to_be_bytes());
+        data
+    }
+
+    fn report_block(ssrc: u32, fraction: u8, cumulative: [u8; 3], last_sr: u32) -> Vec<u8> {
+        let mut block = ssrc.to_be_bytes().to_vec();
+        block.push(fraction);
+        block.extend_from_slice(&cumulative);
+        block.extend_from_slice(&1000u32.to_be_bytes()); // highest_seq
+        block.extend_from_slice(&80u32.to_be_bytes()); // jitter
+        block.extend_from_slice(&last_sr.to_be_bytes());
+        block.extend_from_slice(&0x0001_0000u32.to_be_bytes()); // DLSR: 1 sn
+        block
+    }
+
+    /// RR (1 report block) + SDES (CNAME) + BYE (gerekçeli) bileşik paketi.
+    fn compound() -> Vec<u8> {
+        let mut data = header(1, RTCP_RR, 7);
+        data.extend_from_slice(&0x1111u32.to_be_bytes());
+        data.extend_from_slice(&report_block(0x2222, 64, [0xFF, 0xFF, 0xFE], 0));
+
+        data.extend_from_slice(&header(1, RTCP_SDES, 3));
+        data.extend_from_slice(&0x1111u32.to_be_bytes());
+        data.extend_from_slice(&[SDES_CNAME, 4, b'a', b'@', b'h', b'x', 0, 0]);
+
+        data.extend_from_slice(&header(1, RTCP_BYE, 2));
+        data.extend_from_slice(&0x1111u32.to_be_bytes());
+        data.extend_from_slice(&[3, b'b', b'y', b'e']);
+        data
+    }
+
+    #[test]
+    fn parses_compound_packet() {
+        let data = compound();
+        assert!(looks_like_rtcp(&data));
+        let packets = parse(&data, true).expect("RTCP çözülemedi");
+        let types: Vec<_> = packets.iter().map(|p| p.type_name()).collect();
+        assert_eq!(types, ["RR", "SDES", "BYE"]);
+
+        match &packets[0] {
+            RtcpPacket::ReceiverReport { ssrc, reports } => {
+                assert_eq!(*ssrc, 0x1111);
+                assert_eq!(reports[0].ssrc, 0x2222);
+                assert_eq!(reports[0].fraction_lost, 64);
+                assert_eq!(reports[0].cumulative_lost, -2);
+                assert_eq!(reports[0].jitter, 80);
+            }
+            _ => panic!("RR bekleniyordu"),
+        }
+        match &packets[1] {
+            RtcpPacket::SourceDescription(chunks) => {
+                assert_eq!(chunks[0].cname.as_deref(), Some("a@hx"))
+            }
+            _ => panic!("SDES bekleniyordu"),
+        }
+        match &packets[2] {
+            RtcpPacket::Goodbye { sources, reason } => {
+                assert_eq!(sources, &[0x1111]);
+                assert_eq!(reason.as_deref(), Some("bye"));
+            }
+            _ => panic!("BYE bekleniyordu"),
+        }
+    }
+
+    #[test]
+    fn strict_mode_requires_report_first_and_exact_length() {
+        let mut sdes = header(1, RTCP_SDES, 1);
+        sdes.extend_from_slice(&0x1111u32.to_be_bytes());
+        assert!(parse(&sdes, true).is_none());
+        assert!(parse(&sdes, false).is_some());
+
+        let mut truncated = compound();
+        truncated.pop();
+        assert!(parse(&truncated, false).is_none());
+        assert!(!looks_like_rtcp(&[0x80, 0, 0, 1, 0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn parses_xr_voip_metrics() {
+        let mut data = header(0, RTCP_XR, 10);
+        data.extend_from_slice(&0x1111u32.to_be_bytes());
+        let mut block = vec![XR_VOIP_METRICS, 0, 0, 8];
+        block.extend_from_slice(&0x2222u32.to_be_bytes());
+        block.extend_from_slice(&[12, 3, 0, 0, 0, 0, 0, 0]);
+        block.extend_from_slice(&150u16.to_be_bytes());
+        block.extend_from_slice(&40u16.to_be_bytes());
+        block.extend_from_slice(&[0xE2, 0xB0, 0, 0, 88, 0, 41, 43, 0, 0]);
+        block.extend_from_slice(&60u16.to_be_bytes());
+        block.extend_from_slice(&120u16.to_be_bytes());
+        block.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(&block);
+
+        let packets = parse(&data, false).expect("XR çözülemedi");
+        match &packets[0] {
+            RtcpPacket::ExtendedReport { ssrc, voip } => {
+                assert_eq!(*ssrc, 0x1111);
+                let m = &voip[0];
+                assert_eq!(m.ssrc, 0x2222);
+                assert_eq!(m.loss_rate, 12);
+                assert_eq!(m.round_trip_delay_ms, 150);
+                assert_eq!(m.signal_level, -30);
+                assert_eq!(m.r_factor, 88);
+                assert_eq!(m.mos_lq, 41);
+                assert_eq!(m.jb_max_ms, 120);
+            }
+            _ => panic!("XR bekleniyordu"),
+        }
+    }
+
+    #[test]
+    fn round_trip_subtracts_last_sr_and_delay() {
+        // Varış: NTP orta 32 bit = LSR + DLSR (1 sn) + 250 ms
+        let arrival_us = 1_700_000_000_000_000i64;
+        let secs = arrival_us / 1_000_000 + 2_208_988_800;
+        let arrival = ((secs as u32) & 0xFFFF) << 16;
+        let last_sr = arrival.wrapping_sub(0x0001_0000 + 0x4000);
+        let data = report_block(1, 0, [0, 0, 0], last_sr);
+        let block = parse_report_blocks(&data, 1)
+            .expect("blok çözülemedi")
+            .remove(0);
+        assert_eq!(round_trip_ms(&block, arrival_us), Some(250.0));
+
+        let no_sr = parse_report_blocks(&report_block(1, 0, [0, 0, 0], 0), 1)
+            .expect("blok çözülemedi")
+            .remove(0);
+        assert_eq!(round_trip_ms(&no_sr, arrival_us), None);
+    }
+}
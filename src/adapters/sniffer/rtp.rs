@@ -0,0 +1,485 @@
+// src/adapters/sniffer/rtp.rs
+use super::sdp::Codec;
+use lru::LruCache;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+
+/// Aynı anda izlenen SSRC akışı üst sınırı.
+const MAX_STREAMS: usize = 10_000;
+/// Ara rapor periyodu (yakalama zamanı, µs).
+const REPORT_INTERVAL_US: i64 = 5_000_000;
+/// Bu süre paket gelmeyen akış bitmiş sayılır ve final raporu üretilir.
+const STREAM_IDLE_TIMEOUT_US: i64 = 10_000_000;
+const POLL_INTERVAL_US: i64 = 1_000_000;
+/// Duplicate tespiti için hatırlanan son sıra numaraları.
+const RECENT_WINDOW: usize = 128;
+/// Bu eşiklerden birini aşan akış raporu WARN + QOS olarak işaretlenir.
+pub(super) const LOSS_WARN_PCT: f64 = 5.0;
+pub(super) const JITTER_WARN_MS: f64 = 30.0;
+
+/// RFC 3550 sabit başlığı + CSRC listesi, uzantı ve dolgu sınırları.
+pub(super) struct RtpHeader {
+    pub marker: bool,
+    pub payload_type: u8,
+    pub sequence: u16,
+    pub timestamp: u32,
+    pub ssrc: u32,
+    payload_start: usize,
+    payload_end: usize,
+}
+
+impl RtpHeader {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 12 || data[0] >> 6 != 2 {
+            return None;
+        }
+
+        let csrc_count = (data[0] & 0x0F) as usize;
+        let mut payload_start = 12 + csrc_count * 4;
+        if data[0] & 0x10 != 0 {
+            if data.len() < payload_start + 4 {
+                return None;
+            }
+            let ext_words =
+                u16::from_be_bytes([data[payload_start + 2], data[payload_start + 3]]) as usize;
+            payload_start += 4 + ext_words * 4;
+        }
+
+        let mut payload_end = data.len();
+        if data[0] & 0x20 != 0 {
+            payload_end = payload_end.checked_sub(*data.last()? as usize)?;
+        }
+        if payload_start > payload_end {
+            return None;
+        }
+
+        Some(Self {
+            marker: data[1] & 0x80 != 0,
+            payload_type: data[1] & 0x7F,
+            sequence: u16::from_be_bytes([data[2], data[3]]),
+            timestamp: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            ssrc: u32::from_be_bytes([data[8], data[9], data[10], data[11]]),
+            payload_start,
+            payload_end,
+        })
+    }
+
+    pub fn payload<'a>(&self, data: &'a [u8]) -> &'a [u8] {
+        &data[self.payload_start..self.payload_end]
+    }
+}
+
+/// Tek bir SSRC akışının anlık kalite özeti.
+pub(super) struct StreamReport {
+    pub ssrc: u32,
+    pub src: SocketAddr,
+    pub dst: SocketAddr,
+    pub call_id: Option<String>,
+    pub direction: Option<&'static str>,
+    pub payload_type: u8,
+    pub codec: Option<String>,
+    pub packets_received: u64,
+    pub packets_expected: u64,
+    pub packets_lost: u64,
+    pub duplicates: u64,
+    pub out_of_order: u64,
+    pub jitter_ms: f64,
+    pub max_jitter_ms: f64,
+    pub max_delta_ms: f64,
+    pub duration_ms: f64,
+    /// Akıştaki son paketin yakalama zamanı; raporun zaman damgası olarak kullanılır.
+    pub last_seen_us: i64,
+    pub is_final: bool,
+}
+
+impl StreamReport {
+    pub fn loss_pct(&self) -> f64 {
+        if self.packets_expected == 0 {
+            0.0
+        } else {
+            self.packets_lost as f64 * 100.0 / self.packets_expected as f64
+        }
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.loss_pct() >= LOSS_WARN_PCT || self.jitter_ms >= JITTER_WARN_MS
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct StreamKey {
+    ssrc: u32,
+    src: SocketAddr,
+}
+
+struct StreamState {
+    dst: SocketAddr,
+    call_id: Option<String>,
+    direction: Option<&'static str>,
+    payload_type: u8,
+    codec: Option<String>,
+    clock_rate: u32,
+
+    base_seq: u16,
+    max_seq: u16,
+    cycles: u64,
+    received: u64,
+    duplicates: u64,
+    out_of_order: u64,
+    recent: VecDeque<u16>,
+
+    /// Akışın asıl medya PT'si görüldü mü. İlk paket telephone-event ise PT, kodek ve
+    /// saat hızı (jitter tabanı) ilk medya paketinden alınır.
+    has_media: bool,
+    last_timestamp: u32,
+    /// Jitter yalnızca akışın asıl PT'si için hesaplanır; telephone-event / CN
+    /// aynı SSRC'yi paylaşsa da timestamp anlamları farklıdır.
+    last_media_arrival_us: i64,
+    last_arrival_us: i64,
+    first_arrival_us: i64,
+    /// RFC 3550 A.8 kestirimi, RTP saat birimi cinsinden.
+    jitter: f64,
+    max_jitter: f64,
+    max_delta_us: i64,
+    last_report_us: i64,
+}
+
+/// Bağlamı çağrıdan gelen (Call-ID, yön, kodek) bilgisi.
+pub(super) struct StreamContext<'a> {
+    pub call_id: Option<&'a str>,
+    pub direction: Option<&'static str>,
+    pub codec: Option<&'a Codec>,
+    /// RFC 4733 telephone-event paketi; jitter tabanını belirlemez.
+    pub telephone_event: bool,
+}
+
+/// SSRC başına kayıp, jitter, sıra dışı / tekrar paket ve en büyük varış aralığı takibi.
+pub(super) struct RtpStreamTracker {
+    streams: LruCache<StreamKey, StreamState>,
+    last_poll_us: i64,
+}
+
+impl RtpStreamTracker {
+    pub fn new() -> Self {
+        Self {
+            streams: LruCache::new(NonZeroUsize::new(MAX_STREAMS).unwrap_or(NonZeroUsize::MIN)),
+            last_poll_us: 0,
+        }
+    }
+
+    pub fn observe(
+        &mut self,
+        header: &RtpHeader,
+        src: SocketAddr,
+        dst: SocketAddr,
+        arrival_us: i64,
+        ctx: StreamContext,
+    ) {
+        let key = StreamKey {
+            ssrc: header.ssrc,
+            src,
+        };
+        let clock_rate = ctx.codec.map(|c| c.clock_rate).unwrap_or(8000).max(1);
+
+        let Some(stream) = self.streams.get_mut(&key) else {
+            self.streams.put(
+                key,
+                StreamState {
+                    dst,
+                    call_id: ctx.call_id.map(|c| c.to_string()),
+                    direction: ctx.direction,
+                    payload_type: header.payload_type,
+                    codec: ctx.codec.map(|c| c.name.clone()),
+                    clock_rate,
+                    base_seq: header.sequence,
+                    max_seq: header.sequence,
+                    cycles: 0,
+                    received: 1,
+                    duplicates: 0,
+                    out_of_order: 0,
+                    recent: VecDeque::from([header.sequence]),
+                    has_media: !ctx.telephone_event,
+                    last_timestamp: header.timestamp,
+                    last_media_arrival_us: arrival_us,
+                    last_arrival_us: arrival_us,
+                    first_arrival_us: arrival_us,
+                    jitter: 0.0,
+                    max_jitter: 0.0,
+                    max_delta_us: 0,
+                    last_report_us: arrival_us,
+                },
+            );
+            return;
+        };
+
+        // Çağrı bağı akış başladıktan sonra (ör. geç gelen 200 OK) öğrenilmiş olabilir.
+        if stream.call_id.is_none() {
+            stream.call_id = ctx.call_id.map(|c| c.to_string());
+            stream.direction = ctx.direction;
+        }
+
+        let delta = header.sequence.wrapping_sub(stream.max_seq);
+        if delta == 0 {
+            stream.duplicates += 1;
+            return;
+        } else if delta < 0x8000 {
+            if header.sequence < stream.max_seq {
+                stream.cycles += 1 << 16;
+            }
+            stream.max_seq = header.sequence;
+        } else if stream.recent.contains(&header.sequence) {
+            stream.duplicates += 1;
+            return;
+        } else {
+            stream.out_of_order += 1;
+        }
+
+        stream.received += 1;
+        stream.recent.push_back(header.sequence);
+        if stream.recent.len() > RECENT_WINDOW {
+            stream.recent.pop_front();
+        }
+
+        if !stream.has_media && !ctx.telephone_event {
+            // İlk medya paketi: jitter tabanı buradan başlar, fark hesaplanmaz.
+            stream.has_media = true;
+            stream.payload_type = header.payload_type;
+            stream.codec = ctx.codec.map(|c| c.name.clone());
+            stream.clock_rate = clock_rate;
+            stream.last_timestamp = header.timestamp;
+            stream.last_media_arrival_us = arrival_us;
+        } else if stream.has_media
+            && !ctx.telephone_event
+            && header.payload_type == stream.payload_type
+        {
+            // D(i-1, i) = (Rj - Ri) - (Sj - Si); J += (|D| - J) / 16
+            let arrival_delta = (arrival_us - stream.last_media_arrival_us) as f64
+                * stream.clock_rate as f64
+                / 1_000_000.0;
+            let ts_delta = header.timestamp.wrapping_sub(stream.last_timestamp) as i32 as f64;
+            let d = arrival_delta - ts_delta;
+            stream.jitter += (d.abs() - stream.jitter) / 16.0;
+            stream.max_jitter = stream.max_jitter.max(stream.jitter);
+            stream.last_timestamp = header.timestamp;
+            stream.last_media_arrival_us = arrival_us;
+        }
+
+        stream.max_delta_us = stream.max_delta_us.max(arrival_us - stream.last_arrival_us);
+        stream.last_arrival_us = arrival_us;
+    }
+
+    /// Periyodik ara raporlar ve boşta kalan akışların final raporları.
+    pub fn poll(&mut self, now_us: i64) -> Vec<StreamReport> {
+        if now_us - self.last_poll_us < POLL_INTERVAL_US {
+            return Vec::new();
+        }
+        self.last_poll_us = now_us;
+
+        let mut reports = Vec::new();
+        let mut finished = Vec::new();
+        for (key, stream) in self.streams.iter_mut() {
+            if now_us - stream.last_arrival_us > STREAM_IDLE_TIMEOUT_US {
+                finished.push(*key);
+            } else if now_us - stream.last_report_us >= REPORT_INTERVAL_US {
+                stream.last_report_us = now_us;
+                reports.push(Self::report(key, stream, false));
+            }
+        }
+        for key in finished {
+            if let Some(stream) = self.streams.pop(&key) {
+                reports.push(Self::report(&key, &stream, true));
+            }
+        }
+        reports
+    }
+
+    /// Yakalama sonunda (ör. pcap dosyası bitti) tüm akışlar için final rapor.
+    pub fn drain(&mut self) -> Vec<StreamReport> {
+        let mut reports = Vec::new();
+        while let Some((key, stream)) = self.streams.pop_lru() {
+            reports.push(Self::report(&key, &stream, true));
+        }
+        reports
+    }
+
+    fn report(key: &StreamKey, s: &StreamState, is_final: bool) -> StreamReport {
+        let extended_max = s.cycles + s.max_seq as u64;
+        let packets_expected = (extended_max + 1).saturating_sub(s.base_seq as u64);
+        let to_ms = |units: f64| units * 1000.0 / s.clock_rate as f64;
+
+        StreamReport {
+            ssrc: key.ssrc,
+            src: key.src,
+            dst: s.dst,
+            call_id: s.call_id.clone(),
+            direction: s.direction,
+            payload_type: s.payload_type,
+            codec: s.codec.clone(),
+            packets_received: s.received,
+            packets_expected,
+            packets_lost: packets_expected.saturating_sub(s.received),
+            duplicates: s.duplicates,
+            out_of_order: s.out_of_order,
+            jitter_ms: to_ms(s.jitter),
+            max_jitter_ms: to_ms(s.max_jitter),
+            max_delta_ms: s.max_delta_us as f64 / 1000.0,
+            duration_ms: (s.last_arrival_us - s.first_arrival_us) as f64 / 1000.0,
+            last_seen_us: s.last_arrival_us,
+            is_final,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(pt: u8, seq: u16, ts: u32, ssrc: u32, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0x80, pt];
+        data.extend_from_slice(&seq.to_be_bytes());
+        data.extend_from_slice(&ts.to_be_bytes());
+        data.extend_from_slice(&ssrc.to_be_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([10, 0, 0, 1], port))
+    }
+
+    fn ctx() -> StreamContext<'static> {
+        StreamContext {
+            call_id: Some("call-1"),
+            direction: Some("caller_to_callee"),
+            codec: None,
+            telephone_event: false,
+        }
+    }
+
+    #[test]
+    fn parses_header_with_csrc_extension_and_padding() {
+        let mut data = packet(0x80 | 8, 7, 160, 0xDEADBEEF, &[]);
+        data[0] |= 0x01 | 0x10 | 0x20;
+        data.extend_from_slice(&[0, 0, 0, 1]); // CSRC
+        data.extend_from_slice(&[0xBE, 0xDE, 0, 1, 1, 2, 3, 4]); // 1 kelimelik uzantı
+        data.extend_from_slice(&[0xAA, 0xBB, 0, 0, 3]); // yük + 3 bayt dolgu
+
+        let header = RtpHeader::parse(&data).expect("RTP çözülemedi");
+        assert!(header.marker);
+        assert_eq!(header.payload_type, 8);
+        assert_eq!(header.sequence, 7);
+        assert_eq!(header.timestamp, 160);
+        assert_eq!(header.ssrc, 0xDEADBEEF);
+        assert_eq!(header.payload(&data), &[0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn rejects_wrong_version_and_bad_padding() {
+        let mut data = packet(0, 1, 0, 1, &[1, 2]);
+        data[0] = 0x40;
+        assert!(RtpHeader::parse(&data).is_none());
+
+        let mut padded = packet(0, 1, 0, 1, &[1, 200]);
+        padded[0] |= 0x20;
+        assert!(RtpHeader::parse(&padded).is_none());
+        assert!(RtpHeader::parse(&[0x80; 11]).is_none());
+    }
+
+    #[test]
+    fn tracks_loss_duplicates_and_reordering() {
+        let mut tracker = RtpStreamTracker::new();
+        // 1, 2, 2 (tekrar), 5, 4 (sıra dışı); 3 kayıp
+        for (i, seq) in [1u16, 2, 2, 5, 4].into_iter().enumerate() {
+            let data = packet(0, seq, seq as u32 * 160, 42, &[0; 160]);
+            let header = RtpHeader::parse(&data).expect("RTP çözülemedi");
+            tracker.observe(&header, addr(4000), addr(5000), i as i64 * 20_000, ctx());
+        }
+
+        let reports = tracker.drain();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert!(report.is_final);
+        assert_eq!(report.call_id.as_deref(), Some("call-1"));
+        assert_eq!(report.packets_expected, 5);
+        assert_eq!(report.packets_received, 4);
+        assert_eq!(report.packets_lost, 1);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.out_of_order, 1);
+        assert!((report.loss_pct() - 20.0).abs() < f64::EPSILON);
+        assert!(report.is_degraded());
+    }
+
+    #[test]
+    fn sequence_wrap_does_not_count_as_loss() {
+        let mut tracker = RtpStreamTracker::new();
+        for (i, seq) in [65534u16, 65535, 0, 1].into_iter().enumerate() {
+            let data = packet(0, seq, i as u32 * 160, 7, &[0; 160]);
+            let header = RtpHeader::parse(&data).expect("RTP çözülemedi");
+            tracker.observe(&header, addr(4000), addr(5000), i as i64 * 20_000, ctx());
+        }
+        let report = tracker.drain().pop().expect("rapor yok");
+        assert_eq!(report.packets_expected, 4);
+        assert_eq!(report.packets_lost, 0);
+        assert_eq!(report.jitter_ms, 0.0);
+    }
+
+    #[test]
+    fn idle_stream_gets_final_report_on_poll() {
+        let mut tracker = RtpStreamTracker::new();
+        let data = packet(0, 1, 0, 9, &[0; 160]);
+        let header = RtpHeader::parse(&data).expect("RTP çözülemedi");
+        tracker.observe(&header, addr(4000), addr(5000), 1_000_000, ctx());
+
+        assert!(tracker.poll(2_000_000).is_empty());
+        let reports = tracker.poll(1_000_000 + STREAM_IDLE_TIMEOUT_US + 1);
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].is_final);
+        assert!(tracker.drain().is_empty());
+    }
+
+    #[test]
+    fn telephone_event_does_not_set_the_jitter_baseline() {
+        let pcmu = Codec {
+            name: "PCMU".to_string(),
+            clock_rate: 8000,
+        };
+        let event = Codec {
+            name: "telephone-event".to_string(),
+            clock_rate: 48000,
+        };
+        let mut tracker = RtpStreamTracker::new();
+        let mut observe = |pt: u8, seq: u16, ts: u32, arrival_us: i64| {
+            let (codec, telephone_event) = if pt == 101 {
+                (&event, true)
+            } else {
+                (&pcmu, false)
+            };
+            let data = packet(pt, seq, ts, 11, &[0; 160]);
+            let header = RtpHeader::parse(&data).expect("RTP çözülemedi");
+            tracker.observe(
+                &header,
+                addr(4000),
+                addr(5000),
+                arrival_us,
+                StreamContext {
+                    telephone_event,
+                    codec: Some(codec),
+                    ..ctx()
+                },
+            );
+        };
+        // Akış bir DTMF paketiyle başlar, ardından düzenli 20 ms'lik G.711 gelir.
+        observe(101, 1, 999_999, 0);
+        for i in 0..5u16 {
+            observe(0, 2 + i, i as u32 * 160, 20_000 + i as i64 * 20_000);
+        }
+        observe(101, 7, 999_999, 130_000);
+
+        let report = tracker.drain().pop().expect("rapor yok");
+        assert_eq!(report.payload_type, 0);
+        assert_eq!(report.codec.as_deref(), Some("PCMU"));
+        assert_eq!(report.jitter_ms, 0.0);
+        assert_eq!(report.packets_received, 7);
+    }
+}
@@ -0,0 +1,166 @@
+// src/adapters/sniffer/sdp.rs
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// SDP (RFC 4566) içinden medya bağlama için gereken alt küme.
+pub(super) struct SessionDescription {
+    pub media: Vec<MediaDescription>,
+}
+
+pub(super) struct MediaDescription {
+    pub kind: String,
+    pub addr: Option<IpAddr>,
+    pub port: u16,
+    pub rtcp_port: u16,
+    pub payload_types: Vec<u8>,
+    /// `a=rtpmap` eşlemeleri: PT -> kodek (ör. 101 -> "telephone-event/8000")
+    pub codecs: HashMap<u8, Codec>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Codec {
+    pub name: String,
+    pub clock_rate: u32,
+}
+
+impl SessionDescription {
+    pub fn parse(body: &str) -> Option<Self> {
+        if !body.trim_start().starts_with("v=") {
+            return None;
+        }
+
+        let mut session_addr: Option<IpAddr> = None;
+        let mut media: Vec<MediaDescription> = Vec::new();
+
+        for line in body.lines() {
+            let Some((kind, value)) = line.trim().split_once('=') else {
+                continue;
+            };
+            match kind {
+                "c" => {
+                    // c=IN IP4 10.0.0.1  (multicast TTL eki: 224.2.1.1/127)
+                    let addr = value
+                        .split_whitespace()
+                        .nth(2)
+                        .and_then(|a| a.split('/').next())
+                        .and_then(|a| a.parse().ok());
+                    match media.last_mut() {
+                        Some(m) => m.addr = addr,
+                        None => session_addr = addr,
+                    }
+                }
+                "m" => {
+                    // m=audio 49170 RTP/AVP 0 8 101
+                    let mut parts = value.split_whitespace();
+                    let kind = parts.next().unwrap_or("").to_string();
+                    let port: u16 = parts
+                        .next()
+                        .and_then(|p| p.split('/').next())
+                        .and_then(|p| p.parse().ok())
+                        .unwrap_or(0);
+                    let _proto = parts.next();
+                    let payload_types = parts.filter_map(|pt| pt.parse().ok()).collect();
+                    media.push(MediaDescription {
+                        kind,
+                        addr: session_addr,
+                        port,
+                        rtcp_port: port.saturating_add(1),
+                        payload_types,
+                        codecs: HashMap::new(),
+                    });
+                }
+                "a" => {
+                    let Some(m) = media.last_mut() else {
+                        continue;
+                    };
+                    if let Some(map) = value.strip_prefix("rtpmap:") {
+                        // a=rtpmap:101 telephone-event/8000
+                        if let Some((pt, encoding)) = map.split_once(' ') {
+                            if let Ok(pt) = pt.trim().parse() {
+                                let mut parts = encoding.trim().split('/');
+                                let name = parts.next().unwrap_or("").to_string();
+                                let clock_rate =
+                                    parts.next().and_then(|r| r.parse().ok()).unwrap_or(8000);
+                                m.codecs.insert(pt, Codec { name, clock_rate });
+                            }
+                        }
+                    } else if let Some(rtcp) = value.strip_prefix("rtcp:") {
+                        if let Some(port) =
+                            rtcp.split_whitespace().next().and_then(|p| p.parse().ok())
+                        {
+                            m.rtcp_port = port;
+                        }
+                    } else if value.trim() == "rtcp-mux" {
+                        m.rtcp_port = m.port;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self { media })
+    }
+}
+
+/// RFC 3551 statik payload tipleri. Hepsi 8 kHz RTP saatiyle çalışır (G722 dahil).
+pub(super) fn static_codec(pt: u8) -> Option<Codec> {
+    let name = match pt {
+        0 => "PCMU",
+        3 => "GSM",
+        4 => "G723",
+        8 => "PCMA",
+        9 => "G722",
+        13 => "CN",
+        18 => "G729",
+        _ => return None,
+    };
+    Some(Codec {
+        name: name.to_string(),
+        clock_rate: 8000,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_media_with_session_and_media_level_connection() {
+        let body = "v=0\r\n\
+            o=- 1 1 IN IP4 10.0.0.1\r\n\
+            c=IN IP4 10.0.0.1\r\n\
+            m=audio 40000 RTP/AVP 0 8 101\r\n\
+            a=rtpmap:101 telephone-event/8000\r\n\
+            a=rtcp:40005\r\n\
+            m=video 50000 RTP/AVP 96\r\n\
+            c=IN IP4 224.2.1.1/127\r\n\
+            a=rtpmap:96 H264/90000\r\n\
+            a=rtcp-mux\r\n";
+        let sdp = SessionDescription::parse(body).expect("SDP çözülemedi");
+        assert_eq!(sdp.media.len(), 2);
+
+        let audio = &sdp.media[0];
+        assert_eq!(audio.kind, "audio");
+        assert_eq!(audio.addr, "10.0.0.1".parse().ok());
+        assert_eq!(audio.port, 40000);
+        assert_eq!(audio.rtcp_port, 40005);
+        assert_eq!(audio.payload_types, vec![0, 8, 101]);
+        assert_eq!(audio.codecs[&101].name, "telephone-event");
+
+        let video = &sdp.media[1];
+        assert_eq!(video.addr, "224.2.1.1".parse().ok());
+        assert_eq!(video.rtcp_port, 50000);
+        assert_eq!(video.codecs[&96].clock_rate, 90000);
+    }
+
+    #[test]
+    fn rejects_non_sdp_and_defaults_rtcp_port() {
+        assert!(SessionDescription::parse("hello").is_none());
+        let sdp =
+            SessionDescription::parse("v=0\nm=audio 4000 RTP/AVP 0\n").expect("SDP çözülemedi");
+        assert_eq!(sdp.media[0].rtcp_port, 4001);
+        assert_eq!(sdp.media[0].addr, None);
+        assert_eq!(static_codec(8).map(|c| c.name), Some("PCMA".to_string()));
+        assert!(static_codec(96).is_none());
+    }
+}
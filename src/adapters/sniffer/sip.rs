@@ -0,0 +1,281 @@
+// src/adapters/sniffer/sip.rs
+
+/// Ayrıştırılmış SIP mesajı (RFC 3261). Başlık adları küçük harfe ve kompakt
+/// formlardan (`i`, `f`, `v` ...) uzun adlarına normalize edilir.
+pub(super) struct SipMessage {
+    pub start: StartLine,
+    headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+pub(super) enum StartLine {
+    Request { method: String, uri: String },
+    Response { code: u16, reason: String },
+}
+
+/// `From`/`To`/`Contact` gibi name-addr / addr-spec başlıkları.
+pub(super) struct NameAddr {
+    pub display: Option<String>,
+    pub uri: String,
+    pub tag: Option<String>,
+}
+
+impl SipMessage {
+    pub fn parse(text: &str) -> Option<Self> {
+        let (head, body) = match text.find("\r\n\r\n") {
+            Some(pos) => (&text[..pos], &text[pos + 4..]),
+            None => match text.find("\n\n") {
+                Some(pos) => (&text[..pos], &text[pos + 2..]),
+                None => (text, ""),
+            },
+        };
+
+        let mut lines = head.lines();
+        let start = Self::parse_start_line(lines.next()?.trim())?;
+
+        let mut headers: Vec<(String, String)> = Vec::new();
+        for line in lines {
+            // Katlanmış (folded) başlık: SP/HTAB ile başlayan satır öncekinin devamıdır.
+            if line.starts_with(' ') || line.starts_with('\t') {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((canonical_name(name.trim()), value.trim().to_string()));
+            }
+        }
+
+        Some(Self {
+            start,
+            headers,
+            body: body.to_string(),
+        })
+    }
+
+    fn parse_start_line(line: &str) -> Option<StartLine> {
+        if let Some(rest) = line.strip_prefix("SIP/2.0 ") {
+            let (code, reason) = rest.split_once(' ').unwrap_or((rest, ""));
+            return Some(StartLine::Response {
+                code: code.trim().parse().ok()?,
+                reason: reason.trim().to_string(),
+            });
+        }
+
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?;
+        let uri = parts.next()?;
+        if parts.next()? != "SIP/2.0" {
+            return None;
+        }
+        Some(StartLine::Request {
+            method: method.to_string(),
+            uri: uri.to_string(),
+        })
+    }
+
+    /// Başlığın ilk geçtiği değer.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Çok değerli başlık: tüm tekrarlar + virgülle ayrılmış değerler sırasıyla.
+    pub fn header_values(&self, name: &str) -> Vec<&str> {
+        self.headers
+            .iter()
+            .filter(|(n, _)| n == name)
+            .flat_map(|(_, v)| split_list(v))
+            .collect()
+    }
+
+    /// CSeq: (sıra numarası, metot)
+    pub fn cseq(&self) -> Option<(u32, &str)> {
+        let (num, method) = self.header("cseq")?.split_once(' ')?;
+        Some((num.trim().parse().ok()?, method.trim()))
+    }
+}
+
+impl NameAddr {
+    pub fn parse(value: &str) -> Self {
+        let (display, uri, params) = match (value.find('<'), value.find('>')) {
+            (Some(open), Some(close)) if open < close => {
+                let display = value[..open].trim().trim_matches('"').trim();
+                (
+                    (!display.is_empty()).then(|| display.to_string()),
+                    value[open + 1..close].trim(),
+                    &value[close + 1..],
+                )
+            }
+            // addr-spec formunda ';' sonrası başlık parametresidir, URI'ye ait değildir.
+            _ => match value.split_once(';') {
+                Some((uri, params)) => (None, uri.trim(), params),
+                None => (None, value.trim(), ""),
+            },
+        };
+
+        Self {
+            display,
+            uri: uri.to_string(),
+            tag: param(params, "tag").map(|t| t.to_string()),
+        }
+    }
+
+    /// URI'nin kullanıcı kısmı (`sip:alice@host` -> `alice`, `tel:+90...` -> `+90...`).
+    pub fn user(&self) -> Option<&str> {
+        let rest = self
+            .uri
+            .split_once(':')
+            .map(|(_, r)| r)
+            .unwrap_or(&self.uri);
+        let user = match rest.split_once('@') {
+            Some((user, _)) => user,
+            None if self.uri.starts_with("tel:") => rest,
+            None => return None,
+        };
+        let user = user.split([';', ':']).next().unwrap_or(user);
+        (!user.is_empty()).then_some(user)
+    }
+}
+
+/// `;name=value` listesinden bir parametre (adı büyük/küçük harf duyarsız).
+pub(super) fn param<'a>(params: &'a str, name: &str) -> Option<&'a str> {
+    params.split(';').find_map(|p| {
+        let (k, v) = p.split_once('=')?;
+        k.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| v.trim().trim_matches('"'))
+    })
+}
+
+/// RFC 3261 §7.3.1 kompakt formlar ve diğer yaygın kısaltmalar.
+fn canonical_name(name: &str) -> String {
+    let lower = name.to_ascii_lowercase();
+    let long = match lower.as_str() {
+        "i" => "call-id",
+        "f" => "from",
+        "t" => "to",
+        "v" => "via",
+        "m" => "contact",
+        "l" => "content-length",
+        "c" => "content-type",
+        "e" => "content-encoding",
+        "k" => "supported",
+        "s" => "subject",
+        "o" => "event",
+        "r" => "refer-to",
+        "b" => "referred-by",
+        "u" => "allow-events",
+        "x" => "session-expires",
+        "a" => "accept-contact",
+        "j" => "reject-contact",
+        "d" => "request-disposition",
+        "y" => "identity",
+        _ => return lower,
+    };
+    long.to_string()
+}
+
+/// Tırnak ve `<...>` içindeki virgüllere dokunmadan listeyi böler.
+fn split_list(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut in_angle = false;
+    let mut start = 0;
+
+    for (i, ch) in value.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_angle = true,
+            '>' if !in_quotes => in_angle = false,
+            ',' if !in_quotes && !in_angle => {
+                parts.push(value[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(value[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVITE: &str = "INVITE sip:bob@example.com SIP/2.0\r\n\
+        v: SIP/2.0/UDP 10.0.0.1:5060;branch=z9hG4bK1, SIP/2.0/UDP 10.0.0.2:5060;branch=z9hG4bK2\r\n\
+        f: \"Alice, A\" <sip:alice@example.com>;tag=abc\r\n\
+        t: <sip:bob@example.com>\r\n\
+        i: call-1@host\r\n\
+        CSeq: 1 INVITE\r\n\
+        Subject: uzun\r\n \tbaşlık\r\n\
+        \r\n\
+        v=0\r\n";
+
+    #[test]
+    fn parses_request_with_compact_and_folded_headers() {
+        let msg = SipMessage::parse(INVITE).expect("INVITE çözülemedi");
+        match &msg.start {
+            StartLine::Request { method, uri } => {
+                assert_eq!(method, "INVITE");
+                assert_eq!(uri, "sip:bob@example.com");
+            }
+            StartLine::Response { .. } => panic!("istek bekleniyordu"),
+        }
+        assert_eq!(msg.header("call-id"), Some("call-1@host"));
+        assert_eq!(msg.header("subject"), Some("uzun başlık"));
+        assert_eq!(msg.header_values("via").len(), 2);
+        assert_eq!(msg.cseq(), Some((1, "INVITE")));
+        assert_eq!(msg.body, "v=0\r\n");
+    }
+
+    #[test]
+    fn parses_response_and_rejects_garbage() {
+        let msg = SipMessage::parse("SIP/2.0 486 Busy Here\r\nCSeq: 2 INVITE\r\n\r\n")
+            .expect("yanıt çözülemedi");
+        match msg.start {
+            StartLine::Response { code, reason } => {
+                assert_eq!(code, 486);
+                assert_eq!(reason, "Busy Here");
+            }
+            StartLine::Request { .. } => panic!("yanıt bekleniyordu"),
+        }
+        assert!(SipMessage::parse("HTTP/1.1 200 OK\r\n\r\n").is_none());
+        assert!(SipMessage::parse("SIP/2.0 abc\r\n\r\n").is_none());
+    }
+
+    #[test]
+    fn name_addr_forms() {
+        let quoted = NameAddr::parse("\"Alice, A\" <sip:alice@example.com;transport=tcp>;tag=abc");
+        assert_eq!(quoted.display.as_deref(), Some("Alice, A"));
+        assert_eq!(quoted.uri, "sip:alice@example.com;transport=tcp");
+        assert_eq!(quoted.tag.as_deref(), Some("abc"));
+        assert_eq!(quoted.user(), Some("alice"));
+
+        let spec = NameAddr::parse("sip:bob@example.com;tag=xyz");
+        assert_eq!(spec.display, None);
+        assert_eq!(spec.uri, "sip:bob@example.com");
+        assert_eq!(spec.tag.as_deref(), Some("xyz"));
+
+        assert_eq!(
+            NameAddr::parse("<tel:+905551112233>").user(),
+            Some("+905551112233")
+        );
+        assert_eq!(NameAddr::parse("<sip:example.com>").user(), None);
+    }
+
+    #[test]
+    fn split_list_keeps_quoted_and_bracketed_commas() {
+        assert_eq!(
+            split_list("\"A, B\" <sip:a@x>, <sip:b@x;p=1,2>,"),
+            vec!["\"A, B\" <sip:a@x>", "<sip:b@x;p=1,2>"]
+        );
+        assert_eq!(param("a=1; Tag=\"q\"", "tag"), Some("q"));
+    }
+}
@@ -0,0 +1,424 @@
+// src/adapters/sniffer/tcp.rs
+use super::ws::{self, WsDecoder};
+use lru::LruCache;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+
+pub(super) const TCP_FIN: u8 = 0x01;
+pub(super) const TCP_RST: u8 = 0x04;
+
+/// Aynı anda izlenen TCP akışı (yön başına) üst sınırı.
+const MAX_FLOWS: usize = 4096;
+/// Tamamlanmamış tek bir SIP mesajı için tutulabilecek en fazla bayt.
+const MAX_FLOW_BUFFER: usize = 256 * 1024;
+/// Boşluk kapanmadan bekletilebilecek sırasız segment sayısı.
+const MAX_PENDING_SEGMENTS: usize = 64;
+/// Bu süre boyunca paket görmeyen akışlar bellekten atılır (yakalama zamanı, µs).
+const FLOW_IDLE_TIMEOUT_US: i64 = 120_000_000;
+const SWEEP_INTERVAL_US: i64 = 30_000_000;
+
+/// Yönlü 4'lü (src_ip, src_port, dst_ip, dst_port). Her yön ayrı bir bayt akışıdır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct FlowKey {
+    pub src_ip: IpAddr,
+    pub src_port: u16,
+    pub dst_ip: IpAddr,
+    pub dst_port: u16,
+}
+
+struct FlowState {
+    next_seq: u32,
+    buffer: Vec<u8>,
+    pending: BTreeMap<u32, Vec<u8>>,
+    last_seen_us: i64,
+    framing: Framing,
+}
+
+/// Akıştaki bayt dizisinin SIP mesajlarına nasıl bölüneceği.
+enum Framing {
+    /// RFC 3261 stream çerçevelemesi (`Content-Length`)
+    Sip,
+    /// HTTP upgrade başlığı bekleniyor; ardından WebSocket'e geçilir.
+    Handshake,
+    /// RFC 7118: her WebSocket mesajı bir SIP mesajıdır.
+    WebSocket(WsDecoder),
+    /// Taşma veya kayıp sonrası çerçeve sınırı bilinmiyor; akış FIN/RST ya da boşta kalma
+    /// ile düşene kadar gelen veri atılır (rastgele bir ofsetten yeniden çözülmez).
+    Broken,
+}
+
+/// Akıştan çıkarılan tam SIP mesajı ve taşındığı katman (`net.transport`).
+pub(super) struct StreamMessage {
+    pub data: Vec<u8>,
+    pub transport: &'static str,
+}
+
+/// SIP-over-TCP akışlarını segment sırasına göre birleştirip `Content-Length`
+/// çerçevelemesine göre tam SIP mesajlarına bölen izleyici. HTTP upgrade ile başlayan
+/// akışlar SIP-over-WebSocket (RFC 7118) olarak çözülür.
+pub(super) struct TcpReassembler {
+    flows: LruCache<FlowKey, FlowState>,
+    last_sweep_us: i64,
+}
+
+impl TcpReassembler {
+    pub fn new() -> Self {
+        Self {
+            flows: LruCache::new(NonZeroUsize::new(MAX_FLOWS).unwrap_or(NonZeroUsize::MIN)),
+            last_sweep_us: 0,
+        }
+    }
+
+    /// Akış SIP taşıdığı için takip ediliyor mu.
+    pub fn is_tracking(&self, key: &FlowKey) -> bool {
+        self.flows.contains(key)
+    }
+
+    /// Bir segmenti akışa ekler ve tamamlanan SIP mesajlarını döner.
+    pub fn push(
+        &mut self,
+        key: FlowKey,
+        seq: u32,
+        flags: u8,
+        payload: &[u8],
+        now_us: i64,
+    ) -> Vec<StreamMessage> {
+        self.sweep(now_us);
+
+        if flags & TCP_RST != 0 {
+            self.flows.pop(&key);
+            return Vec::new();
+        }
+
+        // Akış ancak SIP start-line veya WebSocket el sıkışması içeren ilk veriyle izlenmeye
+        // başlanır; böylece filtre boşken alakasız TCP trafiği tabloyu doldurmaz.
+        if !self.flows.contains(&key) {
+            let framing = if ws::looks_like_handshake(payload) {
+                Framing::Handshake
+            } else if find_start_line(payload).is_some() {
+                Framing::Sip
+            } else {
+                return Vec::new();
+            };
+            self.flows.put(
+                key,
+                FlowState {
+                    next_seq: seq,
+                    buffer: Vec::new(),
+                    pending: BTreeMap::new(),
+                    last_seen_us: now_us,
+                    framing,
+                },
+            );
+        }
+
+        let mut messages = Vec::new();
+        if let Some(flow) = self.flows.get_mut(&key) {
+            flow.last_seen_us = now_us;
+            flow.ingest(seq, payload);
+            messages = flow.drain_messages();
+        }
+
+        if flags & TCP_FIN != 0 {
+            self.flows.pop(&key);
+        }
+        messages
+    }
+
+    fn sweep(&mut self, now_us: i64) {
+        if now_us - self.last_sweep_us < SWEEP_INTERVAL_US {
+            return;
+        }
+        self.last_sweep_us = now_us;
+
+        let expired: Vec<FlowKey> = self
+            .flows
+            .iter()
+            .filter(|(_, f)| now_us - f.last_seen_us > FLOW_IDLE_TIMEOUT_US)
+            .map(|(k, _)| *k)
+            .collect();
+        for key in expired {
+            self.flows.pop(&key);
+        }
+    }
+}
+
+impl FlowState {
+    fn ingest(&mut self, seq: u32, payload: &[u8]) {
+        if payload.is_empty() {
+            return;
+        }
+
+        if (seq.wrapping_sub(self.next_seq) as i32) > 0 {
+            // Sırasız segment: boşluk kapanana kadar beklet.
+            if self.pending.len() < MAX_PENDING_SEGMENTS {
+                self.pending.insert(seq, payload.to_vec());
+                return;
+            }
+            // Boşluk kapanmıyor: kayıp kabul edilir, akış bu segmentten yeniden senkronize edilir.
+            self.buffer.clear();
+            self.pending.clear();
+            self.next_seq = seq;
+            // WebSocket çerçeve sınırı segmentin ortasında olabilir; SIP start-line ile
+            // yeniden eşlenebilir ama WS akışı için güvenilir bir eşleme noktası yoktur.
+            if !matches!(self.framing, Framing::Sip) {
+                self.mark_broken();
+            }
+        }
+
+        self.append(seq, payload);
+
+        while let Some(ready) = self
+            .pending
+            .keys()
+            .copied()
+            .find(|s| (s.wrapping_sub(self.next_seq) as i32) <= 0)
+        {
+            if let Some(data) = self.pending.remove(&ready) {
+                self.append(ready, &data);
+            }
+        }
+    }
+
+    /// `seq <= next_seq` olan segmenti ekler; yeniden iletilen (overlap) kısım atlanır.
+    fn append(&mut self, seq: u32, payload: &[u8]) {
+        let overlap = self.next_seq.wrapping_sub(seq) as usize;
+        if overlap >= payload.len() {
+            return;
+        }
+        self.buffer.extend_from_slice(&payload[overlap..]);
+        self.next_seq = seq.wrapping_add(payload.len() as u32);
+    }
+
+    fn drain_messages(&mut self) -> Vec<StreamMessage> {
+        if let Framing::Broken = self.framing {
+            self.buffer.clear();
+            return Vec::new();
+        }
+
+        if let Framing::Handshake = self.framing {
+            let Some(header_end) = find(&self.buffer, b"\r\n\r\n") else {
+                self.enforce_limit();
+                return Vec::new();
+            };
+            // Upgrade reddedildiyse (ör. 401) akış düz SIP-over-TCP gibi izlenmeye devam eder.
+            self.framing = if ws::is_upgrade(&self.buffer[..header_end]) {
+                Framing::WebSocket(WsDecoder::default())
+            } else {
+                Framing::Sip
+            };
+            self.buffer.drain(..header_end + 4);
+        }
+
+        if let Framing::WebSocket(decoder) = &mut self.framing {
+            let messages = decoder
+                .drain(&mut self.buffer)
+                .into_iter()
+                .map(|data| StreamMessage {
+                    data,
+                    transport: "ws",
+                })
+                .collect();
+            if decoder.overflowed() {
+                self.mark_broken();
+            }
+            self.enforce_limit();
+            return messages;
+        }
+
+        let mut messages = Vec::new();
+
+        loop {
+            // RFC 5626 keep-alive (CRLF) paketleri mesaj değildir.
+            let keepalive = self
+                .buffer
+                .iter()
+                .take_while(|b| **b == b'\r' || **b == b'\n')
+                .count();
+            self.buffer.drain(..keepalive);
+            if self.buffer.is_empty() {
+                break;
+            }
+
+            match find_start_line(&self.buffer) {
+                Some(0) => {}
+                Some(pos) => {
+                    self.buffer.drain(..pos);
+                }
+                None => {
+                    self.enforce_limit();
+                    break;
+                }
+            }
+
+            let Some(header_end) = find(&self.buffer, b"\r\n\r\n") else {
+                self.enforce_limit();
+                break;
+            };
+            let total = header_end + 4 + content_length(&self.buffer[..header_end]);
+            if self.buffer.len() < total {
+                self.enforce_limit();
+                break;
+            }
+            messages.push(StreamMessage {
+                data: self.buffer.drain(..total).collect(),
+                transport: "tcp",
+            });
+        }
+
+        messages
+    }
+
+    /// Tampon sınırı aşıldığında SIP akışı bir sonraki start-line'dan yeniden eşlenir;
+    /// el sıkışma ve WebSocket akışları ise bozuk işaretlenir.
+    fn enforce_limit(&mut self) {
+        if self.buffer.len() > MAX_FLOW_BUFFER {
+            self.buffer.clear();
+            if !matches!(self.framing, Framing::Sip) {
+                self.mark_broken();
+            }
+        }
+    }
+
+    fn mark_broken(&mut self) {
+        self.buffer.clear();
+        self.pending.clear();
+        self.framing = Framing::Broken;
+    }
+}
+
+/// İlk SIP start-line'ının (request veya status line) başladığı ofset.
+/// `Via: SIP/2.0/TCP` gibi başlık satırları eşleşmez.
+fn find_start_line(buf: &[u8]) -> Option<usize> {
+    let mut line_start = 0;
+    while let Some(rel) = buf[line_start..].iter().position(|b| *b == b'\n') {
+        let line_end = line_start + rel;
+        let line = buf[line_start..line_end]
+            .strip_suffix(b"\r")
+            .unwrap_or(&buf[line_start..line_end]);
+        if line.starts_with(b"SIP/2.0 ") || line.ends_with(b" SIP/2.0") {
+            return Some(line_start);
+        }
+        line_start = line_end + 1;
+    }
+    None
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Başlık bloğundan `Content-Length` (kompakt form `l`) değeri. Yoksa 0.
+fn content_length(headers: &[u8]) -> usize {
+    String::from_utf8_lossy(headers)
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| {
+            let name = name.trim();
+            name.eq_ignore_ascii_case("content-length") || name.eq_ignore_ascii_case("l")
+        })
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ws::tests::frame;
+    use super::*;
+
+    fn key() -> FlowKey {
+        FlowKey {
+            src_ip: IpAddr::from([10, 0, 0, 1]),
+            src_port: 40000,
+            dst_ip: IpAddr::from([10, 0, 0, 2]),
+            dst_port: 5060,
+        }
+    }
+
+    fn texts(messages: Vec<StreamMessage>) -> Vec<String> {
+        messages
+            .into_iter()
+            .map(|m| String::from_utf8_lossy(&m.data).into_owned())
+            .collect()
+    }
+
+    const OPTIONS: &str = "OPTIONS sip:a SIP/2.0\r\nl: 4\r\n\r\nbody";
+
+    #[test]
+    fn splits_stream_by_content_length() {
+        let mut tcp = TcpReassembler::new();
+        let stream = format!("{}\r\n\r\n{}", OPTIONS, OPTIONS);
+        // İlk segment tam start-line içermeli; akış ancak o zaman izlenir.
+        let (first, rest) = stream.as_bytes().split_at(30);
+
+        assert!(tcp.push(key(), 1000, 0, first, 0).is_empty());
+        let messages = texts(tcp.push(key(), 1030, 0, rest, 0));
+        assert_eq!(messages, vec![OPTIONS, OPTIONS]);
+        assert!(tcp.is_tracking(&key()));
+    }
+
+    #[test]
+    fn reorders_segments_and_ignores_retransmissions() {
+        let mut tcp = TcpReassembler::new();
+        let (first, rest) = OPTIONS.as_bytes().split_at(23);
+
+        assert!(tcp.push(key(), 1000, 0, first, 0).is_empty());
+        // Önce boşluktan sonraki segment, ardından yeniden iletilen ilk segment gelir.
+        assert!(tcp.push(key(), 1023 + 5, 0, &rest[5..], 0).is_empty());
+        assert!(tcp.push(key(), 1000, 0, first, 0).is_empty());
+        let messages = texts(tcp.push(key(), 1023, 0, &rest[..5], 0));
+        assert_eq!(messages, vec![OPTIONS]);
+    }
+
+    #[test]
+    fn untracked_flow_needs_start_line_and_rst_drops_it() {
+        let mut tcp = TcpReassembler::new();
+        assert!(tcp.push(key(), 1, 0, b"random bytes\r\n", 0).is_empty());
+        assert!(!tcp.is_tracking(&key()));
+
+        assert!(tcp
+            .push(key(), 1, 0, b"INVITE sip:a SIP/2.0\r\n", 0)
+            .is_empty());
+        assert!(tcp.is_tracking(&key()));
+        tcp.push(key(), 0, TCP_RST, b"", 0);
+        assert!(!tcp.is_tracking(&key()));
+    }
+
+    #[test]
+    fn decodes_websocket_after_upgrade() {
+        let mut tcp = TcpReassembler::new();
+        let mut stream =
+            b"GET /ws HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\n".to_vec();
+        stream.extend(frame(0x81, OPTIONS.as_bytes(), Some([9, 8, 7, 6])));
+
+        let messages = tcp.push(key(), 1, 0, &stream, 0);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].transport, "ws");
+        assert_eq!(messages[0].data, OPTIONS.as_bytes());
+    }
+
+    #[test]
+    fn oversized_websocket_frame_breaks_flow() {
+        let mut tcp = TcpReassembler::new();
+        let mut stream = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n".to_vec();
+        stream.extend([0x82, 127]);
+        stream.extend_from_slice(&(MAX_FLOW_BUFFER as u64 * 4).to_be_bytes());
+        let mut seq = 1u32;
+        assert!(tcp.push(key(), seq, 0, &stream, 0).is_empty());
+        seq += stream.len() as u32;
+
+        // Sonraki geçerli görünen çerçeveler de artık çözülmez.
+        let next = frame(0x81, OPTIONS.as_bytes(), None);
+        assert!(tcp.push(key(), seq, 0, &next, 0).is_empty());
+        assert!(tcp.is_tracking(&key()));
+    }
+
+    #[test]
+    fn content_length_and_start_line_helpers() {
+        assert_eq!(content_length(b"Via: x\r\nContent-Length: 12"), 12);
+        assert_eq!(content_length(b"Via: x"), 0);
+        assert_eq!(find_start_line(b"junk\r\nSIP/2.0 200 OK\r\n"), Some(6));
+        assert_eq!(find_start_line(b"Via: SIP/2.0/TCP host\r\n"), None);
+    }
+}
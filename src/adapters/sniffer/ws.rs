@@ -0,0 +1,275 @@
+// src/adapters/sniffer/ws.rs
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+/// permessage-deflate (RFC 7692) ile sıkıştırılmış mesaj işareti.
+const RSV1: u8 = 0x40;
+
+/// Tek bir WebSocket mesajı için birleştirilebilecek en fazla bayt.
+const MAX_MESSAGE_LEN: usize = 256 * 1024;
+
+/// Akışın başındaki HTTP/1.1 upgrade el sıkışması (istemcinin `GET` isteği veya
+/// sunucunun `101` yanıtı) olabilir mi. Kesin karar başlık bloğu tamamlanınca verilir.
+pub(super) fn looks_like_handshake(payload: &[u8]) -> bool {
+    (payload.starts_with(b"GET ") || payload.starts_with(b"HTTP/1.1 101"))
+        && payload
+            .windows(b"websocket".len())
+            .any(|w| w.eq_ignore_ascii_case(b"websocket"))
+}
+
+/// Tamamlanmış başlık bloğu bir WebSocket upgrade isteği / 101 yanıtı mı.
+pub(super) fn is_upgrade(headers: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(headers);
+    let mut lines = text.lines();
+    let start_ok = lines
+        .next()
+        .is_some_and(|line| line.starts_with("GET ") || line.starts_with("HTTP/1.1 101"));
+    start_ok
+        && lines
+            .filter_map(|line| line.split_once(':'))
+            .any(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("upgrade")
+                    && value.trim().eq_ignore_ascii_case("websocket")
+            })
+}
+
+/// RFC 6455 çerçeve çözücü. İstemci maskesi kaldırılır, parçalı (FIN=0) mesajlar
+/// birleştirilir; araya giren kontrol çerçeveleri (ping/pong/close) atlanır.
+/// RFC 7118 gereği her text/binary mesaj tam olarak bir SIP mesajı taşır.
+#[derive(Default)]
+pub(super) struct WsDecoder {
+    message: Vec<u8>,
+    in_message: bool,
+    compressed: bool,
+    overflowed: bool,
+}
+
+impl WsDecoder {
+    /// Tampondaki tam çerçeveleri tüketir ve tamamlanan mesajları döner.
+    /// Eksik kalan çerçeve bir sonraki segmente kadar tamponda bekler. Bildirilen uzunluğu
+    /// sınırı aşan bir çerçevede çözme durur ve `overflowed()` true döner.
+    pub fn drain(&mut self, buffer: &mut Vec<u8>) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+
+        loop {
+            let frame = match Frame::parse(buffer) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(FrameTooLarge) => {
+                    self.reset();
+                    self.overflowed = true;
+                    buffer.clear();
+                    break;
+                }
+            };
+            let mut payload = buffer[frame.payload_start..frame.end].to_vec();
+            buffer.drain(..frame.end);
+            if let Some(key) = frame.mask {
+                for (i, byte) in payload.iter_mut().enumerate() {
+                    *byte ^= key[i % 4];
+                }
+            }
+
+            match frame.opcode {
+                OPCODE_TEXT | OPCODE_BINARY => {
+                    self.message = payload;
+                    self.in_message = true;
+                    self.compressed = frame.rsv & RSV1 != 0;
+                }
+                OPCODE_CONTINUATION if self.in_message => {
+                    self.message.extend_from_slice(&payload);
+                }
+                // Kontrol çerçeveleri ve yetim continuation çerçeveleri
+                _ => continue,
+            }
+
+            if self.message.len() > MAX_MESSAGE_LEN {
+                self.reset();
+                continue;
+            }
+            if frame.fin {
+                // Sıkıştırılmış mesajlar (permessage-deflate) çözülmez.
+                if !self.compressed {
+                    messages.push(std::mem::take(&mut self.message));
+                }
+                self.reset();
+            }
+        }
+
+        messages
+    }
+
+    /// Sınırı aşan bir çerçeve görüldü; akışta çerçeve sınırı artık bilinmiyor.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    fn reset(&mut self) {
+        self.message.clear();
+        self.in_message = false;
+        self.compressed = false;
+    }
+}
+
+struct Frame {
+    fin: bool,
+    rsv: u8,
+    opcode: u8,
+    mask: Option<[u8; 4]>,
+    payload_start: usize,
+    end: usize,
+}
+
+/// Çerçevenin bildirdiği yük uzunluğu `MAX_MESSAGE_LEN`'i aşıyor.
+#[derive(Debug, PartialEq, Eq)]
+struct FrameTooLarge;
+
+impl Frame {
+    /// Tamponun başındaki çerçevenin başlığı; çerçeve henüz tamamlanmadıysa `Ok(None)`.
+    /// Uzunluk alanı okunur okunmaz sınır denetlenir; büyük çerçeve tamponda beklenmez.
+    fn parse(buf: &[u8]) -> Result<Option<Self>, FrameTooLarge> {
+        if buf.len() < 2 {
+            return Ok(None);
+        }
+        let masked = buf[1] & 0x80 != 0;
+        let (len, mut offset) = match buf[1] & 0x7F {
+            126 => {
+                if buf.len() < 4 {
+                    return Ok(None);
+                }
+                (u16::from_be_bytes([buf[2], buf[3]]) as u64, 4)
+            }
+            127 => {
+                let Some(bytes) = buf.get(2..10) else {
+                    return Ok(None);
+                };
+                let mut len = [0u8; 8];
+                len.copy_from_slice(bytes);
+                (u64::from_be_bytes(len), 10)
+            }
+            len => (len as u64, 2),
+        };
+        if len > MAX_MESSAGE_LEN as u64 {
+            return Err(FrameTooLarge);
+        }
+        // Sınır denetiminden sonra uzunluk usize'a sığar.
+        let len = len as usize;
+
+        let mask = if masked {
+            let Some(bytes) = buf.get(offset..offset + 4) else {
+                return Ok(None);
+            };
+            let mut key = [0u8; 4];
+            key.copy_from_slice(bytes);
+            offset += 4;
+            Some(key)
+        } else {
+            None
+        };
+
+        let end = offset + len;
+        if buf.len() < end {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            fin: buf[0] & 0x80 != 0,
+            rsv: buf[0] & 0x70,
+            opcode: buf[0] & 0x0F,
+            mask,
+            payload_start: offset,
+            end,
+        }))
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// İstemci çerçevesi; `mask` verilirse yük maskelenir.
+    pub(in crate::adapters::sniffer) fn frame(
+        first: u8,
+        payload: &[u8],
+        mask: Option<[u8; 4]>,
+    ) -> Vec<u8> {
+        let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+        let mut data = vec![first];
+        match payload.len() {
+            len if len < 126 => data.push(mask_bit | len as u8),
+            len if len <= u16::MAX as usize => {
+                data.push(mask_bit | 126);
+                data.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            len => {
+                data.push(mask_bit | 127);
+                data.extend_from_slice(&(len as u64).to_be_bytes());
+            }
+        }
+        match mask {
+            Some(key) => {
+                data.extend_from_slice(&key);
+                data.extend(payload.iter().enumerate().map(|(i, b)| b ^ key[i % 4]));
+            }
+            None => data.extend_from_slice(payload),
+        }
+        data
+    }
+
+    #[test]
+    fn unmasks_and_joins_fragmented_messages() {
+        let mut buffer = frame(OPCODE_TEXT, b"OPTIONS sip:a ", Some([1, 2, 3, 4]));
+        buffer.extend(frame(0x80 | 0x9, b"ping", None));
+        buffer.extend(frame(0x80 | OPCODE_CONTINUATION, b"SIP/2.0\r\n\r\n", None));
+        buffer.extend(frame(0x80 | OPCODE_BINARY, b"tail", None));
+        let partial = buffer.len() - 2;
+        let mut head = buffer[..partial].to_vec();
+
+        let mut decoder = WsDecoder::default();
+        let messages = decoder.drain(&mut head);
+        assert_eq!(messages, vec![b"OPTIONS sip:a SIP/2.0\r\n\r\n".to_vec()]);
+        // Eksik çerçeve tamponda bekler.
+        assert_eq!(head.len(), 4);
+
+        head.extend_from_slice(&buffer[partial..]);
+        assert_eq!(decoder.drain(&mut head), vec![b"tail".to_vec()]);
+        assert!(head.is_empty());
+        assert!(!decoder.overflowed());
+    }
+
+    #[test]
+    fn skips_compressed_and_orphan_continuation() {
+        let mut buffer = frame(0x80 | RSV1 | OPCODE_TEXT, b"deflated", None);
+        buffer.extend(frame(0x80 | OPCODE_CONTINUATION, b"orphan", None));
+        let mut decoder = WsDecoder::default();
+        assert!(decoder.drain(&mut buffer).is_empty());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn rejects_oversized_frame_from_header() {
+        // Yalnızca başlık gelmiş olsa da bildirilen uzunluk sınırı aşıyor.
+        let mut buffer = vec![0x80 | OPCODE_BINARY, 127];
+        buffer.extend_from_slice(&((MAX_MESSAGE_LEN as u64) + 1).to_be_bytes());
+        assert!(matches!(Frame::parse(&buffer), Err(FrameTooLarge)));
+
+        let mut decoder = WsDecoder::default();
+        assert!(decoder.drain(&mut buffer).is_empty());
+        assert!(decoder.overflowed());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn recognizes_upgrade_handshake() {
+        let request = b"GET /ws HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\nConnection: Upgrade";
+        assert!(looks_like_handshake(request));
+        assert!(is_upgrade(request));
+        assert!(is_upgrade(
+            b"HTTP/1.1 101 Switching Protocols\r\nupgrade:  WebSocket"
+        ));
+        assert!(!is_upgrade(
+            b"HTTP/1.1 401 Unauthorized\r\nUpgrade: websocket"
+        ));
+        assert!(!looks_like_handshake(b"INVITE sip:a SIP/2.0\r\n"));
+    }
+}
@@ -0,0 +1,99 @@
+// src/adapters/syslog/framing.rs
+
+/// Tek bir syslog mesajı için kabul edilen en fazla bayt.
+pub(super) const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+/// RFC 6587 TCP çerçeveleme. Her mesaj kendi başına değerlendirilir: rakamla başlıyorsa
+/// octet-counting (`UZUNLUK SP MESAJ`), `<` ile başlıyorsa satır sonuna kadar (non-transparent).
+/// Sınırı aşan mesajda `Err` döner; bağlantı kapatılmalıdır.
+pub(super) fn drain(buffer: &mut Vec<u8>) -> Result<Vec<Vec<u8>>, usize> {
+    let mut messages = Vec::new();
+    let mut start = 0;
+
+    loop {
+        // Çerçeveler arasındaki boş satırlar atlanır.
+        while buffer
+            .get(start)
+            .is_some_and(|b| matches!(b, b'\n' | b'\r' | b' ' | b'\0'))
+        {
+            start += 1;
+        }
+        let Some(&first) = buffer.get(start) else {
+            break;
+        };
+
+        if first.is_ascii_digit() {
+            let digits = buffer[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            let Some(&separator) = buffer.get(start + digits) else {
+                if digits > 6 {
+                    return Err(digits);
+                }
+                break;
+            };
+            let len: usize = std::str::from_utf8(&buffer[start..start + digits])
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(usize::MAX);
+            if separator != b' ' || len > MAX_MESSAGE_LEN {
+                return Err(len);
+            }
+            let body_start = start + digits + 1;
+            if buffer.len() < body_start + len {
+                break;
+            }
+            messages.push(buffer[body_start..body_start + len].to_vec());
+            start = body_start + len;
+        } else {
+            let Some(end) = buffer[start..].iter().position(|b| *b == b'\n') else {
+                if buffer.len() - start > MAX_MESSAGE_LEN {
+                    return Err(buffer.len() - start);
+                }
+                break;
+            };
+            messages.push(buffer[start..start + end].to_vec());
+            start += end + 1;
+        }
+    }
+
+    buffer.drain(..start);
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_octet_counted_and_newline_frames() {
+        let mut buffer = b"11 <13>1 - - x\n<14>line two\r\n\n6 <13>a".to_vec();
+        let messages = drain(&mut buffer).expect("çerçeve hatası");
+        assert_eq!(
+            messages,
+            vec![b"<13>1 - - x".to_vec(), b"<14>line two\r".to_vec()]
+        );
+        // Eksik octet-counted çerçeve tamponda bekler.
+        assert_eq!(buffer, b"6 <13>a");
+
+        buffer.extend_from_slice(b"b");
+        assert_eq!(drain(&mut buffer), Ok(vec![b"<13>ab".to_vec()]));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn rejects_oversized_or_malformed_frames() {
+        let mut too_long = format!("{} <13>", MAX_MESSAGE_LEN + 1).into_bytes();
+        assert_eq!(drain(&mut too_long), Err(MAX_MESSAGE_LEN + 1));
+
+        let mut bad_separator = b"12x<13>".to_vec();
+        assert!(drain(&mut bad_separator).is_err());
+
+        let mut endless_digits = b"12345678".to_vec();
+        assert_eq!(drain(&mut endless_digits), Err(8));
+
+        let mut unterminated = vec![b'<'; MAX_MESSAGE_LEN + 1];
+        assert!(drain(&mut unterminated).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+// src/adapters/syslog/mod.rs
+mod framing;
+mod parse;
+
+use crate::core::domain::{LogRecord, ResourceContext};
+use crate::ports::LogIngestor;
+use crate::utils::parser;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::mpsc::Sender;
+use tokio::sync::Semaphore;
+use tracing::{error, info, warn};
+
+/// Aynı anda açık tutulabilecek en fazla TCP syslog bağlantısı.
+const MAX_TCP_CONNECTIONS: usize = 512;
+
+/// Structured data'da Call-ID taşıyabilecek parametre adları.
+const CALL_ID_PARAMS: [&str; 4] = ["callid", "call_id", "call-id", "ci"];
+
+/// Kamailio, FreeSWITCH ve SBC cihazlarının syslog çıktısını (RFC 5424 / RFC 3164)
+/// UDP ve TCP üzerinden alıp ortak ingest kanalına basan ingestor.
+#[derive(Clone)]
+pub struct SyslogIngestor {
+    udp_addr: Option<SocketAddr>,
+    tcp_addr: Option<SocketAddr>,
+    tx: Sender<LogRecord>,
+    node_name: String,
+    tenant_id: String, // [ARCH-COMPLIANCE]
+}
+
+impl SyslogIngestor {
+    pub fn new(
+        udp_addr: Option<SocketAddr>,
+        tcp_addr: Option<SocketAddr>,
+        tx: Sender<LogRecord>,
+        node_name: String,
+        tenant_id: String,
+    ) -> Self {
+        Self {
+            udp_addr,
+            tcp_addr,
+            tx,
+            node_name,
+            tenant_id,
+        }
+    }
+
+    fn to_record(&self, raw: &[u8], peer: SocketAddr, transport: &'static str) -> LogRecord {
+        let observed_at = Utc::now();
+        let text = String::from_utf8_lossy(raw);
+        let msg = parse::parse(&text, observed_at);
+
+        let mut attributes: HashMap<String, Value> = HashMap::new();
+        let mut put = |key: &str, value: &str| {
+            attributes.insert(key.to_string(), Value::String(value.to_string()));
+        };
+        put("syslog.format", msg.format.as_str());
+        put("syslog.facility", msg.facility_name());
+        put("syslog.severity", msg.severity_name());
+        for (key, value) in [
+            ("syslog.hostname", &msg.hostname),
+            ("syslog.appname", &msg.app_name),
+            ("syslog.procid", &msg.proc_id),
+            ("syslog.msgid", &msg.msg_id),
+        ] {
+            if let Some(value) = value {
+                put(key, value);
+            }
+        }
+        for (key, value) in parse::structured_data_attributes(&msg) {
+            put(&key, &value);
+        }
+        put("net.peer.ip", &peer.ip().to_string());
+        put("net.transport", transport);
+        put("observed_ts", &observed_at.to_rfc3339());
+
+        // SIP çekirdek logları diğer kayıtlarla Call-ID üzerinden birleşsin.
+        let call_id = msg
+            .structured_data
+            .iter()
+            .flat_map(|(_, params)| params)
+            .find(|(name, value)| {
+                !value.is_empty() && CALL_ID_PARAMS.contains(&name.to_lowercase().as_str())
+            })
+            .map(|(_, value)| value.clone())
+            .or_else(|| parser::extract_call_id(&msg.message));
+        if let Some(call_id) = call_id {
+            attributes.insert("sip.call_id".to_string(), Value::String(call_id));
+        }
+
+        let mut record = LogRecord {
+            schema_v: "1.0.0".to_string(),
+            ts: msg.timestamp.unwrap_or(observed_at).to_rfc3339(),
+            severity: msg.log_level().to_string(),
+            tenant_id: self.tenant_id.clone(),
+            resource: ResourceContext {
+                service_name: msg.app_name.clone().unwrap_or_else(|| "syslog".to_string()),
+                service_version: "unknown".to_string(),
+                service_env: "production".to_string(),
+                host_name: Some(
+                    msg.hostname
+                        .clone()
+                        .unwrap_or_else(|| peer.ip().to_string()),
+                ),
+            },
+            trace_id: None,
+            span_id: None,
+            event: "SYSLOG_MESSAGE".to_string(),
+            message: parser::clean_ansi(&msg.message),
+            attributes,
+            smart_tags: vec!["SYSLOG".to_string()],
+            _idx: 0.0,
+        };
+        record.sanitize_and_enrich();
+        record
+    }
+
+    /// Her datagram tek bir syslog mesajıdır.
+    async fn run_udp(self, socket: UdpSocket) {
+        let mut buf = vec![0u8; 65536];
+        loop {
+            let (len, peer) = match socket.recv_from(&mut buf).await {
+                Ok(received) => received,
+                Err(e) => {
+                    warn!(event="SYSLOG_UDP_ERR", error=%e, "Syslog UDP okuma hatası");
+                    continue;
+                }
+            };
+            if buf[..len].iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let record = self.to_record(&buf[..len], peer, "udp");
+            if self.tx.send(record).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    async fn run_tcp(self, listener: TcpListener) {
+        let slots = Arc::new(Semaphore::new(MAX_TCP_CONNECTIONS));
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!(event="SYSLOG_TCP_ERR", error=%e, "Syslog TCP bağlantısı kabul edilemedi");
+                    continue;
+                }
+            };
+            let Ok(permit) = slots.clone().try_acquire_owned() else {
+                warn!(event="SYSLOG_TCP_LIMIT", peer=%peer, limit=MAX_TCP_CONNECTIONS, "⚠️ Syslog TCP bağlantı sınırı aşıldı, bağlantı reddedildi");
+                continue;
+            };
+            let ingestor = self.clone();
+            tokio::spawn(async move {
+                ingestor.handle_connection(stream, peer).await;
+                drop(permit);
+            });
+        }
+    }
+
+    async fn handle_connection(self, mut stream: TcpStream, peer: SocketAddr) {
+        let mut buffer = Vec::new();
+        let mut chunk = vec![0u8; 16 * 1024];
+        loop {
+            let n = match stream.read(&mut chunk).await {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) => {
+                    warn!(event="SYSLOG_TCP_ERR", peer=%peer, error=%e, "Syslog TCP okuma hatası");
+                    break;
+                }
+            };
+            buffer.extend_from_slice(&chunk[..n]);
+            let messages = match framing::drain(&mut buffer) {
+                Ok(messages) => messages,
+                Err(len) => {
+                    warn!(event="SYSLOG_FRAME_INVALID", peer=%peer, len=len, max=framing::MAX_MESSAGE_LEN, "⚠️ Geçersiz syslog çerçevesi, bağlantı kapatılıyor");
+                    return;
+                }
+            };
+            for message in messages {
+                if self
+                    .tx
+                    .send(self.to_record(&message, peer, "tcp"))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+        // Son satır sonu gelmeden kapanan bağlantıdaki mesaj
+        if !buffer.iter().all(u8::is_ascii_whitespace) {
+            let _ = self.tx.send(self.to_record(&buffer, peer, "tcp")).await;
+        }
+    }
+}
+
+#[async_trait]
+impl LogIngestor for SyslogIngestor {
+    async fn start(&self) -> Result<()> {
+        let mut tasks = Vec::new();
+
+        if let Some(addr) = self.udp_addr {
+            let socket = UdpSocket::bind(addr)
+                .await
+                .with_context(|| format!("Syslog UDP Bind Error: {}", addr))?;
+            info!(event="SYSLOG_LISTENING", transport="udp", addr=%addr, node=%self.node_name, "📨 Syslog UDP dinleniyor");
+            tasks.push(tokio::spawn(self.clone().run_udp(socket)));
+        }
+        if let Some(addr) = self.tcp_addr {
+            let listener = TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("Syslog TCP Bind Error: {}", addr))?;
+            info!(event="SYSLOG_LISTENING", transport="tcp", addr=%addr, node=%self.node_name, "📨 Syslog TCP dinleniyor");
+            tasks.push(tokio::spawn(self.clone().run_tcp(listener)));
+        }
+
+        for task in tasks {
+            if let Err(e) = task.await {
+                error!(event="SYSLOG_TASK_ERR", error=%e, "Syslog dinleyici görevi sonlandı");
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,392 @@
+// src/adapters/syslog/parse.rs
+use chrono::{DateTime, Datelike, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFormat {
+    Rfc5424,
+    Rfc3164,
+}
+
+impl SyslogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyslogFormat::Rfc5424 => "rfc5424",
+            SyslogFormat::Rfc3164 => "rfc3164",
+        }
+    }
+}
+
+/// Çözülmüş syslog mesajı. Eksik (`-`) alanlar `None`'dır.
+#[derive(Debug)]
+pub struct SyslogMessage {
+    pub format: SyslogFormat,
+    pub facility: u8,
+    pub severity: u8,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub hostname: Option<String>,
+    pub app_name: Option<String>,
+    pub proc_id: Option<String>,
+    pub msg_id: Option<String>,
+    /// `[id param="değer"]` blokları: SD-ID -> parametreler
+    pub structured_data: Vec<(String, Vec<(String, String)>)>,
+    pub message: String,
+}
+
+impl SyslogMessage {
+    pub fn facility_name(&self) -> &'static str {
+        FACILITIES
+            .get(self.facility as usize)
+            .copied()
+            .unwrap_or("unknown")
+    }
+
+    pub fn severity_name(&self) -> &'static str {
+        SEVERITIES
+            .get(self.severity as usize)
+            .copied()
+            .unwrap_or("unknown")
+    }
+
+    /// Syslog önem derecesinin `LogRecord.severity` karşılığı.
+    pub fn log_level(&self) -> &'static str {
+        match self.severity {
+            0..=2 => "FATAL",
+            3 => "ERROR",
+            4 => "WARN",
+            5 | 6 => "INFO",
+            _ => "DEBUG",
+        }
+    }
+}
+
+/// RFC 5424 (`<PRI>1 ...`) veya RFC 3164 (`<PRI>Mmm dd hh:mm:ss ...`) mesajını çözer.
+/// PRI yoksa mesajın tamamı `user.notice` gövdesi sayılır; çözüm hiçbir zaman başarısız olmaz.
+pub fn parse(raw: &str, now: DateTime<Utc>) -> SyslogMessage {
+    let raw = raw.trim_end_matches(['\r', '\n', '\0']);
+    let (pri, rest) = split_pri(raw).unwrap_or((13, raw));
+
+    if let Some(body) = rest.strip_prefix("1 ") {
+        if let Some(message) = parse_5424(pri, body) {
+            return message;
+        }
+    }
+    parse_3164(pri, rest, now)
+}
+
+fn split_pri(raw: &str) -> Option<(u8, &str)> {
+    let inner = raw.strip_prefix('<')?;
+    let end = inner.find('>')?;
+    if end == 0 || end > 3 {
+        return None;
+    }
+    let pri: u8 = inner[..end].parse().ok()?;
+    (pri <= 191).then_some((pri, &inner[end + 1..]))
+}
+
+fn nil(field: &str) -> Option<String> {
+    (field != "-" && !field.is_empty()).then(|| field.to_string())
+}
+
+/// `TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA [MSG]`
+fn parse_5424(pri: u8, body: &str) -> Option<SyslogMessage> {
+    let mut fields = body.splitn(6, ' ');
+    let timestamp = fields.next()?;
+    let hostname = fields.next()?;
+    let app_name = fields.next()?;
+    let proc_id = fields.next()?;
+    let msg_id = fields.next()?;
+    let rest = fields.next().unwrap_or("-");
+
+    let (structured_data, message) = if let Some(after) = rest.strip_prefix('-') {
+        (Vec::new(), after)
+    } else {
+        parse_structured_data(rest)?
+    };
+    let message = message.strip_prefix(' ').unwrap_or(message);
+    // RFC 5424 UTF-8 mesajlarının başındaki BOM
+    let message = message.strip_prefix('\u{feff}').unwrap_or(message);
+
+    Some(SyslogMessage {
+        format: SyslogFormat::Rfc5424,
+        facility: pri / 8,
+        severity: pri % 8,
+        timestamp: DateTime::parse_from_rfc3339(timestamp)
+            .ok()
+            .map(|ts| ts.with_timezone(&Utc)),
+        hostname: nil(hostname),
+        app_name: nil(app_name),
+        proc_id: nil(proc_id),
+        msg_id: nil(msg_id),
+        structured_data,
+        message: message.to_string(),
+    })
+}
+
+/// Ardışık `[id k="v" ...]` bloklarını çözer ve kalan mesajı döner.
+/// Parametre değerlerinde `\"`, `\\` ve `\]` kaçışları desteklenir.
+#[allow(clippy::type_complexity)]
+fn parse_structured_data(input: &str) -> Option<(Vec<(String, Vec<(String, String)>)>, &str)> {
+    let mut elements = Vec::new();
+    let mut rest = input;
+
+    while let Some(block) = rest.strip_prefix('[') {
+        let id_end = block.find([' ', ']'])?;
+        let id = block[..id_end].to_string();
+        let mut cursor = &block[id_end..];
+        let mut params = Vec::new();
+
+        loop {
+            cursor = cursor.trim_start_matches(' ');
+            if let Some(after) = cursor.strip_prefix(']') {
+                cursor = after;
+                break;
+            }
+            let eq = cursor.find('=')?;
+            let name = cursor[..eq].to_string();
+            let value_part = cursor[eq + 1..].strip_prefix('"')?;
+
+            let mut value = String::new();
+            let mut chars = value_part.char_indices();
+            let mut end = None;
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => match chars.next() {
+                        Some((_, escaped @ ('"' | '\\' | ']'))) => value.push(escaped),
+                        Some((_, other)) => {
+                            value.push('\\');
+                            value.push(other);
+                        }
+                        None => return None,
+                    },
+                    '"' => {
+                        end = Some(i);
+                        break;
+                    }
+                    c => value.push(c),
+                }
+            }
+            cursor = &value_part[end? + 1..];
+            params.push((name, value));
+        }
+
+        elements.push((id, params));
+        rest = cursor;
+    }
+
+    Some((elements, rest))
+}
+
+/// `Mmm dd hh:mm:ss HOSTNAME TAG[PID]: MSG`. Yerel göndericiler HOSTNAME'i atlayabilir;
+/// zaman damgası yıl ve saat dilimi taşımadığından UTC ve içinde bulunulan yıl varsayılır.
+fn parse_3164(pri: u8, rest: &str, now: DateTime<Utc>) -> SyslogMessage {
+    let (timestamp, after_ts) = parse_3164_timestamp(rest, now);
+
+    let mut message = SyslogMessage {
+        format: SyslogFormat::Rfc3164,
+        facility: pri / 8,
+        severity: pri % 8,
+        timestamp,
+        hostname: None,
+        app_name: None,
+        proc_id: None,
+        msg_id: None,
+        structured_data: Vec::new(),
+        message: after_ts.to_string(),
+    };
+    if timestamp.is_none() {
+        return message;
+    }
+
+    let mut body = after_ts;
+    if let Some((first, remainder)) = body.split_once(' ') {
+        if !is_tag(first) {
+            message.hostname = nil(first);
+            body = remainder;
+        }
+    }
+
+    if let Some((tag, msg)) = body.split_once(' ').filter(|(tag, _)| is_tag(tag)) {
+        let tag = tag.trim_end_matches(':');
+        match tag.split_once('[') {
+            Some((app, pid)) => {
+                message.app_name = nil(app);
+                message.proc_id = nil(pid.trim_end_matches(']'));
+            }
+            None => message.app_name = nil(tag),
+        }
+        body = msg;
+    }
+    message.message = body.to_string();
+    message
+}
+
+/// `kamailio[1234]:` veya `sshd:` biçimindeki TAG alanı.
+fn is_tag(token: &str) -> bool {
+    token.ends_with(':') || (token.contains('[') && token.ends_with("]:"))
+}
+
+/// Klasik `Mmm dd hh:mm:ss` veya bazı göndericilerin kullandığı RFC 3339 zaman damgası.
+fn parse_3164_timestamp(rest: &str, now: DateTime<Utc>) -> (Option<DateTime<Utc>>, &str) {
+    if let Some((first, after)) = rest.split_once(' ') {
+        if let Ok(ts) = DateTime::parse_from_rfc3339(first) {
+            return (Some(ts.with_timezone(&Utc)), after);
+        }
+    }
+
+    let Some(candidate) = rest.get(..15) else {
+        return (None, rest);
+    };
+    let with_year = format!("{} {}", now.year(), candidate.replace("  ", " 0"));
+    let Ok(naive) = NaiveDateTime::parse_from_str(&with_year, "%Y %b %d %H:%M:%S") else {
+        return (None, rest);
+    };
+    let mut ts = Utc.from_utc_datetime(&naive);
+    // Yılbaşı civarında Aralık kayıtları Ocak'ta gelirse önceki yıla aittir.
+    if ts > now + chrono::Duration::days(1) {
+        if let Some(previous) = naive.with_year(now.year() - 1) {
+            ts = Utc.from_utc_datetime(&previous);
+        }
+    }
+    let after = rest[15..].strip_prefix(' ').unwrap_or(&rest[15..]);
+    (Some(ts), after)
+}
+
+/// Structured data bloklarını `syslog.sd.<id>.<param>` attribute anahtarlarına açar.
+pub fn structured_data_attributes(message: &SyslogMessage) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    for (id, params) in &message.structured_data {
+        if params.is_empty() {
+            attributes.insert(format!("syslog.sd.{}", id), String::new());
+        }
+        for (name, value) in params {
+            attributes.insert(format!("syslog.sd.{}.{}", id, name), value.clone());
+        }
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0)
+            .single()
+            .expect("geçersiz tarih")
+    }
+
+    #[test]
+    fn parses_rfc5424_with_structured_data() {
+        let raw = "<165>1 2024-01-02T10:00:00.123Z sbc1 kamailio 1234 ID47 \
+                   [exampleSDID@32473 iut=\"3\" eventSource=\"App\\\"lication\\]\"][origin] \u{feff}Call-ID: abc\r\n";
+        let msg = parse(raw, now());
+        assert_eq!(msg.format, SyslogFormat::Rfc5424);
+        assert_eq!(msg.facility_name(), "local4");
+        assert_eq!(msg.severity_name(), "notice");
+        assert_eq!(msg.log_level(), "INFO");
+        assert_eq!(msg.hostname.as_deref(), Some("sbc1"));
+        assert_eq!(msg.app_name.as_deref(), Some("kamailio"));
+        assert_eq!(msg.proc_id.as_deref(), Some("1234"));
+        assert_eq!(msg.msg_id.as_deref(), Some("ID47"));
+        assert_eq!(
+            msg.timestamp.map(|ts| ts.timestamp_millis()),
+            Some(1_704_189_600_123)
+        );
+        assert_eq!(msg.message, "Call-ID: abc");
+
+        let attributes = structured_data_attributes(&msg);
+        assert_eq!(attributes["syslog.sd.exampleSDID@32473.iut"], "3");
+        assert_eq!(
+            attributes["syslog.sd.exampleSDID@32473.eventSource"],
+            "App\"lication]"
+        );
+        assert_eq!(attributes["syslog.sd.origin"], "");
+    }
+
+    #[test]
+    fn parses_rfc5424_nil_fields() {
+        let msg = parse("<11>1 - - - - - -", now());
+        assert_eq!(msg.format, SyslogFormat::Rfc5424);
+        assert_eq!(msg.log_level(), "ERROR");
+        assert!(msg.timestamp.is_none());
+        assert!(msg.hostname.is_none() && msg.app_name.is_none());
+        assert!(msg.structured_data.is_empty());
+        assert_eq!(msg.message, "");
+    }
+
+    #[test]
+    fn parses_rfc3164_with_and_without_hostname() {
+        let msg = parse(
+            "<34>Jan  2 11:59:00 pbx asterisk[42]: NOTICE peer down",
+            now(),
+        );
+        assert_eq!(msg.format, SyslogFormat::Rfc3164);
+        assert_eq!(msg.facility_name(), "auth");
+        assert_eq!(msg.log_level(), "FATAL");
+        assert_eq!(
+            msg.timestamp,
+            Utc.with_ymd_and_hms(2024, 1, 2, 11, 59, 0).single()
+        );
+        assert_eq!(msg.hostname.as_deref(), Some("pbx"));
+        assert_eq!(msg.app_name.as_deref(), Some("asterisk"));
+        assert_eq!(msg.proc_id.as_deref(), Some("42"));
+        assert_eq!(msg.message, "NOTICE peer down");
+
+        let local = parse("<30>Jan  2 11:59:00 sshd: accepted", now());
+        assert!(local.hostname.is_none());
+        assert_eq!(local.app_name.as_deref(), Some("sshd"));
+        assert_eq!(local.message, "accepted");
+    }
+
+    #[test]
+    fn december_records_in_january_belong_to_previous_year() {
+        let msg = parse("<13>Dec 31 23:59:59 host app: late", now());
+        assert_eq!(
+            msg.timestamp,
+            Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).single()
+        );
+    }
+
+    #[test]
+    fn missing_or_invalid_pri_defaults_to_user_notice() {
+        let plain = parse("just text", now());
+        assert_eq!((plain.facility, plain.severity), (1, 5));
+        assert!(plain.timestamp.is_none());
+        assert_eq!(plain.message, "just text");
+
+        let invalid = parse("<999>text", now());
+        assert_eq!(invalid.facility_name(), "user");
+        assert_eq!(invalid.message, "<999>text");
+    }
+}
@@ -1,18 +1,188 @@
 // src/api/grpc.rs
-use crate::core::domain::LogRecord;
-use tokio::sync::mpsc;
-use tonic::{Request, Response, Status};
+use crate::core::aggregator::{CallSession, MediaStreamStats};
+use crate::core::domain::{LogRecord, ResourceContext};
+use crate::core::session_query::{
+    SessionFilter, SessionPage, SessionPageRequest, SessionQueryHandle, SessionSortKey,
+    SessionSummary, DEFAULT_PAGE_LIMIT,
+};
+use futures_util::Stream;
+use serde_json::Value;
+use std::pin::Pin;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+use tonic::{Request, Response, Status, Streaming};
 
+// Üretilen kodda prost'un oneof kalıpları
+#[allow(clippy::large_enum_variant, clippy::enum_variant_names)]
 pub mod observer_proto {
     tonic::include_proto!("sentiric.observer.v1");
 }
 
+use observer_proto::attribute_value::Kind;
+use observer_proto::ingest_log_request::Payload;
 use observer_proto::observer_service_server::ObserverService;
-use observer_proto::{IngestLogRequest, IngestLogResponse};
+use observer_proto::subscribe_event::Event;
+use observer_proto::{
+    AnomalyFilter, AttributeValue, GetSessionRequest, IngestBatchRequest, IngestLogRequest,
+    IngestLogResponse, IngestRejection, IngestSummary, ListSessionsRequest, PageRequest,
+    SearchSessionsRequest, SubscribeEvent, SubscribeRequest, SubscriptionLag,
+};
+
+/// Özet yanıtında ayrıntısı döndürülen en fazla ret kaydı.
+const MAX_REPORTED_REJECTIONS: usize = 100;
 
 pub struct GrpcServerState {
     pub tx: mpsc::Sender<LogRecord>,
+    /// Aggregator'dan geçmiş canlı kayıtlar (UI WebSocket ile aynı yayın kanalı)
+    pub live: broadcast::Sender<LogRecord>,
     pub tenant_id: String, //[ARCH-COMPLIANCE] Tenant ID Enjeksiyonu
+    /// Oturum sorguları aggregator görevine istek/yanıt kanalıyla iletilir
+    pub sessions: SessionQueryHandle,
+}
+
+impl GrpcServerState {
+    /// Uzak kaydı (tipli veya eski JSON biçimi) çözer ve uzak kaynak işaretlerini ekler.
+    fn decode(&self, req: IngestLogRequest) -> Result<LogRecord, String> {
+        let mut log = match req.payload {
+            Some(Payload::Record(record)) => LogRecord::try_from(record)?,
+            Some(Payload::RawJsonLog(raw)) => {
+                serde_json::from_str(&raw).map_err(|e| format!("json: {}", e))?
+            }
+            None => return Err("boş istek: record veya raw_json_log gerekli".to_string()),
+        };
+        mark_remote(&mut log);
+        Ok(log)
+    }
+
+    async fn search_sessions_page(
+        &self,
+        filter: SessionFilter,
+        page: Option<PageRequest>,
+    ) -> Result<Response<observer_proto::SessionPage>, Status> {
+        let page = page_request(page.unwrap_or_default()).map_err(Status::invalid_argument)?;
+        let result = self
+            .sessions
+            .search(filter, page)
+            .await
+            .ok_or_else(aggregator_unavailable)?;
+        Ok(Response::new(result.into()))
+    }
+}
+
+fn aggregator_unavailable() -> Status {
+    Status::unavailable("aggregator çalışmıyor")
+}
+
+fn page_request(page: PageRequest) -> Result<SessionPageRequest, String> {
+    let sort = if page.order_by.is_empty() {
+        SessionSortKey::default()
+    } else {
+        SessionSortKey::parse(&page.order_by)
+            .ok_or_else(|| format!("geçersiz order_by: {}", page.order_by))?
+    };
+    Ok(SessionPageRequest {
+        offset: page.offset as usize,
+        limit: match page.limit {
+            0 => DEFAULT_PAGE_LIMIT,
+            limit => limit as usize,
+        },
+        sort,
+        descending: !page.ascending,
+    })
+}
+
+/// Upstream'e geri gönderilmesin diye uzak kayıtlar `source=grpc` ile işaretlenir.
+fn mark_remote(log: &mut LogRecord) {
+    log.attributes.insert(
+        "source".to_string(),
+        serde_json::Value::String("grpc".to_string()),
+    );
+    log.smart_tags.push("GRPC".to_string());
+    log.smart_tags.push("REMOTE".to_string());
+}
+
+/// `Subscribe` filtresi; boş alanlar her kaydı kabul eder.
+struct SubscriptionFilter {
+    tenant_id: Option<String>,
+    services: Vec<String>,
+    severities: Vec<String>,
+    trace_id: Option<String>,
+    events: Vec<String>,
+    smart_tags: Vec<String>,
+}
+
+impl SubscriptionFilter {
+    fn new(req: SubscribeRequest) -> Self {
+        let upper = |values: Vec<String>| values.into_iter().map(|v| v.to_uppercase()).collect();
+        Self {
+            tenant_id: non_empty(req.tenant_id),
+            services: req.services,
+            severities: upper(req.severities),
+            trace_id: non_empty(req.trace_id),
+            events: req.events,
+            smart_tags: upper(req.smart_tags),
+        }
+    }
+
+    fn matches(&self, log: &LogRecord) -> bool {
+        self.tenant_id.as_ref().is_none_or(|t| *t == log.tenant_id)
+            && (self.services.is_empty() || self.services.contains(&log.resource.service_name))
+            && (self.severities.is_empty()
+                || self.severities.contains(&log.severity.to_uppercase()))
+            && self
+                .trace_id
+                .as_ref()
+                .is_none_or(|t| log.trace_id.as_ref() == Some(t))
+            && (self.events.is_empty() || self.events.contains(&log.event))
+            && (self.smart_tags.is_empty()
+                || log
+                    .smart_tags
+                    .iter()
+                    .any(|tag| self.smart_tags.contains(&tag.to_uppercase())))
+    }
+}
+
+/// Toplu / akış ingest'inde kayıt bazında kabul-ret sayacı.
+#[derive(Default)]
+struct IngestTally {
+    summary: IngestSummary,
+}
+
+impl IngestTally {
+    fn accept(&mut self) {
+        self.summary.accepted += 1;
+    }
+
+    fn reject(&mut self, index: u64, reason: String) {
+        self.summary.rejected += 1;
+        if self.summary.rejections.len() < MAX_REPORTED_REJECTIONS {
+            self.summary
+                .rejections
+                .push(IngestRejection { index, reason });
+        }
+    }
+
+    async fn ingest(&mut self, state: &GrpcServerState, index: u64, req: IngestLogRequest) {
+        let log = match state.decode(req) {
+            Ok(log) => log,
+            Err(reason) => {
+                self.reject(index, reason);
+                return;
+            }
+        };
+        // Kanal doluysa beklenir: istemci akışı doğal geri basınç görür.
+        match state.tx.send(log).await {
+            Ok(()) => self.accept(),
+            Err(_) => self.reject(index, "ingest channel closed".to_string()),
+        }
+    }
+
+    fn finish(self, rpc: &str) -> IngestSummary {
+        if self.summary.rejected > 0 {
+            tracing::warn!(event="GRPC_INGEST_REJECTED", rpc=%rpc, accepted=self.summary.accepted, rejected=self.summary.rejected, "⚠️ Uzak kayıtların bir kısmı reddedildi");
+        }
+        self.summary
+    }
 }
 
 #[tonic::async_trait]
@@ -23,26 +193,544 @@ impl ObserverService for GrpcServerState {
     ) -> Result<Response<IngestLogResponse>, Status> {
         let req = request.into_inner();
 
-        let mut log: LogRecord = match serde_json::from_str(&req.raw_json_log) {
+        let log = match self.decode(req) {
             Ok(rec) => rec,
             Err(e) => {
                 tracing::warn!(event="GRPC_PARSE_WARN", error=%e, "Failed to parse incoming gRPC log");
                 // [ARCH-COMPLIANCE] Hardcoded system iptal edildi, mevcut tenant verildi.
-                LogRecord::new_system("WARN", "GRPC_PARSE_ERROR", &e.to_string(), &self.tenant_id)
+                let mut log =
+                    LogRecord::new_system("WARN", "GRPC_PARSE_ERROR", &e, &self.tenant_id);
+                mark_remote(&mut log);
+                log
             }
         };
 
-        log.attributes.insert(
-            "source".to_string(),
-            serde_json::Value::String("grpc".to_string()),
-        );
-        log.smart_tags.push("GRPC".to_string());
-        log.smart_tags.push("REMOTE".to_string());
-
         if let Err(e) = self.tx.send(log).await {
             tracing::error!(event="GRPC_CHANNEL_FULL", error=%e, "gRPC Ingest Error (Channel Closed/Full)");
         }
 
         Ok(Response::new(IngestLogResponse { success: true }))
     }
+
+    async fn ingest_batch(
+        &self,
+        request: Request<IngestBatchRequest>,
+    ) -> Result<Response<IngestSummary>, Status> {
+        let mut tally = IngestTally::default();
+        for (index, req) in request.into_inner().logs.into_iter().enumerate() {
+            tally.ingest(self, index as u64, req).await;
+        }
+        Ok(Response::new(tally.finish("IngestBatch")))
+    }
+
+    async fn ingest_stream(
+        &self,
+        request: Request<Streaming<IngestLogRequest>>,
+    ) -> Result<Response<IngestSummary>, Status> {
+        let mut stream = request.into_inner();
+        let mut tally = IngestTally::default();
+        let mut index = 0;
+        while let Some(req) = stream.message().await? {
+            tally.ingest(self, index, req).await;
+            index += 1;
+        }
+        Ok(Response::new(tally.finish("IngestStream")))
+    }
+
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<SubscribeEvent, Status>> + Send>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let peer = request
+            .remote_addr()
+            .map(|a| a.to_string())
+            .unwrap_or_default();
+        let filter = SubscriptionFilter::new(request.into_inner());
+        let rx = self.live.subscribe();
+        tracing::info!(event="GRPC_SUBSCRIBE", peer=%peer, "📡 Canlı akış aboneliği açıldı");
+
+        // İstemci bağlantıyı kapatınca akış ve yayın alıcısı birlikte düşer.
+        let stream = futures_util::stream::unfold((rx, filter), |(mut rx, filter)| async move {
+            loop {
+                let event = match rx.recv().await {
+                    Ok(log) if filter.matches(&log) => Event::Record((&log).into()),
+                    Ok(_) => continue,
+                    // Abone yayının gerisinde kaldı: atlanan kayıt sayısı bildirilir, akış sürer.
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            event = "GRPC_SUBSCRIBER_LAG",
+                            skipped = skipped,
+                            "⚠️ Canlı akış abonesi geride kaldı"
+                        );
+                        Event::Lag(SubscriptionLag { skipped })
+                    }
+                    Err(RecvError::Closed) => return None,
+                };
+                let item = Ok(SubscribeEvent { event: Some(event) });
+                return Some((item, (rx, filter)));
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn list_sessions(
+        &self,
+        request: Request<ListSessionsRequest>,
+    ) -> Result<Response<observer_proto::SessionPage>, Status> {
+        let req = request.into_inner();
+        self.search_sessions_page(SessionFilter::default(), req.page)
+            .await
+    }
+
+    async fn get_session(
+        &self,
+        request: Request<GetSessionRequest>,
+    ) -> Result<Response<observer_proto::CallSession>, Status> {
+        let session_id = request.into_inner().session_id;
+        if session_id.is_empty() {
+            return Err(Status::invalid_argument("session_id gerekli"));
+        }
+        match self.sessions.get(session_id.clone()).await {
+            Some(Some(session)) => Ok(Response::new((&session).into())),
+            Some(None) => Err(Status::not_found(format!(
+                "oturum bulunamadı: {}",
+                session_id
+            ))),
+            None => Err(aggregator_unavailable()),
+        }
+    }
+
+    async fn search_sessions(
+        &self,
+        request: Request<SearchSessionsRequest>,
+    ) -> Result<Response<observer_proto::SessionPage>, Status> {
+        let req = request.into_inner();
+        let has_anomalies = match AnomalyFilter::try_from(req.anomalies) {
+            Ok(AnomalyFilter::AnomalyAny) => None,
+            Ok(AnomalyFilter::AnomalyPresent) => Some(true),
+            Ok(AnomalyFilter::AnomalyAbsent) => Some(false),
+            Err(_) => return Err(Status::invalid_argument("geçersiz anomalies değeri")),
+        };
+        let filter = SessionFilter {
+            status: non_empty(req.status),
+            from_ts: (req.from_unix != 0).then_some(req.from_unix),
+            to_ts: (req.to_unix != 0).then_some(req.to_unix),
+            service: non_empty(req.service),
+            has_anomalies,
+        };
+        self.search_sessions_page(filter, req.page).await
+    }
+}
+
+// --- Tipli kayıt dönüşümleri (JSON round trip olmadan) ---
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn or_default(value: String, default: &str) -> String {
+    if value.is_empty() {
+        default.to_string()
+    } else {
+        value
+    }
+}
+
+/// Boş alanlar JSON biçimindeki `serde` varsayılanlarıyla doldurulur; `tenant_id` JSON'da
+/// olduğu gibi zorunludur.
+impl TryFrom<observer_proto::LogRecord> for LogRecord {
+    type Error = String;
+
+    fn try_from(record: observer_proto::LogRecord) -> Result<Self, Self::Error> {
+        if record.tenant_id.trim().is_empty() {
+            return Err("tenant_id boş olamaz".to_string());
+        }
+        let resource = record
+            .resource
+            .map(|r| ResourceContext {
+                service_name: or_default(r.service_name, "unknown"),
+                service_version: or_default(r.service_version, "unknown"),
+                service_env: or_default(r.service_env, "production"),
+                host_name: non_empty(r.host_name),
+            })
+            .unwrap_or_default();
+
+        Ok(LogRecord {
+            schema_v: or_default(record.schema_v, "1.0.0"),
+            ts: if record.ts.is_empty() {
+                chrono::Utc::now().to_rfc3339()
+            } else {
+                record.ts
+            },
+            severity: or_default(record.severity, "INFO"),
+            tenant_id: record.tenant_id,
+            resource,
+            trace_id: non_empty(record.trace_id),
+            span_id: non_empty(record.span_id),
+            event: or_default(record.event, "LOG_EVENT"),
+            message: record.message,
+            attributes: record
+                .attributes
+                .into_iter()
+                .map(|(key, value)| (key, attribute_to_json(value)))
+                .collect(),
+            // Etiketleri sunucu türetir (JSON yolundaki `skip_deserializing` ile aynı).
+            smart_tags: Vec::new(),
+            _idx: 0.0,
+        })
+    }
+}
+
+impl From<&LogRecord> for observer_proto::LogRecord {
+    fn from(log: &LogRecord) -> Self {
+        Self {
+            schema_v: log.schema_v.clone(),
+            ts: log.ts.clone(),
+            severity: log.severity.clone(),
+            tenant_id: log.tenant_id.clone(),
+            resource: Some(observer_proto::ResourceContext {
+                service_name: log.resource.service_name.clone(),
+                service_version: log.resource.service_version.clone(),
+                service_env: log.resource.service_env.clone(),
+                host_name: log.resource.host_name.clone().unwrap_or_default(),
+            }),
+            trace_id: log.trace_id.clone().unwrap_or_default(),
+            span_id: log.span_id.clone().unwrap_or_default(),
+            event: log.event.clone(),
+            message: log.message.clone(),
+            attributes: log
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), attribute_from_json(value)))
+                .collect(),
+            smart_tags: log.smart_tags.clone(),
+        }
+    }
+}
+
+impl From<SessionSummary> for observer_proto::SessionSummary {
+    fn from(summary: SessionSummary) -> Self {
+        Self {
+            session_id: summary.session_id,
+            start_time: summary.start_time,
+            start_unix: summary.start_ts,
+            last_update_unix: summary.last_update_ts,
+            logs_count: summary.logs_count as u64,
+            status: summary.status,
+            anomaly_count: summary.anomaly_count as u64,
+            services: summary.services,
+            dtmf_digits: summary.dtmf_digits,
+            media_stream_count: summary.media_stream_count as u64,
+        }
+    }
+}
+
+impl From<SessionPage> for observer_proto::SessionPage {
+    fn from(page: SessionPage) -> Self {
+        Self {
+            total: page.total as u64,
+            sessions: page.sessions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&CallSession> for observer_proto::CallSession {
+    fn from(session: &CallSession) -> Self {
+        Self {
+            summary: Some(session.summary().into()),
+            logs: session.logs.iter().map(Into::into).collect(),
+            anomalies: session.anomalies.clone(),
+            media_streams: session.media_streams.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&MediaStreamStats> for observer_proto::MediaStreamStats {
+    fn from(stats: &MediaStreamStats) -> Self {
+        Self {
+            ssrc: stats.ssrc.clone(),
+            direction: stats.direction.clone().unwrap_or_default(),
+            codec: stats.codec.clone().unwrap_or_default(),
+            packets_received: stats.packets_received,
+            packets_expected: stats.packets_expected,
+            packets_lost: stats.packets_lost,
+            loss_pct: stats.loss_pct,
+            jitter_ms: stats.jitter_ms,
+            max_delta_ms: stats.max_delta_ms,
+            duplicates: stats.duplicates,
+            out_of_order: stats.out_of_order,
+            is_final: stats.is_final,
+        }
+    }
+}
+
+fn attribute_to_json(value: AttributeValue) -> Value {
+    match value.kind {
+        Some(Kind::StringValue(s)) => Value::String(s),
+        Some(Kind::IntValue(i)) => Value::from(i),
+        Some(Kind::DoubleValue(f)) => Value::from(f),
+        Some(Kind::BoolValue(b)) => Value::Bool(b),
+        Some(Kind::JsonValue(json)) => serde_json::from_str(&json).unwrap_or(Value::String(json)),
+        None => Value::Null,
+    }
+}
+
+fn attribute_from_json(value: &Value) -> AttributeValue {
+    let kind = match value {
+        Value::Null => None,
+        Value::Bool(b) => Some(Kind::BoolValue(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(Kind::IntValue(i)),
+            None => Some(Kind::DoubleValue(n.as_f64().unwrap_or_default())),
+        },
+        Value::String(s) => Some(Kind::StringValue(s.clone())),
+        Value::Array(_) | Value::Object(_) => Some(Kind::JsonValue(value.to_string())),
+    };
+    AttributeValue { kind }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(capacity: usize) -> (GrpcServerState, mpsc::Receiver<LogRecord>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        let (live, _) = broadcast::channel(16);
+        let (query_tx, _) = mpsc::channel(1);
+        let state = GrpcServerState {
+            tx,
+            live,
+            tenant_id: "t".to_string(),
+            sessions: SessionQueryHandle::new(query_tx),
+        };
+        (state, rx)
+    }
+
+    fn typed(tenant_id: &str, message: &str) -> IngestLogRequest {
+        IngestLogRequest {
+            payload: Some(Payload::Record(observer_proto::LogRecord {
+                tenant_id: tenant_id.to_string(),
+                message: message.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    fn raw(json: &str) -> IngestLogRequest {
+        IngestLogRequest {
+            payload: Some(Payload::RawJsonLog(json.to_string())),
+        }
+    }
+
+    async fn batch(state: &GrpcServerState, logs: Vec<IngestLogRequest>) -> IngestSummary {
+        state
+            .ingest_batch(Request::new(IngestBatchRequest { logs }))
+            .await
+            .expect("IngestBatch hata döndü")
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn batch_tallies_accepted_and_rejected_records() {
+        let (state, mut rx) = state(16);
+        let summary = batch(
+            &state,
+            vec![
+                typed("t", "tipli"),
+                typed("", "kiracısız"),
+                raw(r#"{"tenant_id":"t","message":"json"}"#),
+                raw("{bozuk"),
+                IngestLogRequest { payload: None },
+            ],
+        )
+        .await;
+
+        assert_eq!((summary.accepted, summary.rejected), (2, 3));
+        let indexes: Vec<u64> = summary.rejections.iter().map(|r| r.index).collect();
+        assert_eq!(indexes, [1, 3, 4]);
+        assert!(summary.rejections[0].reason.contains("tenant_id"));
+        assert!(summary.rejections[1].reason.starts_with("json"));
+
+        for message in ["tipli", "json"] {
+            let log = rx.try_recv().expect("kayıt iletilmedi");
+            assert_eq!(log.message, message);
+            assert_eq!(log.attributes["source"], "grpc");
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn closed_ingest_channel_rejects_every_record() {
+        let (state, rx) = state(16);
+        drop(rx);
+        let summary = batch(&state, vec![typed("t", "a"), typed("t", "b")]).await;
+        assert_eq!((summary.accepted, summary.rejected), (0, 2));
+        assert!(summary
+            .rejections
+            .iter()
+            .all(|r| r.reason == "ingest channel closed"));
+    }
+
+    #[tokio::test]
+    async fn rejection_details_are_capped() {
+        let (state, _rx) = state(16);
+        let logs = (0..MAX_REPORTED_REJECTIONS + 50)
+            .map(|_| IngestLogRequest { payload: None })
+            .collect();
+        let summary = batch(&state, logs).await;
+        assert_eq!(summary.rejected as usize, MAX_REPORTED_REJECTIONS + 50);
+        assert_eq!(summary.rejections.len(), MAX_REPORTED_REJECTIONS);
+    }
+
+    #[test]
+    fn typed_record_round_trips_attribute_variants() {
+        let mut log = LogRecord::new_system("WARN", "SIP_PACKET", "mesaj", "t");
+        log.trace_id = Some("call-1".to_string());
+        log.resource.host_name = Some("node".to_string());
+        log.attributes = [
+            ("s", Value::from("metin")),
+            ("i", Value::from(-42)),
+            ("f", Value::from(1.5)),
+            ("b", Value::Bool(true)),
+            ("a", serde_json::json!([1, "iki"])),
+            ("o", serde_json::json!({"k": {"v": null}})),
+            ("n", Value::Null),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        let typed = observer_proto::LogRecord::from(&log);
+        assert!(matches!(
+            typed.attributes["a"].kind,
+            Some(Kind::JsonValue(_))
+        ));
+        assert!(typed.attributes["n"].kind.is_none());
+
+        let back = LogRecord::try_from(typed).expect("geri çevrilemedi");
+        assert_eq!(back.attributes, log.attributes);
+        assert_eq!(
+            (back.ts, back.severity, back.event, back.message),
+            (log.ts, log.severity, log.event, log.message)
+        );
+        assert_eq!(back.trace_id, log.trace_id);
+        assert_eq!(back.span_id, None);
+        assert_eq!(back.resource.host_name, log.resource.host_name);
+    }
+
+    #[test]
+    fn typed_record_requires_tenant_and_fills_defaults() {
+        for tenant_id in ["", "  "] {
+            let record = observer_proto::LogRecord {
+                tenant_id: tenant_id.to_string(),
+                ..Default::default()
+            };
+            assert!(LogRecord::try_from(record).is_err());
+        }
+
+        let record = observer_proto::LogRecord {
+            tenant_id: "t".to_string(),
+            ..Default::default()
+        };
+        let log = LogRecord::try_from(record).expect("çevrilemedi");
+        assert_eq!(log.schema_v, "1.0.0");
+        assert_eq!(log.severity, "INFO");
+        assert_eq!(log.event, "LOG_EVENT");
+        assert_eq!(log.resource.service_name, "unknown");
+        assert!(log.trace_id.is_none());
+        assert!(chrono::DateTime::parse_from_rfc3339(&log.ts).is_ok());
+    }
+
+    #[tokio::test]
+    async fn client_supplied_smart_tags_are_ignored() {
+        let (state, mut rx) = state(4);
+        let request = IngestLogRequest {
+            payload: Some(Payload::Record(observer_proto::LogRecord {
+                tenant_id: "t".to_string(),
+                smart_tags: vec!["VIP".to_string(), "ANOMALY".to_string()],
+                ..Default::default()
+            })),
+        };
+        state
+            .ingest_log(Request::new(request))
+            .await
+            .expect("IngestLog hata döndü");
+        let log = rx.try_recv().expect("kayıt iletilmedi");
+        assert_eq!(log.smart_tags, ["GRPC", "REMOTE"]);
+    }
+
+    fn live_record(tenant_id: &str, event: &str, severity: &str) -> LogRecord {
+        let mut log = LogRecord::new_system(severity, event, "canlı", tenant_id);
+        log.resource.service_name = "proxy".to_string();
+        log.trace_id = Some("call-1".to_string());
+        log.smart_tags = vec!["Sip".to_string()];
+        log
+    }
+
+    #[test]
+    fn subscription_filter_matches_every_field() {
+        let log = live_record("t", "SIP_PACKET", "warn");
+        assert!(SubscriptionFilter::new(SubscribeRequest::default()).matches(&log));
+
+        let filter = SubscriptionFilter::new(SubscribeRequest {
+            tenant_id: "t".to_string(),
+            services: vec!["proxy".to_string()],
+            severities: vec!["Warn".to_string(), "error".to_string()],
+            trace_id: "call-1".to_string(),
+            events: vec!["SIP_PACKET".to_string()],
+            smart_tags: vec!["sip".to_string()],
+        });
+        assert!(filter.matches(&log));
+
+        let mismatches: [fn(&mut LogRecord); 6] = [
+            |l| l.tenant_id = "diğer".to_string(),
+            |l| l.resource.service_name = "media".to_string(),
+            |l| l.severity = "INFO".to_string(),
+            |l| l.trace_id = None,
+            |l| l.event = "RTP_PACKET".to_string(),
+            |l| l.smart_tags.clear(),
+        ];
+        for change in mismatches {
+            let mut other = log.clone();
+            change(&mut other);
+            assert!(!filter.matches(&other));
+        }
+    }
+
+    #[tokio::test]
+    async fn subscriber_receives_filtered_records_and_lag_events() {
+        use futures_util::StreamExt;
+
+        let (state, _rx) = state(4);
+        let mut stream = state
+            .subscribe(Request::new(SubscribeRequest {
+                events: vec!["SIP_PACKET".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .expect("abonelik açılamadı")
+            .into_inner();
+
+        let _ = state.live.send(live_record("t", "RTP_PACKET", "INFO"));
+        let _ = state.live.send(live_record("t", "SIP_PACKET", "INFO"));
+        let event = stream.next().await.expect("akış kapandı");
+        match event.expect("hata").event {
+            Some(Event::Record(record)) => assert_eq!(record.event, "SIP_PACKET"),
+            other => panic!("kayıt bekleniyordu: {:?}", other),
+        }
+
+        // Yayın kapasitesini (16) aşan kayıtlar abonenin gerisinde kalır.
+        for _ in 0..20 {
+            let _ = state.live.send(live_record("t", "SIP_PACKET", "INFO"));
+        }
+        let event = stream.next().await.expect("akış kapandı");
+        match event.expect("hata").event {
+            Some(Event::Lag(lag)) => assert_eq!(lag.skipped, 4),
+            other => panic!("gecikme olayı bekleniyordu: {:?}", other),
+        }
+        let event = stream.next().await.expect("akış kapandı");
+        assert!(matches!(event.expect("hata").event, Some(Event::Record(_))));
+    }
 }
@@ -1,18 +1,27 @@
 // src/api/routes.rs
+use crate::core::call_audio::CallAudioStore;
+use crate::core::call_frames::{render_pcapng, CallFrameStore};
 use crate::core::domain::LogRecord;
+use crate::core::log_query::{LogQuery, DEFAULT_LOG_QUERY_LIMIT};
+use crate::core::session_query::{
+    SessionFilter, SessionPageRequest, SessionQueryHandle, SessionSortKey, DEFAULT_PAGE_LIMIT,
+};
+use crate::ports::LogStore;
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Path, Query, State,
     },
+    http::{header, StatusCode},
     response::{Html, IntoResponse},
     routing::{get, post},
     Json, Router,
 };
+use serde::Deserialize;
 use serde_json::{json, Value};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, Mutex};
 use tower_http::services::ServeDir;
 // DÜZELTME: "warn" import'u artık `handle_socket` içinde kullanılıyor.
 use tracing::info;
@@ -23,6 +32,10 @@ const UI_ASSETS_PATH: &str = "src/ui";
 pub struct AppState {
     pub tx: broadcast::Sender<LogRecord>,
     pub sniffer_active: Arc<AtomicBool>, // Sniffer Motorunun Anahtarı
+    pub call_audio: Arc<Mutex<CallAudioStore>>,
+    pub call_frames: Arc<Mutex<CallFrameStore>>,
+    pub sessions: SessionQueryHandle, // Aggregator oturum sorguları
+    pub log_store: Option<Arc<dyn LogStore>>, // Kalıcı log deposu (kapalıysa None)
     pub config: crate::config::AppConfig,
 }
 
@@ -38,6 +51,22 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/api/sniffer/status", get(get_sniffer_status))
         .route("/api/sniffer/enable", post(enable_sniffer))
         .route("/api/sniffer/disable", post(disable_sniffer))
+        // Çağrı Kaydı: G.711 sesi stereo WAV olarak (sol: arayan, sağ: aranan)
+        .route("/api/calls/:trace_id/audio.wav", get(download_call_audio))
+        // Çağrı Yakalaması: SIP + bağlı RTP/RTCP ham çerçeveleri (Wireshark için pcapng)
+        .route(
+            "/api/calls/:trace_id/capture.pcapng",
+            get(download_call_capture),
+        )
+        // Çağrı Oturumları: Aggregator'daki CallSession verisi (sayfalı liste, detay, özet)
+        .route("/api/sessions", get(list_sessions))
+        .route("/api/sessions/:session_id", get(get_session))
+        .route(
+            "/api/sessions/:session_id/summary",
+            get(get_session_summary),
+        )
+        // Kalıcı Log Deposu: zaman aralığı, tenant, servis, seviye ve trace id ile sorgu
+        .route("/api/logs", get(query_logs))
         // Statik Varlıklar (CSS/JS)
         .nest_service("/ui", ServeDir::new(UI_ASSETS_PATH))
         .with_state(state)
@@ -78,6 +107,271 @@ async fn disable_sniffer(State(state): State<Arc<AppState>>) -> Json<Value> {
     Json(json!({ "status": "deactivated", "message": "Network interception stopped." }))
 }
 
+async fn download_call_audio(
+    State(state): State<Arc<AppState>>,
+    Path(trace_id): Path<String>,
+) -> impl IntoResponse {
+    // Kilit yalnızca kopya alınırken tutulur; ingest döngüsü WAV kodlamasını beklemez.
+    let snapshot = state.call_audio.lock().await.snapshot(&trace_id);
+    match snapshot {
+        Some(snapshot) => match tokio::task::spawn_blocking(move || snapshot.render_wav()).await {
+            Ok(wav) => attachment(&trace_id, "wav", "audio/wav", wav),
+            Err(e) => render_failed(e),
+        },
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "no_audio", "message": "No G.711 audio captured for this trace id." })),
+        )
+            .into_response(),
+    }
+}
+
+async fn download_call_capture(
+    State(state): State<Arc<AppState>>,
+    Path(trace_id): Path<String>,
+) -> impl IntoResponse {
+    let frames = state.call_frames.lock().await.snapshot(&trace_id);
+    match frames {
+        Some(frames) => match tokio::task::spawn_blocking(move || render_pcapng(frames)).await {
+            Ok(pcapng) => attachment(&trace_id, "pcapng", "application/x-pcapng", pcapng),
+            Err(e) => render_failed(e),
+        },
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "no_capture", "message": "No raw frames captured for this trace id." })),
+        )
+            .into_response(),
+    }
+}
+
+/// `GET /api/sessions` sorgu parametreleri; hepsi isteğe bağlıdır.
+#[derive(Debug, Deserialize)]
+struct SessionListParams {
+    status: Option<String>,
+    service: Option<String>,
+    /// Unix saniye: bu andan sonra etkinliği olan oturumlar
+    from: Option<i64>,
+    /// Unix saniye: bu andan önce başlamış oturumlar
+    to: Option<i64>,
+    anomalies: Option<bool>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    /// last_update (varsayılan), start_time, logs_count, anomalies
+    sort: Option<String>,
+    /// asc | desc (varsayılan)
+    order: Option<String>,
+}
+
+impl SessionListParams {
+    fn into_query(self) -> Result<(SessionFilter, SessionPageRequest), String> {
+        let sort = match self.sort.as_deref() {
+            None | Some("") => SessionSortKey::default(),
+            Some(sort) => {
+                SessionSortKey::parse(sort).ok_or_else(|| format!("Unknown sort key: {}", sort))?
+            }
+        };
+        let descending = match self.order.as_deref() {
+            None | Some("") | Some("desc") => true,
+            Some("asc") => false,
+            Some(order) => return Err(format!("Unknown order: {}", order)),
+        };
+        let filter = SessionFilter {
+            status: self.status.filter(|s| !s.is_empty()),
+            from_ts: self.from,
+            to_ts: self.to,
+            service: self.service.filter(|s| !s.is_empty()),
+            has_anomalies: self.anomalies,
+        };
+        let page = SessionPageRequest {
+            offset: self.offset.unwrap_or(0),
+            limit: self.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+            sort,
+            descending,
+        };
+        Ok((filter, page))
+    }
+}
+
+async fn list_sessions(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SessionListParams>,
+) -> impl IntoResponse {
+    let (filter, page) = match params.into_query() {
+        Ok(query) => query,
+        Err(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_query", "message": message })),
+            )
+                .into_response()
+        }
+    };
+    match state.sessions.search(filter, page).await {
+        Some(page) => Json(page).into_response(),
+        None => aggregator_unavailable(),
+    }
+}
+
+async fn get_session(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    match state.sessions.get(session_id).await {
+        Some(Some(session)) => Json(session).into_response(),
+        Some(None) => session_not_found(),
+        None => aggregator_unavailable(),
+    }
+}
+
+async fn get_session_summary(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    match state.sessions.overview(session_id).await {
+        Some(Some(overview)) => Json(overview).into_response(),
+        Some(None) => session_not_found(),
+        None => aggregator_unavailable(),
+    }
+}
+
+fn session_not_found() -> axum::response::Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": "no_session", "message": "No active or retained session for this id." })),
+    )
+        .into_response()
+}
+
+fn aggregator_unavailable() -> axum::response::Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "error": "aggregator_unavailable", "message": "Session aggregator is not running." })),
+    )
+        .into_response()
+}
+
+/// `GET /api/logs` sorgu parametreleri; `from` / `to` unix saniye veya RFC 3339.
+#[derive(Debug, Deserialize)]
+struct LogQueryParams {
+    from: Option<String>,
+    to: Option<String>,
+    tenant_id: Option<String>,
+    service: Option<String>,
+    /// Virgülle ayrılmış seviyeler (ör. `ERROR,FATAL`)
+    severity: Option<String>,
+    trace_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl LogQueryParams {
+    fn into_query(self) -> Result<LogQuery, String> {
+        let non_empty = |value: Option<String>| value.filter(|v| !v.is_empty());
+        Ok(LogQuery {
+            from_ms: parse_time_param("from", self.from)?,
+            to_ms: parse_time_param("to", self.to)?,
+            tenant_id: non_empty(self.tenant_id),
+            service: non_empty(self.service),
+            severities: self
+                .severity
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            trace_id: non_empty(self.trace_id),
+            limit: self.limit.unwrap_or(DEFAULT_LOG_QUERY_LIMIT),
+        })
+    }
+}
+
+fn parse_time_param(name: &str, value: Option<String>) -> Result<Option<i64>, String> {
+    let Some(value) = value.filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if let Ok(secs) = value.parse::<i64>() {
+        return Ok(Some(secs.saturating_mul(1000)));
+    }
+    chrono::DateTime::parse_from_rfc3339(&value)
+        .map(|ts| Some(ts.timestamp_millis()))
+        .map_err(|_| format!("Invalid {}: expected unix seconds or RFC 3339", name))
+}
+
+async fn query_logs(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<LogQueryParams>,
+) -> impl IntoResponse {
+    let Some(store) = state.log_store.clone() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "error": "store_disabled", "message": "Persistent log store is not enabled." })),
+        )
+            .into_response();
+    };
+    let query = match params.into_query() {
+        Ok(query) => query,
+        Err(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_query", "message": message })),
+            )
+                .into_response()
+        }
+    };
+    match store.query(query).await {
+        Ok(records) => Json(json!({ "count": records.len(), "records": records })).into_response(),
+        Err(e) => {
+            tracing::error!(event="STORE_QUERY_ERR", error=%e, "Log deposu sorgusu başarısız");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "store_query_failed", "message": e.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Kilit dışında çalışan dosya üretimi (WAV/pcapng) görevi tamamlanamadı.
+fn render_failed(e: tokio::task::JoinError) -> axum::response::Response {
+    tracing::error!(event="RENDER_TASK_ERR", error=%e, "İndirme dosyası üretilemedi");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "render_failed", "message": e.to_string() })),
+    )
+        .into_response()
+}
+
+/// İndirme yanıtı; dosya adı trace id'den güvenli karakterlerle türetilir.
+fn attachment(
+    trace_id: &str,
+    extension: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> axum::response::Response {
+    let file_name: String = trace_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.{}\"", file_name, extension),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
 // ==========================================
 // WEB & SOCKET HANDLERS
 // ==========================================
@@ -151,3 +445,45 @@ async fn get_system_config(State(_state): State<Arc<AppState>>) -> Json<serde_js
         "is_upstream_enabled": !std::env::var("UPSTREAM_OBSERVER_URL").unwrap_or_default().is_empty(),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(sort: Option<&str>, order: Option<&str>) -> SessionListParams {
+        SessionListParams {
+            status: Some(String::new()),
+            service: Some("proxy".to_string()),
+            from: None,
+            to: Some(10),
+            anomalies: Some(true),
+            offset: None,
+            limit: None,
+            sort: sort.map(String::from),
+            order: order.map(String::from),
+        }
+    }
+
+    #[test]
+    fn session_list_params_default_to_latest_first() {
+        let (filter, page) = params(None, None).into_query().expect("geçerli sorgu");
+        assert!(filter.status.is_none());
+        assert_eq!(filter.service.as_deref(), Some("proxy"));
+        assert_eq!((filter.to_ts, filter.has_anomalies), (Some(10), Some(true)));
+        assert_eq!((page.offset, page.limit), (0, DEFAULT_PAGE_LIMIT));
+        assert_eq!(page.sort, SessionSortKey::LastUpdate);
+        assert!(page.descending);
+
+        let (_, page) = params(Some("logs_count"), Some("asc"))
+            .into_query()
+            .expect("geçerli sorgu");
+        assert_eq!(page.sort, SessionSortKey::LogsCount);
+        assert!(!page.descending);
+    }
+
+    #[test]
+    fn session_list_params_reject_unknown_sort_and_order() {
+        assert!(params(Some("duration"), None).into_query().is_err());
+        assert!(params(None, Some("up")).into_query().is_err());
+    }
+}
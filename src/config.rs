@@ -11,15 +11,55 @@ pub struct AppConfig {
     #[allow(dead_code)]
     pub metric_port: u16,
     pub docker_socket: String,
+    /// `hedef=etiket` çiftleri (ör. `service.name=sentiric.service`)
+    pub docker_label_mappings: Vec<(String, String)>,
+    /// Container include/exclude kuralları (bkz. `ContainerFilter`)
+    pub docker_container_rules: String,
+    pub docker_min_severity: String,
+    pub docker_multiline_enabled: bool,
+    pub docker_multiline_start: String,
+    pub docker_multiline_continuation: String,
+    pub docker_multiline_flush_ms: u64,
+    pub docker_multiline_max_lines: usize,
 
     // --- Feature Flags (Sniffer) ---
     pub sniffer_enabled: bool,
     pub sniffer_interface: String,
     pub sniffer_filter: String,
 
+    // --- Log Dosyası Takibi (Çıplak Metal) ---
+    /// Virgülle ayrılmış glob desenleri (`servis=desen` olabilir); boşsa kapalı
+    pub file_tail_paths: String,
+    pub file_tail_state_path: String,
+    pub file_tail_poll_ms: u64,
+
+    // --- Syslog Receiver (RFC 5424 / RFC 3164) ---
+    pub syslog_enabled: bool,
+    /// 0 ise ilgili taşıma kapalıdır
+    pub syslog_udp_port: u16,
+    pub syslog_tcp_port: u16,
+
+    // --- Kalıcı Log Deposu (Segment Store) ---
+    /// Varsayılan kapalı; açıkken `RTP_PACKET` dahil her kayıt diske yazılır
+    pub store_enabled: bool,
+    pub store_dir: String,
+    pub store_retention_hours: u64,
+    /// Tüm segmentlerin toplam boyut sınırı
+    pub store_max_size_mb: u64,
+    pub store_segment_max_mb: u64,
+
+    // --- Offline Pcap Replay ---
+    pub pcap_replay_file: Option<String>,
+    pub pcap_replay_filter: String,
+    pub pcap_replay_realtime: bool,
+
     // --- Memory Governance (Aggregator) ---
     pub max_active_sessions: usize,
     pub session_ttl_seconds: i64,
+    pub call_audio_max_calls: usize,
+    pub call_audio_max_seconds: u64,
+    pub call_capture_max_calls: usize,
+    pub call_capture_max_bytes: usize,
 
     // --- mTLS Security & Upstream ---
     pub tls_cert_path: Option<String>,
@@ -64,6 +104,40 @@ impl AppConfig {
                     "/var/run/docker.sock".into()
                 }
             }),
+            docker_label_mappings: env::var("DOCKER_LABEL_MAPPINGS")
+                .unwrap_or(
+                    "service.name=sentiric.service,service.version=sentiric.version,service.env=sentiric.env"
+                        .to_string(),
+                )
+                .split(',')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(target, label)| (target.trim().to_string(), label.trim().to_string()))
+                .filter(|(target, label)| !target.is_empty() && !label.is_empty())
+                .collect(),
+            docker_container_rules: env::var("DOCKER_CONTAINER_RULES")
+                .unwrap_or("exclude name=*observer*".to_string()),
+            docker_min_severity: env::var("DOCKER_MIN_SEVERITY").unwrap_or("TRACE".to_string()),
+            // Varsayılan açık: kayıt sonraki başlangıç satırına veya flush süresine kadar bekletilir.
+            docker_multiline_enabled: env::var("DOCKER_MULTILINE_ENABLED")
+                .unwrap_or("true".to_string())
+                .parse()
+                .unwrap_or(true),
+            docker_multiline_start: env::var("DOCKER_MULTILINE_START")
+                .unwrap_or(r"^\S".to_string()),
+            // Java (`at ...`, `Caused by:`), Python (`ValueError: ...`) ve Rust (`stack backtrace:`)
+            // izlerinin girintisiz devam satırları
+            docker_multiline_continuation: env::var("DOCKER_MULTILINE_CONTINUATION").unwrap_or(
+                r"^(\s|at |Caused by:|Suppressed:|\.\.\. \d+ (more|common frames omitted)|stack backtrace:|note: |During handling of the above exception|The above exception was the direct cause|[A-Za-z_][\w.$]*(Error|Exception|Interrupt|Exit)(: |$))"
+                    .to_string(),
+            ),
+            docker_multiline_flush_ms: env::var("DOCKER_MULTILINE_FLUSH_MS")
+                .unwrap_or("500".to_string())
+                .parse()
+                .unwrap_or(500),
+            docker_multiline_max_lines: env::var("DOCKER_MULTILINE_MAX_LINES")
+                .unwrap_or("500".to_string())
+                .parse()
+                .unwrap_or(500),
 
             sniffer_enabled: env::var("SNIFFER_ENABLED")
                 .unwrap_or("false".to_string())
@@ -76,8 +150,60 @@ impl AppConfig {
                     "lo0".into()
                 }
             }),
-            sniffer_filter: env::var("SNIFFER_FILTER")
-                .unwrap_or("port 5060 or port 5061".to_string()),
+            // Taşıma başlığı taşımayan IPv4/IPv6 parçaları port kuralına takılmaz;
+            // birleştiriciye ulaşabilmeleri için varsayılan filtreye ayrıca eklenir.
+            sniffer_filter: env::var("SNIFFER_FILTER").unwrap_or(
+                "port 5060 or port 5061 or (ip[6:2] & 0x1fff != 0) or ip6[6] == 44".to_string(),
+            ),
+
+            file_tail_paths: env::var("FILE_TAIL_PATHS").unwrap_or_default(),
+            file_tail_state_path: env::var("FILE_TAIL_STATE_PATH")
+                .unwrap_or("/var/lib/sentiric-observer/file-tail-offsets.json".to_string()),
+            file_tail_poll_ms: env::var("FILE_TAIL_POLL_MS")
+                .unwrap_or("1000".to_string())
+                .parse()
+                .unwrap_or(1000),
+
+            syslog_enabled: env::var("SYSLOG_ENABLED")
+                .unwrap_or("false".to_string())
+                .parse()
+                .unwrap_or(false),
+            syslog_udp_port: env::var("SYSLOG_UDP_PORT")
+                .unwrap_or("5514".to_string())
+                .parse()
+                .unwrap_or(5514),
+            syslog_tcp_port: env::var("SYSLOG_TCP_PORT")
+                .unwrap_or("5514".to_string())
+                .parse()
+                .unwrap_or(5514),
+
+            store_enabled: env::var("STORE_ENABLED")
+                .unwrap_or("false".to_string())
+                .parse()
+                .unwrap_or(false),
+            store_dir: env::var("STORE_DIR")
+                .unwrap_or("/var/lib/sentiric-observer/store".to_string()),
+            store_retention_hours: env::var("STORE_RETENTION_HOURS")
+                .unwrap_or("72".to_string())
+                .parse()
+                .unwrap_or(72),
+            store_max_size_mb: env::var("STORE_MAX_SIZE_MB")
+                .unwrap_or("10240".to_string())
+                .parse()
+                .unwrap_or(10240),
+            store_segment_max_mb: env::var("STORE_SEGMENT_MAX_MB")
+                .unwrap_or("64".to_string())
+                .parse()
+                .unwrap_or(64),
+
+            pcap_replay_file: env::var("PCAP_REPLAY_FILE")
+                .ok()
+                .filter(|p| !p.trim().is_empty()),
+            pcap_replay_filter: env::var("PCAP_REPLAY_FILTER").unwrap_or_default(),
+            pcap_replay_realtime: env::var("PCAP_REPLAY_REALTIME")
+                .unwrap_or("false".to_string())
+                .parse()
+                .unwrap_or(false),
 
             max_active_sessions: env::var("MAX_ACTIVE_SESSIONS")
                 .unwrap_or("10000".to_string())
@@ -87,6 +213,22 @@ impl AppConfig {
                 .unwrap_or("300".to_string())
                 .parse()
                 .unwrap_or(300),
+            call_audio_max_calls: env::var("CALL_AUDIO_MAX_CALLS")
+                .unwrap_or("100".to_string())
+                .parse()
+                .unwrap_or(100),
+            call_audio_max_seconds: env::var("CALL_AUDIO_MAX_SECONDS")
+                .unwrap_or("300".to_string())
+                .parse()
+                .unwrap_or(300),
+            call_capture_max_calls: env::var("CALL_CAPTURE_MAX_CALLS")
+                .unwrap_or("500".to_string())
+                .parse()
+                .unwrap_or(500),
+            call_capture_max_bytes: env::var("CALL_CAPTURE_MAX_BYTES")
+                .unwrap_or("8388608".to_string())
+                .parse()
+                .unwrap_or(8 * 1024 * 1024),
 
             //[ARCH-COMPLIANCE]: Docker Compose legacy isimlerini ve standart isimleri destekle
             tls_cert_path: env::var("TLS_CERT_PATH")
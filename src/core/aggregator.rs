@@ -1,9 +1,20 @@
 // src/core/aggregator.rs
 use crate::core::domain::LogRecord;
+use crate::core::session_query::{
+    SessionFilter, SessionOverview, SessionPage, SessionPageRequest, SessionQuery, SessionSortKey,
+    SessionSummary, MAX_PAGE_LIMIT,
+};
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tracing::info; // unused import 'warn' temizlendi
 
+/// Oturumda tutulan kayıt sınırı. Aşılınca en eski `RTP_PACKET` kayıtları atılır;
+/// sinyalleşme ve uygulama kayıtları korunur, `logs_count` tüm kayıtları saymaya devam eder.
+const MAX_SESSION_LOGS: usize = 5000;
+/// Sınır aşıldığında her seferinde bir kayıt yerine toplu atılan ek medya kaydı.
+const SESSION_TRIM_BATCH: usize = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallSession {
     pub session_id: String,
@@ -13,6 +24,182 @@ pub struct CallSession {
     pub logs: Vec<LogRecord>,
     pub status: String,
     pub anomalies: Vec<String>,
+    /// SSRC başına son RTP kalite raporu (RTP_STREAM_STATS).
+    #[serde(default)]
+    pub media_streams: Vec<MediaStreamStats>,
+    /// Arayanın/arananın bastığı tuşlar, basılma sırasıyla (DTMF_DIGIT).
+    #[serde(default)]
+    pub dtmf_digits: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaStreamStats {
+    pub ssrc: String,
+    pub direction: Option<String>,
+    pub codec: Option<String>,
+    pub packets_received: u64,
+    pub packets_expected: u64,
+    pub packets_lost: u64,
+    pub loss_pct: f64,
+    pub jitter_ms: f64,
+    pub max_delta_ms: f64,
+    pub duplicates: u64,
+    pub out_of_order: u64,
+    pub is_final: bool,
+}
+
+impl CallSession {
+    /// `start_time` ilk kaydın zaman damgasıdır; çözülemezse son güncelleme kullanılır.
+    fn start_ts(&self) -> i64 {
+        DateTime::parse_from_rfc3339(&self.start_time)
+            .map(|ts| ts.timestamp())
+            .unwrap_or(self.last_update_ts)
+    }
+
+    fn has_service(&self, service: &str) -> bool {
+        self.logs
+            .iter()
+            .any(|log| log.resource.service_name.eq_ignore_ascii_case(service))
+    }
+
+    fn matches(&self, filter: &SessionFilter) -> bool {
+        filter
+            .status
+            .as_ref()
+            .is_none_or(|status| self.status.eq_ignore_ascii_case(status))
+            && filter
+                .from_ts
+                .is_none_or(|from| self.last_update_ts >= from)
+            && filter.to_ts.is_none_or(|to| self.start_ts() <= to)
+            && filter
+                .has_anomalies
+                .is_none_or(|wanted| self.anomalies.is_empty() != wanted)
+            && filter
+                .service
+                .as_ref()
+                .is_none_or(|service| self.has_service(service))
+    }
+
+    pub fn summary(&self) -> SessionSummary {
+        let mut services: Vec<String> = Vec::new();
+        for log in &self.logs {
+            if !services.contains(&log.resource.service_name) {
+                services.push(log.resource.service_name.clone());
+            }
+        }
+        SessionSummary {
+            session_id: self.session_id.clone(),
+            start_time: self.start_time.clone(),
+            start_ts: self.start_ts(),
+            last_update_ts: self.last_update_ts,
+            logs_count: self.logs_count,
+            status: self.status.clone(),
+            anomaly_count: self.anomalies.len(),
+            services,
+            dtmf_digits: self.dtmf_digits.clone(),
+            media_stream_count: self.media_streams.len(),
+        }
+    }
+
+    /// Kayıt sınırı aşıldıysa en eski medya paketi kayıtlarını atar.
+    fn trim_media_logs(&mut self) {
+        if self.logs.len() <= MAX_SESSION_LOGS {
+            return;
+        }
+        let mut excess = self.logs.len() - MAX_SESSION_LOGS + SESSION_TRIM_BATCH;
+        self.logs.retain(|log| {
+            if excess > 0 && log.event == "RTP_PACKET" {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Kayıtlar geliş sırasıyla tutulur; farklı kaynakların gecikmesi yüzünden
+    /// zaman damgasına göre (eşitlikte geliş sırası korunarak) dizilir.
+    pub fn sort_logs(&mut self) {
+        self.logs
+            .sort_by_cached_key(|log| DateTime::parse_from_rfc3339(&log.ts).ok());
+    }
+
+    pub fn overview(&self) -> SessionOverview {
+        let times: Vec<DateTime<FixedOffset>> = self
+            .logs
+            .iter()
+            .filter_map(|log| DateTime::parse_from_rfc3339(&log.ts).ok())
+            .collect();
+        let first = times.iter().min();
+        let last = times.iter().max();
+        let duration_ms = match (first, last) {
+            (Some(first), Some(last)) => (*last - *first).num_milliseconds(),
+            _ => 0,
+        };
+
+        let mut sip_response_codes = BTreeMap::new();
+        let mut error_count = 0;
+        let mut warn_count = 0;
+        for log in &self.logs {
+            if let Some(code) = log
+                .attributes
+                .get("sip.status_code")
+                .and_then(|v| v.as_u64())
+                .and_then(|code| u16::try_from(code).ok())
+            {
+                *sip_response_codes.entry(code).or_insert(0) += 1;
+            }
+            match log.severity.as_str() {
+                "ERROR" | "FATAL" => error_count += 1,
+                "WARN" => warn_count += 1,
+                _ => {}
+            }
+        }
+
+        // Gecikmeli kaynaklar yüzünden ilk gelen kayıt en erken kayıt olmayabilir.
+        let mut summary = self.summary();
+        if let Some(first) = first {
+            summary.start_time = first.to_rfc3339();
+            summary.start_ts = first.timestamp();
+        }
+        SessionOverview {
+            summary,
+            end_time: last
+                .map(|ts| ts.to_rfc3339())
+                .unwrap_or_else(|| self.start_time.clone()),
+            duration_ms,
+            sip_response_codes,
+            error_count,
+            warn_count,
+        }
+    }
+}
+
+impl MediaStreamStats {
+    fn from_log(log: &LogRecord) -> Option<Self> {
+        let attrs = &log.attributes;
+        let text = |key: &str| attrs.get(key).and_then(|v| v.as_str()).map(String::from);
+        let count = |key: &str| attrs.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+        let float = |key: &str| attrs.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+        Some(Self {
+            ssrc: text("rtp.ssrc")?,
+            direction: text("rtp.direction"),
+            codec: text("rtp.codec"),
+            packets_received: count("rtp.packets_received"),
+            packets_expected: count("rtp.packets_expected"),
+            packets_lost: count("rtp.packets_lost"),
+            loss_pct: float("rtp.loss_pct"),
+            jitter_ms: float("rtp.jitter_ms"),
+            max_delta_ms: float("rtp.max_delta_ms"),
+            duplicates: count("rtp.duplicates"),
+            out_of_order: count("rtp.out_of_order"),
+            is_final: attrs
+                .get("rtp.final")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        })
+    }
 }
 
 pub struct Aggregator {
@@ -51,11 +238,33 @@ impl Aggregator {
                 logs: Vec::with_capacity(50),
                 status: "Active".to_string(),
                 anomalies: Vec::new(),
+                media_streams: Vec::new(),
+                dtmf_digits: String::new(),
             });
 
         session.last_update_ts = now_ts;
         session.logs_count += 1;
         session.logs.push(log.clone());
+        session.trim_media_logs();
+
+        if log.event == "DTMF_DIGIT" {
+            if let Some(digit) = log.attributes.get("dtmf.digit").and_then(|v| v.as_str()) {
+                session.dtmf_digits.push_str(digit);
+            }
+        }
+
+        if log.event == "RTP_STREAM_STATS" {
+            if let Some(stats) = MediaStreamStats::from_log(log) {
+                match session
+                    .media_streams
+                    .iter_mut()
+                    .find(|s| s.ssrc == stats.ssrc && s.direction == stats.direction)
+                {
+                    Some(existing) => *existing = stats,
+                    None => session.media_streams.push(stats),
+                }
+            }
+        }
 
         if log.severity == "ERROR" || log.severity == "FATAL" {
             session.status = "Failed".to_string();
@@ -67,6 +276,63 @@ impl Aggregator {
         }
     }
 
+    /// Aggregator görevine istek/yanıt kanalından gelen oturum sorgusunu yanıtlar.
+    /// Sorgunun sahibi vazgeçtiyse (oneshot kapalı) yanıt sessizce düşer.
+    pub fn answer(&self, query: SessionQuery) {
+        match query {
+            SessionQuery::Search {
+                filter,
+                page,
+                reply,
+            } => {
+                let _ = reply.send(self.search(&filter, page));
+            }
+            SessionQuery::Get { session_id, reply } => {
+                let session = self.sessions.get(&session_id).cloned().map(|mut s| {
+                    s.sort_logs();
+                    s
+                });
+                let _ = reply.send(session);
+            }
+            SessionQuery::Overview { session_id, reply } => {
+                let _ = reply.send(self.sessions.get(&session_id).map(CallSession::overview));
+            }
+        }
+    }
+
+    fn search(&self, filter: &SessionFilter, page: SessionPageRequest) -> SessionPage {
+        let mut matched: Vec<SessionSummary> = self
+            .sessions
+            .values()
+            .filter(|s| s.matches(filter))
+            .map(CallSession::summary)
+            .collect();
+
+        matched.sort_by(|a, b| {
+            let order = match page.sort {
+                SessionSortKey::LastUpdate => a.last_update_ts.cmp(&b.last_update_ts),
+                SessionSortKey::StartTime => a.start_ts.cmp(&b.start_ts),
+                SessionSortKey::LogsCount => a.logs_count.cmp(&b.logs_count),
+                SessionSortKey::Anomalies => a.anomaly_count.cmp(&b.anomaly_count),
+            };
+            // Eşit anahtarlarda sayfalar arası kararlı sıra için session_id
+            let order = order.then_with(|| a.session_id.cmp(&b.session_id));
+            if page.descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+
+        let total = matched.len();
+        let sessions = matched
+            .into_iter()
+            .skip(page.offset)
+            .take(page.limit.min(MAX_PAGE_LIMIT))
+            .collect();
+        SessionPage { total, sessions }
+    }
+
     pub fn cleanup(&mut self) {
         let now = chrono::Utc::now().timestamp();
         let ttl = self.ttl_seconds;
@@ -90,3 +356,284 @@ impl Aggregator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(trace_id: &str, event: &str, ts: &str) -> LogRecord {
+        let mut log = LogRecord::new_system("INFO", event, event, "t");
+        log.trace_id = Some(trace_id.to_string());
+        log.ts = ts.to_string();
+        log
+    }
+
+    #[test]
+    fn rtp_records_are_kept_until_the_session_cap() {
+        let mut aggregator = Aggregator::new(10, 60);
+        let ts = "2024-05-01T10:00:00Z";
+        aggregator.process(&record("c", "SIP_PACKET", ts));
+        for _ in 1..MAX_SESSION_LOGS {
+            aggregator.process(&record("c", "RTP_PACKET", ts));
+        }
+        let session = &aggregator.sessions["c"];
+        assert_eq!(session.logs.len(), MAX_SESSION_LOGS);
+        assert_eq!(session.logs[1].event, "RTP_PACKET");
+
+        aggregator.process(&record("c", "BYE", ts));
+        let session = &aggregator.sessions["c"];
+        assert_eq!(session.logs_count, MAX_SESSION_LOGS + 1);
+        assert_eq!(session.logs.len(), MAX_SESSION_LOGS - SESSION_TRIM_BATCH);
+        assert_eq!(session.logs[0].event, "SIP_PACKET");
+        assert_eq!(session.logs.last().map(|l| l.event.as_str()), Some("BYE"));
+    }
+
+    /// `count` kayıtlık oturum; ilk kaydın zamanı `start` (unix sn), kayıtlar birer saniye arayla.
+    fn seed(aggregator: &mut Aggregator, id: &str, start: i64, count: usize, severity: &str) {
+        for i in 0..count {
+            let ts = DateTime::from_timestamp(start + i as i64, 0)
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default();
+            let mut log = record(id, "LOG_EVENT", &ts);
+            log.severity = severity.to_string();
+            aggregator.process(&log);
+        }
+    }
+
+    fn page(
+        sort: SessionSortKey,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> SessionPageRequest {
+        SessionPageRequest {
+            offset,
+            limit,
+            sort,
+            descending,
+        }
+    }
+
+    fn ids(page: &SessionPage) -> Vec<&str> {
+        page.sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn search_pages_through_sorted_sessions() {
+        let mut aggregator = Aggregator::new(100, 60);
+        for i in 0..5 {
+            seed(
+                &mut aggregator,
+                &format!("s{}", i),
+                1_000 - i as i64,
+                i + 1,
+                "INFO",
+            );
+        }
+        let all = SessionFilter::default();
+
+        let pages: Vec<Vec<String>> = [0, 2, 4]
+            .iter()
+            .map(|&offset| {
+                let result =
+                    aggregator.search(&all, page(SessionSortKey::LogsCount, true, offset, 2));
+                assert_eq!(result.total, 5);
+                ids(&result).into_iter().map(String::from).collect()
+            })
+            .collect();
+        assert_eq!(pages, [vec!["s4", "s3"], vec!["s2", "s1"], vec!["s0"]]);
+
+        // Sonu aşan offset boş sayfa döner; toplam değişmez.
+        let beyond = aggregator.search(&all, page(SessionSortKey::LogsCount, true, 5, 2));
+        assert!(beyond.sessions.is_empty());
+        assert_eq!(beyond.total, 5);
+        let empty = aggregator.search(&all, page(SessionSortKey::LogsCount, true, 0, 0));
+        assert!(empty.sessions.is_empty());
+
+        let by_start = aggregator.search(&all, page(SessionSortKey::StartTime, false, 0, 10));
+        assert_eq!(ids(&by_start), ["s4", "s3", "s2", "s1", "s0"]);
+        assert_eq!(by_start.sessions[0].start_ts, 996);
+    }
+
+    #[test]
+    fn equal_sort_keys_are_ordered_by_session_id() {
+        let mut aggregator = Aggregator::new(100, 60);
+        for id in ["b", "c", "a"] {
+            seed(&mut aggregator, id, 1_000, 1, "INFO");
+        }
+        seed(&mut aggregator, "d", 1_000, 1, "ERROR");
+        let all = SessionFilter::default();
+
+        let desc = aggregator.search(&all, page(SessionSortKey::LastUpdate, true, 0, 10));
+        assert_eq!(ids(&desc), ["d", "c", "b", "a"]);
+        let anomalies = aggregator.search(&all, page(SessionSortKey::Anomalies, false, 0, 10));
+        assert_eq!(ids(&anomalies), ["a", "b", "c", "d"]);
+        let first = aggregator.search(&all, page(SessionSortKey::Anomalies, true, 0, 1));
+        assert_eq!(ids(&first), ["d"]);
+    }
+
+    #[test]
+    fn page_limit_is_capped() {
+        let mut aggregator = Aggregator::new(MAX_PAGE_LIMIT * 2, 60);
+        for i in 0..=MAX_PAGE_LIMIT {
+            seed(&mut aggregator, &format!("s{:04}", i), 1_000, 1, "INFO");
+        }
+        let result = aggregator.search(
+            &SessionFilter::default(),
+            page(SessionSortKey::LastUpdate, false, 0, usize::MAX),
+        );
+        assert_eq!(result.total, MAX_PAGE_LIMIT + 1);
+        assert_eq!(result.sessions.len(), MAX_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn search_filters_by_status_service_anomalies_and_time() {
+        let mut aggregator = Aggregator::new(100, 60);
+        seed(&mut aggregator, "ok", 1_000, 2, "INFO");
+        seed(&mut aggregator, "failed", 2_000, 2, "ERROR");
+        let mut bye = record("done", "BYE", "1970-01-01T00:50:00Z");
+        bye.resource.service_name = "proxy".to_string();
+        aggregator.process(&bye);
+
+        let search = |filter: SessionFilter| {
+            let mut found: Vec<String> = aggregator
+                .search(&filter, SessionPageRequest::default())
+                .sessions
+                .into_iter()
+                .map(|s| s.session_id)
+                .collect();
+            found.sort();
+            found
+        };
+        let status = |s: &str| SessionFilter {
+            status: Some(s.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(search(status("failed")), ["failed"]);
+        assert_eq!(search(status("Completed")), ["done"]);
+        assert_eq!(
+            search(SessionFilter {
+                service: Some("PROXY".to_string()),
+                ..Default::default()
+            }),
+            ["done"]
+        );
+        assert_eq!(
+            search(SessionFilter {
+                has_anomalies: Some(false),
+                ..Default::default()
+            }),
+            ["done", "ok"]
+        );
+        assert_eq!(
+            search(SessionFilter {
+                to_ts: Some(1_999),
+                ..Default::default()
+            }),
+            ["ok"]
+        );
+        assert!(search(SessionFilter {
+            from_ts: Some(i64::MAX),
+            ..Default::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn answer_replies_over_the_query_channel() {
+        let mut aggregator = Aggregator::new(100, 60);
+        aggregator.process(&record("c", "LATE", "2024-05-01T10:00:05Z"));
+        aggregator.process(&record("c", "EARLY", "2024-05-01T10:00:01Z"));
+
+        let (reply, mut rx) = tokio::sync::oneshot::channel();
+        aggregator.answer(SessionQuery::Get {
+            session_id: "c".to_string(),
+            reply,
+        });
+        let session = rx.try_recv().expect("yanıt yok").expect("oturum yok");
+        let events: Vec<&str> = session.logs.iter().map(|l| l.event.as_str()).collect();
+        assert_eq!(events, ["EARLY", "LATE"]);
+
+        let (reply, mut rx) = tokio::sync::oneshot::channel();
+        aggregator.answer(SessionQuery::Overview {
+            session_id: "yok".to_string(),
+            reply,
+        });
+        assert!(rx.try_recv().expect("yanıt yok").is_none());
+
+        // Sorgunun sahibi vazgeçtiyse yanıt sessizce düşer.
+        let (reply, rx) = tokio::sync::oneshot::channel();
+        drop(rx);
+        aggregator.answer(SessionQuery::Search {
+            filter: SessionFilter::default(),
+            page: SessionPageRequest::default(),
+            reply,
+        });
+    }
+
+    #[test]
+    fn overview_summarizes_the_call() {
+        let mut aggregator = Aggregator::new(100, 60);
+        let log = |event: &str, ts: &str, service: &str, severity: &str| {
+            let mut log = record("c", event, ts);
+            log.resource.service_name = service.to_string();
+            log.severity = severity.to_string();
+            log
+        };
+
+        let mut invite = log("SIP_PACKET", "2024-05-01T10:00:01Z", "sniffer", "INFO");
+        invite
+            .attributes
+            .insert("sip.method".to_string(), "INVITE".into());
+        // Gecikmeli kaynak: ilk gelen kayıt en erken kayıt değil.
+        aggregator.process(&log("LOG_EVENT", "2024-05-01T10:00:02Z", "proxy", "WARN"));
+        aggregator.process(&invite);
+        for (code, ts) in [
+            (180, "2024-05-01T10:00:02.500Z"),
+            (200, "2024-05-01T10:00:03Z"),
+        ] {
+            let mut response = log("SIP_PACKET", ts, "sniffer", "INFO");
+            response
+                .attributes
+                .insert("sip.status_code".to_string(), code.into());
+            aggregator.process(&response);
+        }
+        let mut digit = log("DTMF_DIGIT", "2024-05-01T10:00:04Z", "sniffer", "INFO");
+        digit
+            .attributes
+            .insert("dtmf.digit".to_string(), "7".into());
+        aggregator.process(&digit);
+        let mut stats = log(
+            "RTP_STREAM_STATS",
+            "2024-05-01T10:00:05Z",
+            "sniffer",
+            "INFO",
+        );
+        stats
+            .attributes
+            .insert("rtp.ssrc".to_string(), "0x1".into());
+        aggregator.process(&stats);
+        aggregator.process(&log("LOG_EVENT", "2024-05-01T10:00:06Z", "media", "ERROR"));
+
+        let overview = aggregator.sessions["c"].overview();
+        assert_eq!(overview.summary.start_time, "2024-05-01T10:00:01+00:00");
+        assert_eq!(overview.end_time, "2024-05-01T10:00:06+00:00");
+        assert_eq!(overview.duration_ms, 5_000);
+        assert_eq!(
+            overview.sip_response_codes.into_iter().collect::<Vec<_>>(),
+            [(180, 1), (200, 1)]
+        );
+        assert_eq!((overview.error_count, overview.warn_count), (1, 1));
+
+        let summary = overview.summary;
+        assert_eq!(summary.logs_count, 7);
+        assert_eq!(summary.status, "Failed");
+        assert_eq!(summary.anomaly_count, 1);
+        assert_eq!(summary.services, ["proxy", "sniffer", "media"]);
+        assert_eq!(summary.dtmf_digits, "7");
+        assert_eq!(summary.media_stream_count, 1);
+    }
+}
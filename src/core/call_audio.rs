@@ -0,0 +1,194 @@
+// src/core/call_audio.rs
+use crate::core::domain::LogRecord;
+use crate::utils::audio::{alaw_to_linear, encode_wav_stereo, ulaw_to_linear};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use lru::LruCache;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+
+/// G.711 her iki varyantta da 8 kHz, örnek başına 1 bayt.
+const SAMPLE_RATE: u32 = 8000;
+
+#[derive(Debug, Clone, Copy)]
+enum Law {
+    Mu,
+    A,
+}
+
+/// Tek bir SSRC'nin ham G.711 çerçeveleri, genişletilmiş sıra numarasına göre.
+#[derive(Clone)]
+struct AudioStream {
+    law: Law,
+    caller_to_callee: bool,
+    first_arrival_us: i64,
+    max_ext_seq: i64,
+    frames: BTreeMap<i64, Vec<u8>>,
+    bytes: usize,
+}
+
+struct CallAudio {
+    streams: HashMap<String, AudioStream>,
+    last_update_ts: i64,
+}
+
+/// Sniffer'ın `RTP_PACKET` kayıtlarındaki `rtp.audio_b64` yüklerini çağrı bazında
+/// biriktirir ve talep üzerine stereo WAV (sol: arayan -> aranan, sağ: aranan -> arayan) üretir.
+/// Tarayıcıdaki halka tampondan bağımsızdır; bellek çağrı sayısı ve süre ile sınırlıdır.
+pub struct CallAudioStore {
+    calls: LruCache<String, CallAudio>,
+    max_bytes_per_stream: usize,
+}
+
+impl CallAudioStore {
+    pub fn new(max_calls: usize, max_seconds: u64) -> Self {
+        Self {
+            calls: LruCache::new(NonZeroUsize::new(max_calls).unwrap_or(NonZeroUsize::MIN)),
+            max_bytes_per_stream: (max_seconds * SAMPLE_RATE as u64) as usize,
+        }
+    }
+
+    pub fn ingest(&mut self, log: &LogRecord) {
+        if log.event != "RTP_PACKET" {
+            return;
+        }
+        let Some(trace_id) = &log.trace_id else {
+            return;
+        };
+        let attrs = &log.attributes;
+        let law = match attrs.get("rtp.payload_type").and_then(|v| v.as_u64()) {
+            Some(0) => Law::Mu,
+            Some(8) => Law::A,
+            _ => return,
+        };
+        let (Some(seq), Some(ssrc), Some(audio)) = (
+            attrs.get("rtp.sequence").and_then(|v| v.as_u64()),
+            attrs.get("rtp.ssrc").and_then(|v| v.as_str()),
+            attrs.get("rtp.audio_b64").and_then(|v| v.as_str()),
+        ) else {
+            return;
+        };
+        let Ok(payload) = STANDARD.decode(audio) else {
+            return;
+        };
+        let Ok(arrival) = chrono::DateTime::parse_from_rfc3339(&log.ts) else {
+            return;
+        };
+        let arrival_us = arrival.timestamp_micros();
+        let caller_to_callee = attrs
+            .get("rtp.direction")
+            .and_then(|v| v.as_str())
+            .is_none_or(|d| d == "caller_to_callee");
+
+        let call = self
+            .calls
+            .get_or_insert_mut(trace_id.clone(), || CallAudio {
+                streams: HashMap::new(),
+                last_update_ts: 0,
+            });
+        call.last_update_ts = chrono::Utc::now().timestamp();
+
+        let stream = call
+            .streams
+            .entry(ssrc.to_string())
+            .or_insert_with(|| AudioStream {
+                law,
+                caller_to_callee,
+                first_arrival_us: arrival_us,
+                max_ext_seq: seq as i64,
+                frames: BTreeMap::new(),
+                bytes: 0,
+            });
+        if stream.bytes + payload.len() > self.max_bytes_per_stream {
+            return;
+        }
+
+        // 16 bit sıra numarası en yüksek görülen değere göre genişletilir (wrap + geç paket).
+        let diff = (seq as u16).wrapping_sub(stream.max_ext_seq as u16) as i16 as i64;
+        let ext_seq = stream.max_ext_seq + diff;
+        if ext_seq < 0 {
+            return;
+        }
+        stream.max_ext_seq = stream.max_ext_seq.max(ext_seq);
+        if let Entry::Vacant(slot) = stream.frames.entry(ext_seq) {
+            stream.bytes += payload.len();
+            slot.insert(payload);
+        }
+    }
+
+    /// Çağrının ham çerçevelerinin kopyası. Kilit yalnızca kopyalama süresince tutulur;
+    /// çözme ve WAV kodlama kilit dışında (`render_wav`) yapılır.
+    pub fn snapshot(&self, trace_id: &str) -> Option<CallAudioSnapshot> {
+        let call = self.calls.peek(trace_id)?;
+        if call.streams.is_empty() {
+            return None;
+        }
+        Some(CallAudioSnapshot {
+            streams: call.streams.values().cloned().collect(),
+            max_samples: self.max_bytes_per_stream * 2,
+        })
+    }
+
+    pub fn cleanup(&mut self, ttl_seconds: i64) {
+        let now = chrono::Utc::now().timestamp();
+        let expired: Vec<String> = self
+            .calls
+            .iter()
+            .filter(|(_, c)| now - c.last_update_ts >= ttl_seconds)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in expired {
+            self.calls.pop(&id);
+        }
+    }
+}
+
+/// `CallAudioStore::snapshot` ile alınan, depodan bağımsız çağrı sesi.
+pub struct CallAudioSnapshot {
+    streams: Vec<AudioStream>,
+    max_samples: usize,
+}
+
+impl CallAudioSnapshot {
+    /// Kayıtlı sesi sıra numarası düzeninde çözer; kayıp paketler yerine bir önceki
+    /// çerçeve boyunda sessizlik koyar. Her SSRC kendi ilk varış anına hizalanır.
+    pub fn render_wav(&self) -> Vec<u8> {
+        let call_start = self
+            .streams
+            .iter()
+            .map(|s| s.first_arrival_us)
+            .min()
+            .unwrap_or_default();
+        let max_samples = self.max_samples;
+
+        let mut channels: [Vec<i16>; 2] = [Vec::new(), Vec::new()];
+        for stream in &self.streams {
+            let channel = &mut channels[if stream.caller_to_callee { 0 } else { 1 }];
+            let offset =
+                ((stream.first_arrival_us - call_start) * SAMPLE_RATE as i64 / 1_000_000) as usize;
+            let mut cursor = offset.min(max_samples);
+            let mut previous: Option<(i64, usize)> = None;
+
+            for (ext_seq, frame) in &stream.frames {
+                if let Some((prev_seq, prev_len)) = previous {
+                    let missing = (ext_seq - prev_seq - 1).max(0) as usize;
+                    cursor = (cursor + missing * prev_len).min(max_samples);
+                }
+                let end = (cursor + frame.len()).min(max_samples);
+                if channel.len() < end {
+                    channel.resize(end, 0);
+                }
+                for (slot, byte) in channel[cursor..end].iter_mut().zip(frame) {
+                    *slot = match stream.law {
+                        Law::Mu => ulaw_to_linear(*byte),
+                        Law::A => alaw_to_linear(*byte),
+                    };
+                }
+                cursor = end;
+                previous = Some((*ext_seq, frame.len()));
+            }
+        }
+
+        encode_wav_stereo(&channels[0], &channels[1], SAMPLE_RATE)
+    }
+}
@@ -0,0 +1,83 @@
+// src/core/call_frames.rs
+use crate::utils::pcapng::PcapngWriter;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+
+/// Yakalandığı haliyle bir ağ çerçevesi (link katmanı başlığı dahil).
+#[derive(Debug, Clone)]
+pub struct RawFrame {
+    pub ts_us: i64,
+    /// libpcap DLT değeri (ör. 1 = Ethernet, 113 = Linux SLL)
+    pub link_type: i32,
+    pub orig_len: u32,
+    pub data: Vec<u8>,
+}
+
+struct CallFrames {
+    frames: Vec<RawFrame>,
+    bytes: usize,
+    last_update_ts: i64,
+}
+
+/// Çağrıya (trace id) ait ham SIP/RTP/RTCP çerçevelerinin sınırlı deposu.
+/// `LogRecord` hattının tamamlayıcısıdır: operatör bir çağrıyı Wireshark'ta açabilsin diye
+/// çerçeveler olduğu gibi saklanır ve talep üzerine pcapng olarak dışa aktarılır.
+pub struct CallFrameStore {
+    calls: LruCache<String, CallFrames>,
+    max_bytes_per_call: usize,
+}
+
+impl CallFrameStore {
+    pub fn new(max_calls: usize, max_bytes_per_call: usize) -> Self {
+        Self {
+            calls: LruCache::new(NonZeroUsize::new(max_calls).unwrap_or(NonZeroUsize::MIN)),
+            max_bytes_per_call,
+        }
+    }
+
+    /// Çerçeve, çağrının bayt bütçesi dolduysa sessizce atılır (SIP akışının başı korunur).
+    pub fn push(&mut self, trace_id: &str, frame: RawFrame) {
+        let call = self
+            .calls
+            .get_or_insert_mut(trace_id.to_string(), || CallFrames {
+                frames: Vec::new(),
+                bytes: 0,
+                last_update_ts: 0,
+            });
+        call.last_update_ts = chrono::Utc::now().timestamp();
+        if call.bytes + frame.data.len() > self.max_bytes_per_call {
+            return;
+        }
+        call.bytes += frame.data.len();
+        call.frames.push(frame);
+    }
+
+    /// Çağrının çerçevelerinin kopyası; pcapng üretimi kilit dışında yapılır.
+    pub fn snapshot(&self, trace_id: &str) -> Option<Vec<RawFrame>> {
+        self.calls.peek(trace_id).map(|call| call.frames.clone())
+    }
+
+    pub fn cleanup(&mut self, ttl_seconds: i64) {
+        let now = chrono::Utc::now().timestamp();
+        let expired: Vec<String> = self
+            .calls
+            .iter()
+            .filter(|(_, c)| now - c.last_update_ts >= ttl_seconds)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in expired {
+            self.calls.pop(&id);
+        }
+    }
+}
+
+/// Çerçeveleri yakalama zamanına göre sıralı bir pcapng dosyası olarak döner.
+pub fn render_pcapng(mut frames: Vec<RawFrame>) -> Vec<u8> {
+    frames.sort_by_key(|f| f.ts_us);
+
+    let mut writer = PcapngWriter::new();
+    for frame in &frames {
+        writer.write_packet(frame.link_type, frame.ts_us, frame.orig_len, &frame.data);
+    }
+    writer.finish()
+}
@@ -115,6 +115,13 @@ impl LogRecord {
         }
     }
 
+    /// `ts` alanının unix milisaniye karşılığı; RFC 3339 değilse `None`.
+    pub fn ts_millis(&self) -> Option<i64> {
+        chrono::DateTime::parse_from_rfc3339(&self.ts)
+            .ok()
+            .map(|ts| ts.timestamp_millis())
+    }
+
     pub fn sanitize_and_enrich(&mut self) {
         if self.message.trim().starts_with('{') {
             if let Ok(parsed) = serde_json::from_str::<HashMap<String, Value>>(&self.message) {
@@ -0,0 +1,70 @@
+// src/core/log_query.rs
+use crate::core::domain::LogRecord;
+
+pub const DEFAULT_LOG_QUERY_LIMIT: usize = 500;
+pub const MAX_LOG_QUERY_LIMIT: usize = 5000;
+
+/// Kalıcı log deposu sorgusu; `None` / boş alanlar filtre uygulamaz.
+/// Zaman sınırları kaydın `ts` alanına göre, unix milisaniye ve kapalı aralıktır.
+#[derive(Debug, Clone)]
+pub struct LogQuery {
+    pub from_ms: Option<i64>,
+    pub to_ms: Option<i64>,
+    pub tenant_id: Option<String>,
+    pub service: Option<String>,
+    /// Değerlerden biri eşleşmeli (büyük/küçük harf duyarsız)
+    pub severities: Vec<String>,
+    pub trace_id: Option<String>,
+    pub limit: usize,
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            from_ms: None,
+            to_ms: None,
+            tenant_id: None,
+            service: None,
+            severities: Vec::new(),
+            trace_id: None,
+            limit: DEFAULT_LOG_QUERY_LIMIT,
+        }
+    }
+}
+
+impl LogQuery {
+    pub fn has_time_bounds(&self) -> bool {
+        self.from_ms.is_some() || self.to_ms.is_some()
+    }
+
+    /// `[min_ms, max_ms]` aralığındaki kayıtları taşıyan bir bölüm sorguyla kesişiyor mu?
+    pub fn overlaps(&self, min_ms: i64, max_ms: i64) -> bool {
+        self.from_ms.is_none_or(|from| max_ms >= from) && self.to_ms.is_none_or(|to| min_ms <= to)
+    }
+
+    /// Zaman damgası çözülemeyen kayıtlar yalnızca zaman sınırı olmayan sorgularda döner.
+    pub fn matches(&self, log: &LogRecord, ts_ms: Option<i64>) -> bool {
+        let in_window = match ts_ms {
+            Some(ts) => self.overlaps(ts, ts),
+            None => !self.has_time_bounds(),
+        };
+        in_window
+            && self
+                .tenant_id
+                .as_ref()
+                .is_none_or(|tenant| &log.tenant_id == tenant)
+            && self
+                .service
+                .as_ref()
+                .is_none_or(|service| log.resource.service_name.eq_ignore_ascii_case(service))
+            && (self.severities.is_empty()
+                || self
+                    .severities
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(&log.severity)))
+            && self
+                .trace_id
+                .as_ref()
+                .is_none_or(|trace| log.trace_id.as_ref() == Some(trace))
+    }
+}
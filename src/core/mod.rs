@@ -1,2 +1,6 @@
 pub mod aggregator;
+pub mod call_audio;
+pub mod call_frames;
 pub mod domain; // <--- EKLENDİ
+pub mod log_query;
+pub mod session_query;
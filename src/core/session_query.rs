@@ -0,0 +1,165 @@
+// src/core/session_query.rs
+use crate::core::aggregator::CallSession;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tokio::sync::{mpsc, oneshot};
+
+/// Tek sayfada döndürülebilecek en fazla oturum.
+pub const MAX_PAGE_LIMIT: usize = 500;
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// Oturum arama ölçütleri; `None` alanlar filtre uygulamaz.
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilter {
+    /// `Active` / `Failed` / `Completed` (büyük/küçük harf duyarsız)
+    pub status: Option<String>,
+    /// Bu andan (unix sn) sonra etkinliği olan oturumlar
+    pub from_ts: Option<i64>,
+    /// Bu andan (unix sn) önce başlamış oturumlar
+    pub to_ts: Option<i64>,
+    /// Oturumda bu servisten en az bir kayıt bulunmalı
+    pub service: Option<String>,
+    pub has_anomalies: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionSortKey {
+    #[default]
+    LastUpdate,
+    StartTime,
+    LogsCount,
+    Anomalies,
+}
+
+impl SessionSortKey {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "last_update" => Some(Self::LastUpdate),
+            "start_time" => Some(Self::StartTime),
+            "logs_count" => Some(Self::LogsCount),
+            "anomalies" => Some(Self::Anomalies),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SessionPageRequest {
+    pub offset: usize,
+    pub limit: usize,
+    pub sort: SessionSortKey,
+    pub descending: bool,
+}
+
+impl Default for SessionPageRequest {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: DEFAULT_PAGE_LIMIT,
+            sort: SessionSortKey::LastUpdate,
+            descending: true,
+        }
+    }
+}
+
+/// Kayıtları taşımayan oturum özeti (liste ve arama yanıtları).
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub start_time: String,
+    pub start_ts: i64,
+    pub last_update_ts: i64,
+    pub logs_count: usize,
+    pub status: String,
+    pub anomaly_count: usize,
+    pub services: Vec<String>,
+    pub dtmf_digits: String,
+    pub media_stream_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionPage {
+    /// Filtreye uyan toplam oturum (sayfalamadan önce)
+    pub total: usize,
+    pub sessions: Vec<SessionSummary>,
+}
+
+/// Tek oturumun süre, servis, SIP yanıt kodu ve hata dökümü.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionOverview {
+    #[serde(flatten)]
+    pub summary: SessionSummary,
+    /// Son kaydın zaman damgası (RFC 3339)
+    pub end_time: String,
+    /// İlk ve son kayıt arasındaki süre
+    pub duration_ms: i64,
+    /// SIP yanıt kodu -> görülme sayısı
+    pub sip_response_codes: BTreeMap<u16, usize>,
+    pub error_count: usize,
+    pub warn_count: usize,
+}
+
+/// Aggregator görevine gönderilen sorgu; yanıt `reply` kanalından döner.
+pub enum SessionQuery {
+    Search {
+        filter: SessionFilter,
+        page: SessionPageRequest,
+        reply: oneshot::Sender<SessionPage>,
+    },
+    Get {
+        session_id: String,
+        reply: oneshot::Sender<Option<CallSession>>,
+    },
+    Overview {
+        session_id: String,
+        reply: oneshot::Sender<Option<SessionOverview>>,
+    },
+}
+
+/// Aggregator yalnızca kendi görevinde erişilebilir; diğer bileşenler oturumları bu
+/// tutamaç üzerinden istek/yanıt kanalıyla okur. Aggregator kapandıysa `None` döner.
+#[derive(Clone)]
+pub struct SessionQueryHandle {
+    tx: mpsc::Sender<SessionQuery>,
+}
+
+impl SessionQueryHandle {
+    pub fn new(tx: mpsc::Sender<SessionQuery>) -> Self {
+        Self { tx }
+    }
+
+    pub async fn search(
+        &self,
+        filter: SessionFilter,
+        page: SessionPageRequest,
+    ) -> Option<SessionPage> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(SessionQuery::Search {
+                filter,
+                page,
+                reply,
+            })
+            .await
+            .ok()?;
+        rx.await.ok()
+    }
+
+    pub async fn get(&self, session_id: String) -> Option<Option<CallSession>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(SessionQuery::Get { session_id, reply })
+            .await
+            .ok()?;
+        rx.await.ok()
+    }
+
+    pub async fn overview(&self, session_id: String) -> Option<Option<SessionOverview>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(SessionQuery::Overview { session_id, reply })
+            .await
+            .ok()?;
+        rx.await.ok()
+    }
+}
@@ -8,11 +8,14 @@ mod utils;
 
 use crate::config::AppConfig;
 use crate::core::aggregator::Aggregator;
+use crate::core::call_audio::CallAudioStore;
+use crate::core::call_frames::CallFrameStore;
 use crate::core::domain::LogRecord;
-use crate::ports::LogIngestor;
+use crate::core::session_query::{SessionQuery, SessionQueryHandle};
+use crate::ports::{LogIngestor, LogStore};
 use std::net::SocketAddr;
 use std::sync::{atomic::AtomicBool, Arc};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, Mutex};
 use tracing::{error, info};
 
 #[tokio::main]
@@ -36,13 +39,85 @@ async fn main() -> anyhow::Result<()> {
 
     let (ingest_tx, mut ingest_rx) = mpsc::channel::<LogRecord>(50000);
     let (ui_tx, _) = broadcast::channel::<LogRecord>(50000);
+    let (session_query_tx, mut session_query_rx) = mpsc::channel::<SessionQuery>(256);
+    let session_queries = SessionQueryHandle::new(session_query_tx);
 
     let sniffer_active = Arc::new(AtomicBool::new(cfg.sniffer_enabled));
 
-    // --- 3. AGGREGATOR TASK ---
+    // Kalıcı log deposu: açılamazsa servis bellekte çalışmaya devam eder.
+    let mut store_tx: Option<mpsc::Sender<LogRecord>> = None;
+    let mut log_store: Option<Arc<dyn LogStore>> = None;
+    if cfg.store_enabled {
+        let settings = adapters::segment_store::StoreSettings {
+            dir: cfg.store_dir.clone().into(),
+            retention_hours: cfg.store_retention_hours,
+            max_bytes: cfg.store_max_size_mb * 1024 * 1024,
+            segment_max_bytes: cfg.store_segment_max_mb * 1024 * 1024,
+        };
+        match adapters::segment_store::SegmentStore::open(settings) {
+            Ok(store) => {
+                let (tx, rx) = mpsc::channel::<LogRecord>(50000);
+                store.start(rx);
+                store_tx = Some(tx);
+                log_store = Some(Arc::new(store));
+            }
+            Err(e) => {
+                error!(event="STORE_OPEN_FAIL", error=%e, "Kalıcı Log Deposu Açılamadı, Kayıtlar Yalnızca Bellekte Tutulacak");
+            }
+        }
+    }
+
+    // --- 3. INGEST STAGE ---
+    // Kayıtlar aggregator'dan önce burada normalize edilir ve depoya yazılır. Disk yavaşsa
+    // yalnızca bu görev bekler (baskı ingest kaynaklarına yansır); aggregator döngüsü
+    // oturum sorgularına, temizliğe ve UI yayınına devam eder.
+    let (aggregate_tx, mut aggregate_rx) = mpsc::channel::<LogRecord>(50000);
+    tokio::spawn(async move {
+        while let Some(mut log) = ingest_rx.recv().await {
+            log._idx = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_micros() as f64
+                / 1000.0;
+
+            if log.trace_id.is_none() {
+                if let Some(call_id_val) = log.attributes.get("sip.call_id") {
+                    if let Some(s) = call_id_val.as_str() {
+                        log.trace_id = Some(s.to_string());
+                    }
+                }
+            }
+
+            if let Some(tx) = &store_tx {
+                // Kayıt atılmaz: kuyruk doluysa disk yetişene kadar ingest bekler.
+                if tx.send(log.clone()).await.is_err() {
+                    error!(
+                        event = "STORE_WRITER_GONE",
+                        "Log deposu yazıcısı kapandı, kayıtlar artık diske yazılmıyor"
+                    );
+                    store_tx = None;
+                }
+            }
+            if aggregate_tx.send(log).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // --- 4. AGGREGATOR TASK ---
     let aggregator_ui_tx = ui_tx.clone();
     let max_sessions = cfg.max_active_sessions;
     let ttl_seconds = cfg.session_ttl_seconds;
+    let call_audio = Arc::new(Mutex::new(CallAudioStore::new(
+        cfg.call_audio_max_calls,
+        cfg.call_audio_max_seconds,
+    )));
+    let aggregator_call_audio = call_audio.clone();
+    let call_frames = Arc::new(Mutex::new(CallFrameStore::new(
+        cfg.call_capture_max_calls,
+        cfg.call_capture_max_bytes,
+    )));
+    let aggregator_call_frames = call_frames.clone();
 
     tokio::spawn(async move {
         let mut aggregator = Aggregator::new(max_sessions, ttl_seconds);
@@ -50,19 +125,21 @@ async fn main() -> anyhow::Result<()> {
 
         loop {
             tokio::select! {
-                Some(mut log) = ingest_rx.recv() => {
-                    log._idx = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_micros() as f64 / 1000.0;
-
-                    if log.trace_id.is_none() {
-                        if let Some(call_id_val) = log.attributes.get("sip.call_id") {
-                             if let Some(s) = call_id_val.as_str() { log.trace_id = Some(s.to_string()); }
-                        }
-                    }
-
+                Some(log) = aggregate_rx.recv() => {
                     aggregator.process(&log);
+                    if log.event == "RTP_PACKET" {
+                        aggregator_call_audio.lock().await.ingest(&log);
+                    }
                     let _ = aggregator_ui_tx.send(log);
                 }
-                _ = cleanup_interval.tick() => { aggregator.cleanup(); }
+                Some(query) = session_query_rx.recv() => {
+                    aggregator.answer(query);
+                }
+                _ = cleanup_interval.tick() => {
+                    aggregator.cleanup();
+                    aggregator_call_audio.lock().await.cleanup(ttl_seconds);
+                    aggregator_call_frames.lock().await.cleanup(ttl_seconds);
+                }
             }
         }
     });
@@ -72,7 +149,7 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or("unknown".into());
     let global_tenant_id = cfg.tenant_id.clone();
 
-    // --- 4. INGESTION ADAPTERS ---
+    // --- 5. INGESTION ADAPTERS ---
 
     // A. Sniffer
     let sniffer_tx = ingest_tx.clone();
@@ -81,6 +158,7 @@ async fn main() -> anyhow::Result<()> {
     let filter = cfg.sniffer_filter.clone();
     let sniffer_node = node_name.clone();
     let sniffer_tenant = global_tenant_id.clone();
+    let sniffer_frames = call_frames.clone();
 
     #[cfg(any(target_os = "linux", target_os = "macos"))]
     tokio::spawn(async move {
@@ -91,6 +169,7 @@ async fn main() -> anyhow::Result<()> {
             sniffer_node,
             sniffer_flag,
             sniffer_tenant,
+            sniffer_frames,
         );
         if let Err(e) = sniffer.start().await {
             error!(event="SNIFFER_START_FAIL", error=%e, "Sniffer Başlatılamadı");
@@ -102,6 +181,25 @@ async fn main() -> anyhow::Result<()> {
     let docker_socket = cfg.docker_socket.clone();
     let node_clone = node_name.clone();
     let docker_tenant = global_tenant_id.clone();
+    let docker_labels = cfg.docker_label_mappings.clone();
+    let docker_rules = cfg.docker_container_rules.clone();
+    let docker_min_severity = cfg.docker_min_severity.clone();
+    let docker_multiline = if cfg.docker_multiline_enabled {
+        match utils::multiline::MultilineRules::new(
+            &cfg.docker_multiline_start,
+            &cfg.docker_multiline_continuation,
+            cfg.docker_multiline_flush_ms,
+            cfg.docker_multiline_max_lines,
+        ) {
+            Ok(rules) => Some(rules),
+            Err(e) => {
+                error!(event="MULTILINE_PATTERN_INVALID", error=%e, "Multiline Deseni Geçersiz, Satır Birleştirme Kapalı");
+                None
+            }
+        }
+    } else {
+        None
+    };
 
     tokio::spawn(async move {
         if let Ok(ingestor) = adapters::docker::DockerIngestor::new(
@@ -109,12 +207,74 @@ async fn main() -> anyhow::Result<()> {
             docker_tx,
             node_clone,
             docker_tenant,
+            docker_labels,
+            adapters::docker_filter::ContainerFilter::parse(&docker_rules, &docker_min_severity),
+            docker_multiline,
         ) {
             let _ = ingestor.start().await;
         }
     });
 
-    // C. gRPC Server
+    // C. Log Dosyası Takibi (Kamailio, rtpengine gibi çıplak metal servisler)
+    if !cfg.file_tail_paths.trim().is_empty() {
+        let file_tail = adapters::file_tail::FileTailIngestor::new(
+            &cfg.file_tail_paths,
+            &cfg.file_tail_state_path,
+            cfg.file_tail_poll_ms,
+            ingest_tx.clone(),
+            node_name.clone(),
+            global_tenant_id.clone(),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = file_tail.start().await {
+                error!(event="FILE_TAIL_FAIL", error=%e, "File Tail Ingestor Hatası");
+            }
+        });
+    }
+
+    // D. Syslog Receiver (Kamailio, FreeSWITCH, SBC cihazları)
+    if cfg.syslog_enabled {
+        let port_addr = |port: u16| (port != 0).then(|| SocketAddr::from(([0, 0, 0, 0], port)));
+        let syslog = adapters::syslog::SyslogIngestor::new(
+            port_addr(cfg.syslog_udp_port),
+            port_addr(cfg.syslog_tcp_port),
+            ingest_tx.clone(),
+            node_name.clone(),
+            global_tenant_id.clone(),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = syslog.start().await {
+                error!(event="SYSLOG_START_FAIL", error=%e, "Syslog Receiver Başlatılamadı");
+            }
+        });
+    }
+
+    // E. Offline Pcap Replay
+    if let Some(replay_file) = cfg.pcap_replay_file.clone() {
+        let replay_tx = ingest_tx.clone();
+        let replay_filter = cfg.pcap_replay_filter.clone();
+        let replay_realtime = cfg.pcap_replay_realtime;
+        let replay_node = node_name.clone();
+        let replay_tenant = global_tenant_id.clone();
+        let replay_frames = call_frames.clone();
+
+        tokio::spawn(async move {
+            let ingestor = adapters::pcap_file::PcapFileIngestor::new(
+                &replay_file,
+                &replay_filter,
+                replay_realtime,
+                replay_tx,
+                replay_node,
+                replay_tenant,
+                replay_frames,
+            );
+            if let Err(e) = ingestor.start().await {
+                error!(event="PCAP_REPLAY_FAIL", error=%e, "Pcap Replay Başlatılamadı");
+            }
+        });
+    }
+
+    // F. gRPC Server
     let grpc_tx = ingest_tx.clone();
     let grpc_addr = SocketAddr::from(([0, 0, 0, 0], cfg.grpc_port));
 
@@ -122,11 +282,15 @@ async fn main() -> anyhow::Result<()> {
     let tls_key = cfg.tls_key_path.clone();
     let tls_ca = cfg.tls_ca_path.clone();
     let grpc_tenant = global_tenant_id.clone();
+    let grpc_live = ui_tx.clone();
+    let grpc_sessions = session_queries.clone();
 
     tokio::spawn(async move {
         let state = api::grpc::GrpcServerState {
             tx: grpc_tx,
+            live: grpc_live,
             tenant_id: grpc_tenant,
+            sessions: grpc_sessions,
         };
 
         // [ARCH-COMPLIANCE] mTLS Failure Policy: Silent degradation YASAKTIR. Bail fırlatılır.
@@ -172,10 +336,10 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
-    // --- 5. UPSTREAM EXPORT ---
+    // --- 6. UPSTREAM EXPORT ---
     if !cfg.upstream_url.is_empty() {
         info!(event="UPSTREAM_MODE_ACTIVE", url=%cfg.upstream_url, "🚀 OMNISCIENT MODE: Upstream aktif");
-        let mut export_manager = adapters::exporter::ExportManager::new(50, 2);
+        let mut export_manager = adapters::exporter::ExportManager::new(500, 2);
 
         export_manager.register_emitter(Arc::new(adapters::grpc_client::GrpcEmitter::new(
             cfg.upstream_url.clone(),
@@ -200,10 +364,14 @@ async fn main() -> anyhow::Result<()> {
         export_manager.start(bridge_rx);
     }
 
-    // --- 6. API & UI ---
+    // --- 7. API & UI ---
     let app_state = Arc::new(api::routes::AppState {
         tx: ui_tx,
         sniffer_active,
+        call_audio,
+        call_frames,
+        sessions: session_queries,
+        log_store,
         config: cfg.clone(),
     });
 
@@ -1,5 +1,6 @@
 // src/ports/mod.rs
 use crate::core::domain::LogRecord;
+use crate::core::log_query::LogQuery;
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -15,3 +16,10 @@ pub trait LogEmitter: Send + Sync {
     async fn emit_batch(&self, logs: Vec<LogRecord>) -> Result<()>;
     fn name(&self) -> &'static str;
 }
+
+/// Kalıcı log deposu: yazma yolu adaptöre özgüdür, API yalnızca sorgular.
+#[async_trait]
+pub trait LogStore: Send + Sync {
+    /// Eşleşen en yeni `query.limit` kayıt, en yeniden eskiye.
+    async fn query(&self, query: LogQuery) -> Result<Vec<LogRecord>>;
+}
@@ -0,0 +1,63 @@
+// src/utils/audio.rs
+
+/// ITU-T G.711 A-law örneğini 16 bit lineer PCM'e çevirir.
+pub fn alaw_to_linear(sample: u8) -> i16 {
+    let a = sample ^ 0x55;
+    let exponent = (a & 0x70) >> 4;
+    let mantissa = (a & 0x0F) as i16;
+    let magnitude = match exponent {
+        0 => (mantissa << 4) + 8,
+        _ => ((mantissa << 4) + 0x108) << (exponent - 1),
+    };
+    if a & 0x80 != 0 {
+        magnitude
+    } else {
+        -magnitude
+    }
+}
+
+/// ITU-T G.711 µ-law örneğini 16 bit lineer PCM'e çevirir.
+pub fn ulaw_to_linear(sample: u8) -> i16 {
+    let u = !sample;
+    let exponent = (u & 0x70) >> 4;
+    let mantissa = (u & 0x0F) as i16;
+    let magnitude = (((mantissa << 3) + 0x84) << exponent) - 0x84;
+    if u & 0x80 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// 16 bit PCM, iki kanallı (sol/sağ) RIFF WAVE dosyası üretir.
+/// Kısa kalan kanal sessizlikle tamamlanır.
+pub fn encode_wav_stereo(left: &[i16], right: &[i16], sample_rate: u32) -> Vec<u8> {
+    const CHANNELS: u16 = 2;
+    const BITS_PER_SAMPLE: u16 = 16;
+
+    let frames = left.len().max(right.len());
+    let block_align = CHANNELS * BITS_PER_SAMPLE / 8;
+    let data_len = (frames * block_align as usize) as u32;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&CHANNELS.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for i in 0..frames {
+        wav.extend_from_slice(&left.get(i).copied().unwrap_or(0).to_le_bytes());
+        wav.extend_from_slice(&right.get(i).copied().unwrap_or(0).to_le_bytes());
+    }
+    wav
+}
@@ -0,0 +1,105 @@
+// src/utils/glob.rs
+use std::path::{Path, PathBuf};
+
+/// `*` (herhangi bir dizi) ve `?` (tek karakter) destekleyen basit glob eşleştirici.
+/// Karakter sınıfları (`[abc]`) desteklenmez; büyük/küçük harf duyarlıdır.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Son görülen `*` konumu ve o noktada metinde kalınan yer (geri izleme için)
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Glob desenini dosya sistemindeki mevcut dosyalara açar (ör. `/var/log/kamailio/*.log`).
+/// Joker karakterler her yol bileşeninde kullanılabilir ancak bileşen sınırını aşmaz;
+/// `**` desteklenmez. Sonuç yalnızca normal dosyaları içerir ve sıralıdır.
+pub fn expand_paths(pattern: &str) -> Vec<PathBuf> {
+    let mut matches = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !part.contains(['*', '?']) {
+            for path in &mut matches {
+                path.push(component);
+            }
+            continue;
+        }
+        matches = matches
+            .iter()
+            .flat_map(|dir| {
+                let read_from = if dir.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    dir.as_path()
+                };
+                std::fs::read_dir(read_from)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| glob_match(&part, &entry.file_name().to_string_lossy()))
+                    .map(|entry| dir.join(entry.file_name()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    matches.retain(|path| path.is_file());
+    matches.sort();
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_wildcards_with_backtracking() {
+        assert!(glob_match("*.log", "kamailio.log"));
+        assert!(glob_match("kam*lio-?.log", "kamailio-1.log"));
+        assert!(glob_match("*a*b", "xaab"));
+        assert!(glob_match("**", ""));
+        assert!(!glob_match("*.log", "kamailio.log.1"));
+        assert!(!glob_match("?", ""));
+        assert!(!glob_match("Kam*", "kamailio"));
+    }
+
+    #[test]
+    fn expands_only_matching_files_in_order() {
+        let dir = std::env::temp_dir().join(format!("observer-glob-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("b.log")).expect("dizin oluşturulamadı");
+        for name in ["c.log", "a.log", "a.txt"] {
+            std::fs::write(dir.join(name), b"").expect("dosya yazılamadı");
+        }
+
+        let pattern = format!("{}/*.log", dir.display());
+        assert_eq!(
+            expand_paths(&pattern),
+            vec![dir.join("a.log"), dir.join("c.log")]
+        );
+        assert!(expand_paths(&format!("{}/missing/*.log", dir.display())).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
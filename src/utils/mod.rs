@@ -1 +1,5 @@
+pub mod audio;
+pub mod glob;
+pub mod multiline;
 pub mod parser;
+pub mod pcapng;
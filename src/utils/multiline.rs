@@ -0,0 +1,289 @@
+// src/utils/multiline.rs
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Bir satırın yeni kayıt mı yoksa önceki kaydın devamı mı olduğuna karar veren kurallar.
+/// `start` ile eşleşip `continuation` ile eşleşmeyen satır yeni kayıt başlatır.
+#[derive(Debug, Clone)]
+pub struct MultilineRules {
+    start: Regex,
+    continuation: Option<Regex>,
+    flush_after: Duration,
+    max_lines: usize,
+}
+
+impl MultilineRules {
+    pub fn new(
+        start: &str,
+        continuation: &str,
+        flush_after_ms: u64,
+        max_lines: usize,
+    ) -> Result<Self, regex::Error> {
+        Ok(Self {
+            start: Regex::new(start)?,
+            continuation: if continuation.is_empty() {
+                None
+            } else {
+                Some(Regex::new(continuation)?)
+            },
+            flush_after: Duration::from_millis(flush_after_ms),
+            max_lines: max_lines.max(1),
+        })
+    }
+
+    /// Aynı ayarlarla yalnızca başlangıç desenini değiştirir (container etiketi ile override).
+    pub fn with_start(&self, start: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            start: Regex::new(start)?,
+            ..self.clone()
+        })
+    }
+
+    fn is_start(&self, line: &str) -> bool {
+        self.start.is_match(line) && !self.continuation.as_ref().is_some_and(|c| c.is_match(line))
+    }
+}
+
+/// Birleştirilmiş kayıt metni ve kaç satırdan oluştuğu.
+#[derive(Debug)]
+pub struct JoinedRecord {
+    pub text: String,
+    pub stream: &'static str,
+    pub lines: usize,
+    /// Kaynağın bildirdiği ilk satır zamanı (ör. Docker log zaman damgası)
+    pub ts: Option<DateTime<Utc>>,
+    /// İlk satırın okunduğu an
+    pub observed_at: DateTime<Utc>,
+}
+
+struct Pending {
+    lines: Vec<String>,
+    ts: Option<DateTime<Utc>>,
+    observed_at: DateTime<Utc>,
+    last_update: Instant,
+}
+
+/// Akış (stdout/stderr) başına devam satırlarını tek kayıtta toplayan birleştirici.
+/// Kayıt, bir sonraki başlangıç satırı geldiğinde veya `flush_after` süresince yeni satır
+/// gelmediğinde tamamlanır. Kural verilmezse her satır ayrı kayıttır.
+pub struct MultilineAssembler {
+    rules: Option<MultilineRules>,
+    pending: HashMap<&'static str, Pending>,
+}
+
+impl MultilineAssembler {
+    pub fn new(rules: Option<MultilineRules>) -> Self {
+        Self {
+            rules,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        stream: &'static str,
+        line: &str,
+        ts: Option<DateTime<Utc>>,
+        now: Instant,
+    ) -> Vec<JoinedRecord> {
+        let line = line.trim_end();
+        let observed_at = Utc::now();
+        let (is_start, max_lines) = match &self.rules {
+            Some(rules) => (rules.is_start(line), rules.max_lines),
+            None if line.trim().is_empty() => return Vec::new(),
+            None => {
+                return vec![JoinedRecord {
+                    text: line.to_string(),
+                    stream,
+                    lines: 1,
+                    ts,
+                    observed_at,
+                }]
+            }
+        };
+
+        let mut done = Vec::new();
+        let trimmed = line.trim();
+        // Tam bir JSON satırı (SUTS) her zaman tek başına bir kayıttır.
+        let standalone = trimmed.starts_with('{') && trimmed.ends_with('}');
+
+        if standalone || is_start || !self.pending.contains_key(stream) {
+            done.extend(self.take(stream));
+            if trimmed.is_empty() {
+                return done;
+            }
+            if standalone {
+                done.push(JoinedRecord {
+                    text: line.to_string(),
+                    stream,
+                    lines: 1,
+                    ts,
+                    observed_at,
+                });
+                return done;
+            }
+            self.pending.insert(
+                stream,
+                Pending {
+                    lines: vec![line.to_string()],
+                    ts,
+                    observed_at,
+                    last_update: now,
+                },
+            );
+        } else if let Some(pending) = self.pending.get_mut(stream) {
+            pending.lines.push(line.to_string());
+            pending.last_update = now;
+        }
+
+        if self
+            .pending
+            .get(stream)
+            .is_some_and(|p| p.lines.len() >= max_lines)
+        {
+            done.extend(self.take(stream));
+        }
+        done
+    }
+
+    /// En erken zaman aşımına uğrayacak bekleyen kaydın süresi.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let flush_after = self.rules.as_ref()?.flush_after;
+        self.pending
+            .values()
+            .map(|p| p.last_update + flush_after)
+            .min()
+    }
+
+    pub fn flush_expired(&mut self, now: Instant) -> Vec<JoinedRecord> {
+        let Some(flush_after) = self.rules.as_ref().map(|r| r.flush_after) else {
+            return Vec::new();
+        };
+        let expired: Vec<&'static str> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now >= p.last_update + flush_after)
+            .map(|(stream, _)| *stream)
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|stream| self.take(stream))
+            .collect()
+    }
+
+    pub fn flush_all(&mut self) -> Vec<JoinedRecord> {
+        let streams: Vec<&'static str> = self.pending.keys().copied().collect();
+        streams
+            .into_iter()
+            .filter_map(|stream| self.take(stream))
+            .collect()
+    }
+
+    fn take(&mut self, stream: &'static str) -> Option<JoinedRecord> {
+        let pending = self.pending.remove(stream)?;
+        // Sondaki boş devam satırları kayda dahil edilmez.
+        let mut lines = pending.lines;
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        Some(JoinedRecord {
+            lines: lines.len(),
+            text: lines.join("\n"),
+            stream,
+            ts: pending.ts,
+            observed_at: pending.observed_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assembler(max_lines: usize) -> MultilineAssembler {
+        let rules = MultilineRules::new(r"^\d{4}-\d{2}-\d{2}", r"^\s", 500, max_lines)
+            .expect("geçersiz desen");
+        MultilineAssembler::new(Some(rules))
+    }
+
+    #[test]
+    fn joins_continuation_lines_until_next_start() {
+        let mut joiner = assembler(100);
+        let now = Instant::now();
+        assert!(joiner
+            .push("stderr", "2024-01-01 panic: boom", None, now)
+            .is_empty());
+        assert!(joiner
+            .push("stderr", "goroutine 1 [running]:", None, now)
+            .is_empty());
+        assert!(joiner.push("stderr", "  main.go:10", None, now).is_empty());
+        assert!(joiner.push("stderr", "", None, now).is_empty());
+
+        let done = joiner.push("stderr", "2024-01-01 next", None, now);
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].lines, 3);
+        assert_eq!(
+            done[0].text,
+            "2024-01-01 panic: boom\ngoroutine 1 [running]:\n  main.go:10"
+        );
+
+        let rest = joiner.flush_all();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].text, "2024-01-01 next");
+    }
+
+    #[test]
+    fn json_lines_and_streams_stay_separate() {
+        let mut joiner = assembler(100);
+        let now = Instant::now();
+        assert!(joiner.push("stdout", "2024-01-01 a", None, now).is_empty());
+        assert!(joiner.push("stderr", "2024-01-01 b", None, now).is_empty());
+
+        let done = joiner.push("stdout", r#"{"event":"X"}"#, None, now);
+        let texts: Vec<&str> = done.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(texts, ["2024-01-01 a", r#"{"event":"X"}"#]);
+        assert_eq!(joiner.flush_all()[0].stream, "stderr");
+    }
+
+    #[test]
+    fn flushes_on_timeout_and_line_limit() {
+        let mut joiner = assembler(2);
+        let now = Instant::now();
+        assert!(joiner.push("stdout", "2024-01-01 a", None, now).is_empty());
+        assert_eq!(
+            joiner.next_deadline(),
+            Some(now + Duration::from_millis(500))
+        );
+        assert!(joiner.flush_expired(now).is_empty());
+        let expired = joiner.flush_expired(now + Duration::from_millis(500));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(joiner.next_deadline(), None);
+
+        assert!(joiner.push("stdout", "2024-01-01 b", None, now).is_empty());
+        let full = joiner.push("stdout", "  devam", None, now);
+        assert_eq!(full.len(), 1);
+        assert_eq!(full[0].lines, 2);
+    }
+
+    #[test]
+    fn without_rules_every_non_empty_line_is_a_record() {
+        let mut joiner = MultilineAssembler::new(None);
+        let now = Instant::now();
+        assert!(joiner.push("stdout", "   ", None, now).is_empty());
+        let done = joiner.push("stdout", "  indented  ", None, now);
+        assert_eq!(done[0].text, "  indented");
+        assert_eq!(joiner.next_deadline(), None);
+    }
+
+    #[test]
+    fn with_start_overrides_only_the_start_pattern() {
+        let rules = MultilineRules::new("^A", r"^\s", 500, 10).expect("geçersiz desen");
+        let custom = rules.with_start("^B").expect("geçersiz desen");
+        assert!(custom.is_start("B line"));
+        assert!(!custom.is_start("A line"));
+        assert!(!custom.is_start(" B"));
+        assert!(MultilineRules::new("(", "", 500, 10).is_err());
+    }
+}
@@ -5,6 +5,11 @@ lazy_static! {
     // Bu artık kullanılmıyor ancak gelecekte ham SIP ayrıştırma için
     // gerekebilir diye bırakılabilir. Ancak mevcut uyarıyı gidermek adına siliyoruz.
     // static ref CALL_ID_REGEX: Regex = Regex::new(r"(?i)Call-ID:\s*([^\s\r\n]+)").unwrap();
+
+    static ref FREE_TEXT_CALL_ID: Regex = Regex::new(
+        r#"(?i)(?:\bcall-id:\s*|\bcall[-_]?id\s*[=:]\s*|\bci=)<?([^\s;,'"<>()\[\]]+)"#
+    )
+    .expect("FREE_TEXT_CALL_ID deseni geçersiz");
 }
 
 // Artık sadece bu fonksiyon kullanılıyor.
@@ -12,3 +17,12 @@ pub fn clean_ansi(text: &str) -> String {
     let re = Regex::new(r"\x1b\[[0-9;]*[mK]").unwrap();
     re.replace_all(text, "").trim().to_string()
 }
+
+/// Serbest metin loglarındaki (Kamailio `xlog`, SBC syslog satırları) SIP Call-ID değeri.
+/// `Call-ID: x`, `callid=x`, `call_id=x` ve Kamailio'nun `ci=x` biçimleri tanınır.
+pub fn extract_call_id(text: &str) -> Option<String> {
+    FREE_TEXT_CALL_ID
+        .captures(text)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+}
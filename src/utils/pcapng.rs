@@ -0,0 +1,70 @@
+// src/utils/pcapng.rs
+
+const BLOCK_SHB: u32 = 0x0A0D_0D0A;
+const BLOCK_IDB: u32 = 0x0000_0001;
+const BLOCK_EPB: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// Minimal pcapng yazıcısı (little-endian). Her link tipi için bir Interface
+/// Description Block açılır; zaman çözünürlüğü varsayılan mikro saniyedir.
+pub struct PcapngWriter {
+    buf: Vec<u8>,
+    interfaces: Vec<i32>,
+}
+
+impl PcapngWriter {
+    pub fn new() -> Self {
+        let mut writer = Self {
+            buf: Vec::new(),
+            interfaces: Vec::new(),
+        };
+        let mut body = Vec::with_capacity(16);
+        body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        body.extend_from_slice(&1u16.to_le_bytes()); // major
+        body.extend_from_slice(&0u16.to_le_bytes()); // minor
+        body.extend_from_slice(&(-1i64).to_le_bytes()); // section length: bilinmiyor
+        writer.block(BLOCK_SHB, &body);
+        writer
+    }
+
+    /// `link_type` için arayüz kimliği; ilk kullanımda IDB yazılır.
+    fn interface_id(&mut self, link_type: i32) -> u32 {
+        if let Some(id) = self.interfaces.iter().position(|l| *l == link_type) {
+            return id as u32;
+        }
+        let mut body = Vec::with_capacity(8);
+        body.extend_from_slice(&(link_type as u16).to_le_bytes());
+        body.extend_from_slice(&0u16.to_le_bytes()); // reserved
+        body.extend_from_slice(&0u32.to_le_bytes()); // snaplen: sınırsız
+        self.block(BLOCK_IDB, &body);
+        self.interfaces.push(link_type);
+        (self.interfaces.len() - 1) as u32
+    }
+
+    pub fn write_packet(&mut self, link_type: i32, ts_us: i64, orig_len: u32, data: &[u8]) {
+        let interface_id = self.interface_id(link_type);
+        let ts = ts_us.max(0) as u64;
+
+        let mut body = Vec::with_capacity(20 + data.len() + 3);
+        body.extend_from_slice(&interface_id.to_le_bytes());
+        body.extend_from_slice(&((ts >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(ts as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&orig_len.max(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(data);
+        body.resize(body.len().next_multiple_of(4), 0);
+        self.block(BLOCK_EPB, &body);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn block(&mut self, block_type: u32, body: &[u8]) {
+        let total_len = (12 + body.len()) as u32;
+        self.buf.extend_from_slice(&block_type.to_le_bytes());
+        self.buf.extend_from_slice(&total_len.to_le_bytes());
+        self.buf.extend_from_slice(body);
+        self.buf.extend_from_slice(&total_len.to_le_bytes());
+    }
+}